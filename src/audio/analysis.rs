@@ -0,0 +1,194 @@
+// src/audio/analysis.rs
+
+use crate::audio::window::{Window, WindowTables};
+use realfft::num_complex::Complex;
+use realfft::{RealFftPlanner, RealToComplex};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// Plan FFT d'une taille de trame et ses tampons de travail
+struct Plan {
+    fft: Arc<dyn RealToComplex<f32>>,
+    input: Vec<f32>,
+    output: Vec<Complex<f32>>,
+    scratch: Vec<Complex<f32>>,
+}
+
+/// Contexte d'analyse spectrale : plans FFT réelle → complexe et tampons conservés
+/// d'une trame à l'autre. Une fois une taille de trame rencontrée (ou préparée avec
+/// `prepare`), `analyze` n'alloue plus rien et peut tourner sur un thread temps réel.
+pub struct SpectrumAnalyzer {
+    planner: RealFftPlanner<f32>,
+    plans: HashMap<usize, Plan>,
+    window: WindowTables,
+    magnitudes: Vec<f32>,
+}
+
+impl Default for SpectrumAnalyzer {
+    fn default() -> Self {
+        SpectrumAnalyzer {
+            planner: RealFftPlanner::new(),
+            plans: HashMap::new(),
+            window: WindowTables::default(),
+            magnitudes: Vec::new(),
+        }
+    }
+}
+
+impl fmt::Debug for SpectrumAnalyzer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut sizes: Vec<_> = self.plans.keys().collect();
+        sizes.sort();
+        f.debug_struct("SpectrumAnalyzer")
+            .field("window", &self.window.window())
+            .field("sizes", &sizes)
+            .finish()
+    }
+}
+
+impl SpectrumAnalyzer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fenêtre appliquée aux trames avant la FFT (Hamming par défaut)
+    pub fn with_window(mut self, window: Window) -> Self {
+        self.window = WindowTables::new(window);
+        self
+    }
+
+    pub fn window(&self) -> Window {
+        self.window.window()
+    }
+
+    /// Planifie la FFT, réserve les tampons et calcule la fenêtre pour des trames de
+    /// `len` échantillons, afin que la première trame analysée n'alloue pas
+    pub fn prepare(&mut self, len: usize) {
+        if len == 0 {
+            return;
+        }
+        plan(&mut self.planner, &mut self.plans, len);
+        self.window.coefficients(len);
+        self.magnitudes.reserve(len / 2 + 1);
+    }
+
+    /// Spectre d'amplitude (bins 0 à N/2) de la trame fenêtrée
+    pub fn analyze(&mut self, frame: &[f32]) -> &[f32] {
+        self.magnitudes.clear();
+        if frame.is_empty() {
+            return &self.magnitudes;
+        }
+
+        let plan = plan(&mut self.planner, &mut self.plans, frame.len());
+        plan.input.copy_from_slice(frame);
+        let Plan {
+            fft,
+            input,
+            output,
+            scratch,
+        } = plan;
+        self.window.apply(input);
+        // Les tampons viennent du plan lui-même : leurs tailles sont toujours justes
+        fft.process_with_scratch(input, output, scratch)
+            .expect("tampons FFT de taille incorrecte");
+        self.magnitudes.extend(output.iter().map(|c| c.norm()));
+        &self.magnitudes
+    }
+
+    /// Spectre d'amplitude de la dernière trame analysée
+    pub fn magnitudes(&self) -> &[f32] {
+        &self.magnitudes
+    }
+}
+
+/// Plan de la taille `len`, créé avec ses tampons à la première demande
+fn plan<'a>(
+    planner: &mut RealFftPlanner<f32>,
+    plans: &'a mut HashMap<usize, Plan>,
+    len: usize,
+) -> &'a mut Plan {
+    plans.entry(len).or_insert_with(|| {
+        let fft = planner.plan_fft_forward(len);
+        Plan {
+            input: fft.make_input_vec(),
+            output: fft.make_output_vec(),
+            scratch: fft.make_scratch_vec(),
+            fft,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::realtime::{allocations_counted, count_allocations, CountingAllocator};
+    use std::f32::consts::PI;
+
+    // Allocateur du binaire de test, pour vérifier que l'analyse n'alloue plus
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    fn sine(len: usize, bin: usize) -> Vec<f32> {
+        (0..len)
+            .map(|n| (2.0 * PI * (bin * n) as f32 / len as f32).sin())
+            .collect()
+    }
+
+    #[test]
+    fn centred_sine_peaks_at_its_bin() {
+        let len = 1024;
+        for window in [Window::Rectangular, Window::Hann, Window::Hamming] {
+            let mut analyzer = SpectrumAnalyzer::new().with_window(window);
+            let magnitudes = analyzer.analyze(&sine(len, 64));
+            assert_eq!(magnitudes.len(), len / 2 + 1);
+
+            let peak = (0..magnitudes.len())
+                .max_by(|&a, &b| magnitudes[a].total_cmp(&magnitudes[b]))
+                .unwrap();
+            assert_eq!(peak, 64, "{:?}", window);
+            let expected = len as f32 / 2.0 * window.coherent_gain();
+            let error = (magnitudes[peak] - expected).abs() / expected;
+            assert!(
+                error < 0.01,
+                "{:?} : {} au lieu de {}",
+                window,
+                magnitudes[peak],
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn repeated_sizes_reuse_their_plan() {
+        let mut analyzer = SpectrumAnalyzer::new();
+        analyzer.analyze(&sine(1024, 10));
+        let first = Arc::clone(&analyzer.plans[&1024].fft);
+        analyzer.analyze(&sine(2048, 10));
+        analyzer.analyze(&sine(1024, 20));
+        assert_eq!(analyzer.plans.len(), 2);
+        assert!(Arc::ptr_eq(&first, &analyzer.plans[&1024].fft));
+        assert_eq!(analyzer.magnitudes().len(), 513);
+    }
+
+    #[test]
+    fn prepared_analysis_does_not_allocate() {
+        let frame = sine(2048, 100);
+        let mut analyzer = SpectrumAnalyzer::new().with_window(Window::BlackmanHarris);
+        analyzer.prepare(frame.len());
+        let (peak, allocations) = count_allocations(|| {
+            let mut peak = 0.0f32;
+            for _ in 0..3 {
+                peak = analyzer
+                    .analyze(&frame)
+                    .iter()
+                    .copied()
+                    .fold(peak, f32::max);
+            }
+            peak
+        });
+        assert!(allocations_counted());
+        assert_eq!(allocations, 0);
+        assert!(peak > 0.0);
+    }
+}
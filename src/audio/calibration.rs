@@ -0,0 +1,167 @@
+// src/audio/calibration.rs
+
+use crate::events::{Event, EventSink, NoteState};
+use crate::latency::LatencySummary;
+use crate::tuning::Tuning;
+use rodio::source::{SineWave, Source};
+use rodio::{OutputStream, Sink};
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Calibration en boucle : joue une tonalité connue sur la sortie audio et mesure le
+/// temps jusqu'à sa détection par le pipeline (sortie + trajet acoustique + capture + analyse)
+pub struct LoopbackCalibration {
+    note_number: u8,
+    /// Accord du pipeline, dans lequel la tonalité est jouée pour être reconnue comme
+    /// `note_number`
+    tuning: Tuning,
+    tone_duration: Duration,
+    period: Duration,
+    repetitions: usize,
+    /// Début de la tonalité en cours, pas encore détectée
+    pending: Mutex<Option<Instant>>,
+    measurements: Mutex<Vec<Duration>>,
+}
+
+impl LoopbackCalibration {
+    /// Tonalité de `note_number` (A4 = 69 par défaut) dans l'accord `tuning`,
+    /// répétée `repetitions` fois
+    pub fn new(note_number: u8, repetitions: usize, tuning: Tuning) -> Arc<Self> {
+        Arc::new(LoopbackCalibration {
+            note_number,
+            tuning,
+            tone_duration: Duration::from_millis(300),
+            period: Duration::from_secs(2),
+            repetitions,
+            pending: Mutex::new(None),
+            measurements: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Lance la lecture des tonalités via rodio dans un thread dédié
+    pub fn start(self: &Arc<Self>) -> Result<(), Box<dyn Error>> {
+        let frequency = self
+            .tone_frequency()
+            .ok_or("Note de calibration hors de l'accord")?;
+        let calibration = Arc::clone(self);
+
+        thread::spawn(move || {
+            // Le flux de sortie n'est pas `Send` : il doit être créé dans ce thread
+            let (_stream, handle) = match OutputStream::try_default() {
+                Ok(output) => output,
+                Err(err) => {
+                    log::error!("Calibration impossible, pas de sortie audio : {}", err);
+                    return;
+                }
+            };
+
+            for _ in 0..calibration.repetitions {
+                let sink = match Sink::try_new(&handle) {
+                    Ok(sink) => sink,
+                    Err(err) => {
+                        log::error!("Calibration impossible : {}", err);
+                        return;
+                    }
+                };
+                *calibration.pending.lock().unwrap() = Some(Instant::now());
+                sink.append(
+                    SineWave::new(frequency)
+                        .take_duration(calibration.tone_duration)
+                        .amplify(0.5),
+                );
+                sink.sleep_until_end();
+                thread::sleep(calibration.period.saturating_sub(calibration.tone_duration));
+                *calibration.pending.lock().unwrap() = None;
+            }
+
+            match calibration.summary() {
+                Some(summary) => log::info!("Calibration en boucle : {}", summary),
+                None => log::warn!("Calibration en boucle : tonalité jamais détectée"),
+            }
+        });
+        Ok(())
+    }
+
+    /// Fréquence de la tonalité, `None` si la note n'a pas de touche dans l'accord
+    pub fn tone_frequency(&self) -> Option<f32> {
+        self.tuning.frequency(self.note_number)
+    }
+
+    /// À appeler à chaque note détectée : mesure le délai si c'est la tonalité attendue
+    pub fn on_detection(&self, note_number: u8, detected_at: Instant) {
+        if note_number != self.note_number {
+            return;
+        }
+        if let Some(started) = self.pending.lock().unwrap().take() {
+            let delay = detected_at.saturating_duration_since(started);
+            log::info!(
+                "Calibration : tonalité détectée après {:.1} ms",
+                delay.as_secs_f64() * 1000.0
+            );
+            self.measurements.lock().unwrap().push(delay);
+        }
+    }
+
+    pub fn summary(&self) -> Option<LatencySummary> {
+        LatencySummary::from_measurements(&self.measurements.lock().unwrap())
+    }
+}
+
+/// Abonné au bus : compare chaque Note On à la tonalité de calibration
+impl EventSink for Arc<LoopbackCalibration> {
+    fn handle(&mut self, event: &Event) {
+        let Event::Note(note) = event else {
+            return;
+        };
+        if note.state == NoteState::On {
+            let detected_at = note.timestamps.detected.unwrap_or(note.timestamps.captured);
+            self.on_detection(note.note, detected_at);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tuning::{KeyboardMapping, Scale};
+
+    #[test]
+    fn tone_is_played_in_the_pipeline_tuning() {
+        let calibration = LoopbackCalibration::new(69, 1, Tuning::equal(432.0));
+        assert_eq!(calibration.tone_frequency(), Some(432.0));
+
+        // Gamme de quarte et quinte sur le Do 4 : le La 4 n'a pas de touche
+        let scale = Scale {
+            description: "Quarte et quinte".to_string(),
+            ratios: vec![4.0 / 3.0, 3.0 / 2.0, 2.0],
+        };
+        let mut mapping = KeyboardMapping::linear(440.0);
+        mapping.keys = vec![Some(0), None, None, None, None, Some(1), None, Some(2)];
+        mapping.keys.resize(12, None);
+        mapping.reference_note = 60;
+        mapping.reference_frequency = 261.6;
+        mapping.octave_degree = Some(3);
+        let tuning = Tuning::from_scale(&scale, &mapping);
+        assert_eq!(tuning.frequency(69), None);
+
+        let calibration = LoopbackCalibration::new(69, 1, tuning);
+        assert_eq!(calibration.tone_frequency(), None);
+    }
+
+    #[test]
+    fn only_the_pending_calibration_note_is_measured() {
+        let calibration = LoopbackCalibration::new(69, 1, Tuning::equal(432.0));
+        let started = Instant::now();
+        *calibration.pending.lock().unwrap() = Some(started);
+        calibration.on_detection(70, started + Duration::from_millis(20));
+        assert!(calibration.summary().is_none());
+        calibration.on_detection(69, started + Duration::from_millis(40));
+        calibration.on_detection(69, started + Duration::from_millis(90));
+        assert_eq!(
+            calibration.measurements.lock().unwrap().as_slice(),
+            &[Duration::from_millis(40)]
+        );
+    }
+}
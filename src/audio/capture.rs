@@ -2,171 +2,818 @@
 
 use libpulse_binding as pulse;
 
+use pulse::callbacks::ListResult;
+use pulse::context::subscribe::{Facility, InterestMaskSet, Operation as SubscribeOperation};
 use pulse::context::{Context, FlagSet as ContextFlagSet};
+use pulse::def::BufferAttr;
+use pulse::error::{Code, PAErr};
+use pulse::operation::State as OperationState;
 use pulse::mainloop::standard::{IterateResult, Mainloop};
 use pulse::proplist::Proplist;
 use pulse::sample::{Format, Spec};
-use pulse::stream::{PeekResult, State as StreamState, Stream, FlagSet as StreamFlagSet};
-use std::sync::mpsc::Sender;
-use std::thread;
-use hound;
+use pulse::stream::{Latency, PeekResult, State as StreamState, Stream, FlagSet as StreamFlagSet};
+use pulse::time::MicroSeconds;
+use std::cell::Cell;
+use std::error::Error;
+use std::fmt;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{sync_channel, Sender, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use crate::audio::realtime::ThreadConfig;
+use crate::audio::ring::RingSender;
+use crate::audio::tee::{CaptureTee, TeeConfig};
+use crate::latency::Timestamps;
+
+/// Bloc d'échantillons entrelacés transmis par la capture, avec ses horodatages
+#[derive(Debug, Clone)]
+pub struct AudioBuffer {
+    pub samples: Vec<i16>,
+    pub timestamps: Timestamps,
+}
+
+/// Erreurs pouvant survenir lors de la capture PulseAudio
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CaptureError {
+    /// Impossible d'allouer les objets PulseAudio (Proplist, Mainloop, Context, Stream)
+    Initialization(&'static str),
+    /// Le serveur PulseAudio est injoignable ou a fermé la connexion
+    ServerUnavailable(String),
+    /// La source demandée n'existe pas sur le serveur
+    SourceNotFound(String),
+    /// Le Stream a échoué pour une autre raison
+    StreamFailed(String),
+    /// Le mainloop a retourné une erreur pendant l'itération
+    Mainloop,
+    /// Le thread de capture s'est terminé de façon inattendue (panique)
+    ThreadPanicked,
+}
+
+impl fmt::Display for CaptureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CaptureError::Initialization(what) => write!(f, "Impossible de créer le {}", what),
+            CaptureError::ServerUnavailable(reason) => {
+                write!(f, "Serveur PulseAudio indisponible : {}", reason)
+            }
+            CaptureError::SourceNotFound(source) => write!(f, "Source introuvable : {}", source),
+            CaptureError::StreamFailed(reason) => write!(f, "Le Stream a échoué : {}", reason),
+            CaptureError::Mainloop => write!(f, "Erreur lors de l'itération du mainloop"),
+            CaptureError::ThreadPanicked => write!(f, "Le thread de capture a paniqué"),
+        }
+    }
+}
+
+impl Error for CaptureError {}
+
+/// État courant du thread de capture, consultable depuis le `CaptureHandle`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CaptureStatus {
+    Starting,
+    Running,
+    /// Source perdue, reconnexion en attente
+    Reconnecting,
+    /// Arrêt normal (récepteur déconnecté)
+    Stopped,
+    Failed(CaptureError),
+}
+
+/// Événements d'état publiés par le thread de capture vers le reste du pipeline
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CaptureEvent {
+    /// Stream connecté ; `fallback` indique que c'est la source par défaut du serveur
+    Connected { source: String, fallback: bool },
+    /// La source ou le serveur a été perdu
+    Disconnected(CaptureError),
+    /// Nouvelle tentative de connexion après `delay`
+    Reconnecting { attempt: u32, delay: Duration },
+    /// Une source a été branchée sur le serveur pendant l'attente
+    SourceAppeared,
+    /// Nombre maximal de tentatives atteint, la capture s'arrête
+    GaveUp(CaptureError),
+    /// Mesure périodique de la latence de capture côté serveur
+    Latency { measured: Duration, target: Duration },
+}
+
+/// Réglages de latence du Stream d'enregistrement
+#[derive(Debug, Clone)]
+pub struct LatencyConfig {
+    /// Budget de latence visé : détermine la taille des fragments (`fragsize`)
+    pub target: Duration,
+    /// Laisse le serveur ajuster la latence de la source pour tenir le budget
+    pub adjust_latency: bool,
+    /// Interpole les informations de temps entre deux mises à jour du serveur
+    pub interpolate_timing: bool,
+    /// Période de publication des mesures de latence (`CaptureEvent::Latency`)
+    pub report_interval: Duration,
+}
+
+impl LatencyConfig {
+    pub fn new(target: Duration) -> Self {
+        LatencyConfig {
+            target,
+            adjust_latency: true,
+            interpolate_timing: true,
+            report_interval: Duration::from_secs(1),
+        }
+    }
+
+    /// Attributs de tampon correspondant au budget : seuls `fragsize` et `maxlength`
+    /// concernent l'enregistrement, les autres sont laissés au choix du serveur
+    fn buffer_attr(&self, spec: &Spec) -> BufferAttr {
+        let fragsize = spec.usec_to_bytes(MicroSeconds(self.target.as_micros() as u64)) as u32;
+        BufferAttr {
+            maxlength: fragsize.saturating_mul(4),
+            tlength: u32::MAX,
+            prebuf: u32::MAX,
+            minreq: u32::MAX,
+            fragsize,
+        }
+    }
+
+    fn flags(&self) -> StreamFlagSet {
+        let mut flags = StreamFlagSet::NOFLAGS;
+        if self.adjust_latency {
+            flags |= StreamFlagSet::ADJUST_LATENCY;
+        }
+        if self.interpolate_timing {
+            flags |= StreamFlagSet::INTERPOLATE_TIMING | StreamFlagSet::AUTO_TIMING_UPDATE;
+        }
+        flags
+    }
+}
+
+/// Politique de reconnexion lorsque la source disparaît (ex. périphérique Bluetooth)
+#[derive(Debug, Clone)]
+pub struct ReconnectPolicy {
+    /// Délai avant la première tentative, doublé à chaque échec
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    /// `None` pour réessayer indéfiniment
+    pub max_attempts: Option<u32>,
+    /// Se rabattre sur la source par défaut du serveur si la source demandée est absente
+    pub fallback_to_default: bool,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        ReconnectPolicy {
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+            max_attempts: None,
+            fallback_to_default: true,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Délai d'attente avant la tentative `attempt` (à partir de 1)
+    fn delay(&self, attempt: u32) -> Duration {
+        let factor = 1u32 << attempt.saturating_sub(1).min(16);
+        self.initial_delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
+
+/// Demande d'arrêt de la capture, utilisable depuis n'importe quel thread
+#[derive(Debug, Clone, Default)]
+pub struct CaptureStop(Arc<AtomicBool>);
+
+impl CaptureStop {
+    /// Termine la capture après le bloc en cours : la copie est finalisée et le canal se
+    /// ferme, ce qui arrête le traitement et ses abonnés dans l'ordre
+    pub fn stop(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    fn is_stopped(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Poignée sur un thread de capture démarré
+pub struct CaptureHandle {
+    thread: JoinHandle<Result<(), CaptureError>>,
+    status: Arc<Mutex<CaptureStatus>>,
+    stop: CaptureStop,
+}
+
+impl CaptureHandle {
+    /// Permet d'arrêter la capture depuis un autre thread, `join` consommant la poignée
+    pub fn stopper(&self) -> CaptureStop {
+        self.stop.clone()
+    }
+
+    pub fn status(&self) -> CaptureStatus {
+        self.status.lock().unwrap().clone()
+    }
+
+    pub fn is_running(&self) -> bool {
+        !self.thread.is_finished()
+    }
+
+    /// Attend la fin du thread de capture et retourne son résultat
+    pub fn join(self) -> Result<(), CaptureError> {
+        self.thread.join().unwrap_or(Err(CaptureError::ThreadPanicked))
+    }
+}
 
 pub struct AudioCapturer {
     source_name: String,
+    tee: Option<TeeConfig>,
+    reconnect: Option<ReconnectPolicy>,
+    latency: Option<LatencyConfig>,
+    events: Option<Sender<CaptureEvent>>,
+    thread: ThreadConfig,
+    spec: Spec,
 }
 
 impl AudioCapturer {
     pub fn new(source_name: &str) -> Self {
         AudioCapturer {
             source_name: source_name.to_string(),
+            tee: None,
+            reconnect: None,
+            latency: None,
+            events: None,
+            thread: ThreadConfig::default(),
+            spec: CAPTURE_SPEC,
         }
     }
 
-    pub fn start(&self, sender: Sender<Vec<i16>>) {
+    /// Fréquence d'échantillonnage demandée au serveur (44100 Hz par défaut)
+    pub fn with_sample_rate(mut self, rate: u32) -> Self {
+        self.spec.rate = rate;
+        self
+    }
+
+    /// Active la copie brute des échantillons capturés vers des fichiers
+    pub fn with_tee(mut self, config: TeeConfig) -> Self {
+        self.tee = Some(config);
+        self
+    }
+
+    /// Active la reconnexion automatique lorsque la source ou le serveur disparaît
+    pub fn with_reconnect(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect = Some(policy);
+        self
+    }
+
+    /// Fixe les attributs de tampon et les options de temps du Stream ; sans cet appel,
+    /// le serveur choisit la taille des fragments et la latence n'est pas bornée
+    pub fn with_latency(mut self, config: LatencyConfig) -> Self {
+        self.latency = Some(config);
+        self
+    }
+
+    /// Publie les changements d'état de la capture sur ce canal
+    pub fn with_events(mut self, events: Sender<CaptureEvent>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Priorité temps réel et cœurs du thread de capture
+    pub fn with_thread(mut self, config: ThreadConfig) -> Self {
+        self.thread = config;
+        self
+    }
+
+    /// Démarre la capture dans un thread dédié.
+    ///
+    /// Retourne une erreur si la connexion initiale au serveur ou à la source échoue ;
+    /// les erreurs survenant ensuite sont récupérables via `CaptureHandle::join`.
+    pub fn start(&self, sender: RingSender<AudioBuffer>) -> Result<CaptureHandle, CaptureError> {
         let source_name = self.source_name.clone();
-        thread::spawn(move || {
-            // Le code de capture audio
-
-            // Créer le Proplist
-            let mut proplist = Proplist::new().unwrap();
-            proplist
-                .set_str(
-                    pulse::proplist::properties::APPLICATION_NAME,
-                    "Audio Capture",
-                )
-                .unwrap();
-
-            // Créer le Mainloop et le Context
-            let mut mainloop = Mainloop::new().expect("Impossible de créer le Mainloop");
-            let mut context =
-                Context::new_with_proplist(&mainloop, "Audio Capture Context", &proplist)
-                    .expect("Impossible de créer le Context");
-
-            // Se connecter au serveur PulseAudio
-            context
-                .connect(None, ContextFlagSet::NOFLAGS, None)
-                .expect("Impossible de se connecter au serveur PulseAudio");
-
-            // Attendre que le contexte soit prêt
-            loop {
-                match mainloop.iterate(false) {
-                    IterateResult::Success(_) => match context.get_state() {
-                        pulse::context::State::Ready => break,
-                        pulse::context::State::Failed | pulse::context::State::Terminated => {
-                            eprintln!("Le contexte PulseAudio a échoué ou a été terminé");
-                            return;
+        let tee_config = self.tee.clone();
+        let reconnect = self.reconnect.clone();
+        let latency = self.latency.clone();
+        let events = self.events.clone();
+        let thread_config = self.thread.clone();
+        let spec = self.spec;
+        let status = Arc::new(Mutex::new(CaptureStatus::Starting));
+        let stop = CaptureStop::default();
+        let (ready_sender, ready_receiver) = sync_channel(1);
+
+        let thread_status = Arc::clone(&status);
+        let thread_stop = stop.clone();
+        let thread = thread::spawn(move || {
+            thread_config.apply_or_warn("capture");
+            let mut capture = CaptureThread {
+                source_name,
+                spec,
+                reconnect,
+                latency,
+                sender,
+                events,
+                ready: Some(ready_sender),
+                status: Arc::clone(&thread_status),
+                stop: thread_stop,
+                tee: tee_config.map(|config| {
+                    CaptureTee::new(config, spec.channels as u16, spec.rate)
+                }),
+            };
+            let result = capture.run();
+            *thread_status.lock().unwrap() = match &result {
+                Ok(()) => CaptureStatus::Stopped,
+                Err(err) => CaptureStatus::Failed(err.clone()),
+            };
+            result
+        });
+
+        // Attendre que le Stream soit prêt (ou que l'initialisation échoue)
+        match ready_receiver.recv() {
+            Ok(Ok(())) => Ok(CaptureHandle {
+                thread,
+                status,
+                stop,
+            }),
+            Ok(Err(err)) => {
+                let _ = thread.join();
+                Err(err)
+            }
+            Err(_) => {
+                let _ = thread.join();
+                Err(CaptureError::ThreadPanicked)
+            }
+        }
+    }
+}
+
+/// Spécification par défaut du format audio capturé
+const CAPTURE_SPEC: Spec = Spec {
+    format: Format::S16le,
+    channels: 2,
+    rate: 44100,
+};
+
+/// Raison de la sortie de la boucle de lecture sans erreur
+enum LoopExit {
+    /// Le récepteur a été déconnecté : arrêt normal
+    ReceiverClosed,
+    /// Arrêt demandé par `CaptureStop`
+    Stopped,
+    /// La source demandée est revenue alors qu'on utilisait la source par défaut
+    PreferredSourceBack,
+}
+
+/// État du thread de capture, conservé d'une connexion à l'autre
+struct CaptureThread {
+    source_name: String,
+    spec: Spec,
+    reconnect: Option<ReconnectPolicy>,
+    latency: Option<LatencyConfig>,
+    sender: RingSender<AudioBuffer>,
+    events: Option<Sender<CaptureEvent>>,
+    /// Canal de démarrage, consommé à la première connexion réussie
+    ready: Option<SyncSender<Result<(), CaptureError>>>,
+    status: Arc<Mutex<CaptureStatus>>,
+    stop: CaptureStop,
+    tee: Option<CaptureTee>,
+}
+
+impl CaptureThread {
+    fn run(&mut self) -> Result<(), CaptureError> {
+        let result = self.run_connections();
+
+        // Finaliser la copie
+        if let Some(mut writer) = self.tee.take() {
+            if let Err(err) = writer.finalize() {
+                log::error!("Erreur lors de la finalisation de la copie : {}", err);
+            }
+        }
+        result
+    }
+
+    /// Boucle de (re)connexion : chaque itération ouvre un Stream et le lit jusqu'à sa perte
+    fn run_connections(&mut self) -> Result<(), CaptureError> {
+        let mut attempt = 0;
+        loop {
+            let error = match self.connect() {
+                Ok((mut connection, fallback)) => {
+                    attempt = 0;
+                    self.set_status(CaptureStatus::Running);
+                    let source = connection
+                        .stream
+                        .get_device_name()
+                        .map(|name| name.into_owned())
+                        .unwrap_or_default();
+                    log::info!("Capture connectée à la source {}", source);
+                    self.notify(CaptureEvent::Connected { source, fallback });
+                    if let Some(ready) = self.ready.take() {
+                        let _ = ready.send(Ok(()));
+                    }
+
+                    match self.read_stream(&mut connection, fallback) {
+                        Ok(LoopExit::ReceiverClosed | LoopExit::Stopped) => return Ok(()),
+                        Ok(LoopExit::PreferredSourceBack) => {
+                            log::info!("La source {} est de retour", self.source_name);
+                            continue;
+                        }
+                        Err(err) => {
+                            log::warn!("Capture interrompue : {}", err);
+                            self.notify(CaptureEvent::Disconnected(err.clone()));
+                            err
                         }
-                        _ => {}
-                    },
-                    IterateResult::Quit(_) | IterateResult::Err(_) => {
-                        eprintln!("Erreur lors de l'itération du mainloop");
-                        return;
                     }
                 }
-            }
+                Err(err) => {
+                    // Échec de la connexion initiale : remonté directement à `start`
+                    if let Some(ready) = self.ready.take() {
+                        let _ = ready.send(Err(err.clone()));
+                        return Err(err);
+                    }
+                    err
+                }
+            };
 
-            // Spécification du format audio
-            let spec = Spec {
-                format: Format::S16le,
-                channels: 2,
-                rate: 44100,
+            let Some(policy) = self.reconnect.clone() else {
+                return Err(error);
             };
-            assert!(spec.is_valid());
-
-            // Créer un nouveau Stream audio
-            let mut stream =
-                Stream::new(&mut context, "Audio Capture Stream", &spec, None)
-                    .expect("Impossible de créer le Stream");
-
-            // Connecter le Stream en mode enregistrement
-            stream
-                .connect_record(Some(&source_name), None, StreamFlagSet::NOFLAGS)
-                .expect("Impossible de connecter le Stream pour l'enregistrement");
-
-            // Attendre que le Stream soit prêt
-            loop {
-                match mainloop.iterate(false) {
-                    IterateResult::Success(_) => match stream.get_state() {
-                        StreamState::Ready => break,
-                        StreamState::Failed | StreamState::Terminated => {
-                            eprintln!("Le Stream a échoué ou a été terminé");
-                            return;
+            attempt += 1;
+            if policy.max_attempts.is_some_and(|max| attempt > max) {
+                self.notify(CaptureEvent::GaveUp(error.clone()));
+                return Err(error);
+            }
+
+            let delay = policy.delay(attempt);
+            log::info!("Reconnexion de la capture dans {:?} (tentative {})", delay, attempt);
+            self.set_status(CaptureStatus::Reconnecting);
+            self.notify(CaptureEvent::Reconnecting { attempt, delay });
+            if wait_for_new_source(delay, &self.stop) {
+                self.notify(CaptureEvent::SourceAppeared);
+            }
+            if self.stop.is_stopped() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Ouvre la source demandée, ou la source par défaut si elle est absente et que la
+    /// politique de reconnexion l'autorise. Le booléen indique l'utilisation du repli.
+    fn connect(&self) -> Result<(Connection, bool), CaptureError> {
+        let latency = self.latency.as_ref();
+        match Connection::open(Some(&self.source_name), &self.spec, latency) {
+            Ok(connection) => Ok((connection, false)),
+            Err(CaptureError::SourceNotFound(_))
+                if self.reconnect.as_ref().is_some_and(|p| p.fallback_to_default) =>
+            {
+                log::warn!(
+                    "Source {} introuvable, repli sur la source par défaut",
+                    self.source_name
+                );
+                Connection::open(None, &self.spec, latency).map(|connection| (connection, true))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Boucle principale de capture sur un Stream connecté
+    fn read_stream(
+        &mut self,
+        connection: &mut Connection,
+        fallback: bool,
+    ) -> Result<LoopExit, CaptureError> {
+        let mut last_report = Instant::now();
+        loop {
+            if self.stop.is_stopped() {
+                log::info!("Arrêt de la capture demandé");
+                return Ok(LoopExit::Stopped);
+            }
+            match connection.mainloop.iterate(true) {
+                IterateResult::Success(_) => {
+                    if let StreamState::Failed | StreamState::Terminated = connection.stream.get_state() {
+                        return Err(stream_error(connection.context.errno(), &self.source_name));
+                    }
+                    if let pulse::context::State::Failed | pulse::context::State::Terminated =
+                        connection.context.get_state()
+                    {
+                        return Err(CaptureError::ServerUnavailable(format!(
+                            "{}",
+                            connection.context.errno()
+                        )));
+                    }
+
+                    // Sur la source par défaut, revenir à la source demandée dès qu'elle réapparaît
+                    if fallback
+                        && connection.source_added.replace(false)
+                        && connection.has_source(&self.source_name)
+                    {
+                        return Ok(LoopExit::PreferredSourceBack);
+                    }
+
+                    if let Some(latency) = &self.latency {
+                        if last_report.elapsed() >= latency.report_interval {
+                            last_report = Instant::now();
+                            self.report_latency(connection, latency.target);
                         }
-                        _ => {}
-                    },
-                    IterateResult::Quit(_) | IterateResult::Err(_) => {
-                        eprintln!("Erreur lors de l'itération du mainloop");
-                        return;
                     }
-                }
-            }
 
-            // Configuration du writer WAV
-            let wav_spec = hound::WavSpec {
-                channels: 2,
-                sample_rate: 44100,
-                bits_per_sample: 16,
-                sample_format: hound::SampleFormat::Int,
-            };
+                    // Lire les données du Stream
+                    if connection.stream.readable_size().is_some() {
+                        match connection.stream.peek() {
+                            Ok(PeekResult::Data(data)) => {
+                                let samples = unsafe {
+                                    std::slice::from_raw_parts(
+                                        data.as_ptr() as *const i16,
+                                        data.len() / std::mem::size_of::<i16>(),
+                                    )
+                                };
 
-            let mut wav_writer = hound::WavWriter::create("enregistrement.wav", wav_spec)
-                .expect("Impossible de créer le fichier WAV");
-
-            // Boucle principale de capture
-            loop {
-                match mainloop.iterate(true) {
-                    IterateResult::Success(_) => {
-                        // Lire les données du Stream
-                        if let Some(_) = stream.readable_size() {
-                            match stream.peek() {
-                                Ok(PeekResult::Data(data)) => {
-                                    let samples = unsafe {
-                                        std::slice::from_raw_parts(
-                                            data.as_ptr() as *const i16,
-                                            data.len() / std::mem::size_of::<i16>(),
-                                        )
-                                    };
-
-                                    // Envoyer les échantillons au processeur
-                                    if sender.send(samples.to_vec()).is_err() {
-                                        eprintln!("Le récepteur a été déconnecté");
-                                        break;
-                                    }
+                                // Envoyer les échantillons au processeur, horodatés à leur
+                                // entrée dans le micro (instant de lecture moins la latence)
+                                let buffer = AudioBuffer {
+                                    samples: samples.to_vec(),
+                                    timestamps: Timestamps::new(capture_instant(&connection.stream)),
+                                };
+                                if self.sender.send(buffer).is_err() {
+                                    log::info!("Le récepteur a été déconnecté");
+                                    return Ok(LoopExit::ReceiverClosed);
+                                }
 
-                                    // Écrire les échantillons dans le fichier WAV
-                                    for &sample in samples {
-                                        wav_writer
-                                            .write_sample(sample)
-                                            .expect("Erreur lors de l'écriture du fichier WAV");
+                                // Écrire les échantillons dans la copie ; en cas d'erreur,
+                                // la copie est désactivée mais la capture continue
+                                if let Some(writer) = self.tee.as_mut() {
+                                    if let Err(err) = writer.write(samples) {
+                                        log::error!(
+                                            "Erreur lors de l'écriture de la copie, copie désactivée : {}",
+                                            err
+                                        );
+                                        let _ = writer.finalize();
+                                        self.tee = None;
                                     }
-
-                                    stream.discard().unwrap();
                                 }
-                                Ok(_) => {}
-                                Err(err) => {
-                                    eprintln!(
-                                        "Erreur lors de la lecture des données : {:?}",
-                                        err
-                                    );
+
+                                if let Err(err) = connection.stream.discard() {
+                                    return Err(CaptureError::StreamFailed(format!("{}", err)));
                                 }
                             }
+                            Ok(_) => {}
+                            Err(err) => {
+                                log::warn!("Erreur lors de la lecture des données : {:?}", err);
+                            }
                         }
                     }
-                    IterateResult::Quit(_) | IterateResult::Err(_) => {
-                        eprintln!("Erreur lors de l'itération du mainloop");
-                        break;
+                }
+                IterateResult::Quit(_) | IterateResult::Err(_) => return Err(CaptureError::Mainloop),
+            }
+        }
+    }
+
+    /// Publie la latence actuelle du Stream et signale un dépassement du budget
+    fn report_latency(&self, connection: &Connection, target: Duration) {
+        let measured = match connection.stream.get_latency() {
+            Ok(Latency::Positive(usec)) => Duration::from_micros(usec.0),
+            Ok(Latency::Negative(_)) | Ok(Latency::None) => Duration::ZERO,
+            Err(_) => return,
+        };
+        if measured > target {
+            log::warn!(
+                "Latence de capture {:.1} ms au-delà du budget de {:.1} ms",
+                measured.as_secs_f64() * 1000.0,
+                target.as_secs_f64() * 1000.0
+            );
+        }
+        self.notify(CaptureEvent::Latency { measured, target });
+    }
+
+    fn set_status(&self, status: CaptureStatus) {
+        *self.status.lock().unwrap() = status;
+    }
+
+    fn notify(&self, event: CaptureEvent) {
+        if let Some(events) = &self.events {
+            let _ = events.send(event);
+        }
+    }
+}
+
+/// Connexion PulseAudio ouverte sur une source.
+///
+/// L'ordre des champs est celui de leur destruction : le Stream avant le Context,
+/// le Context avant le Mainloop.
+struct Connection {
+    stream: Stream,
+    context: Context,
+    mainloop: Mainloop,
+    /// Positionné par l'abonnement aux événements lorsqu'une source est ajoutée
+    source_added: Rc<Cell<bool>>,
+}
+
+impl Connection {
+    /// Se connecte au serveur puis ouvre un Stream d'enregistrement sur la source
+    /// (`None` : source par défaut du serveur)
+    fn open(
+        source_name: Option<&str>,
+        spec: &Spec,
+        latency: Option<&LatencyConfig>,
+    ) -> Result<Self, CaptureError> {
+        let (mut mainloop, mut context, source_added) = connect_context()?;
+        let name = source_name.unwrap_or("(défaut)");
+
+        // Créer un nouveau Stream audio
+        let mut stream = Stream::new(&mut context, "Audio Capture Stream", spec, None)
+            .ok_or(CaptureError::Initialization("Stream"))?;
+
+        // Connecter le Stream en mode enregistrement, avec les attributs de tampon
+        // du budget de latence s'il est configuré
+        let buffer_attr = latency.map(|config| config.buffer_attr(spec));
+        let flags = latency.map_or(StreamFlagSet::NOFLAGS, LatencyConfig::flags);
+        stream
+            .connect_record(source_name, buffer_attr.as_ref(), flags)
+            .map_err(|err| stream_error(err, name))?;
+
+        // Attendre que le Stream soit prêt
+        loop {
+            match mainloop.iterate(false) {
+                IterateResult::Success(_) => match stream.get_state() {
+                    StreamState::Ready => break,
+                    StreamState::Failed | StreamState::Terminated => {
+                        return Err(stream_error(context.errno(), name));
                     }
+                    _ => {}
+                },
+                IterateResult::Quit(_) | IterateResult::Err(_) => {
+                    return Err(CaptureError::Mainloop)
+                }
+            }
+        }
+
+        // Taille de fragment réellement accordée par le serveur
+        if let Some(attr) = stream.get_buffer_attr() {
+            log::debug!(
+                "Tampon de capture : fragsize {} octets ({:.1} ms)",
+                attr.fragsize,
+                spec.bytes_to_usec(attr.fragsize as u64).0 as f64 / 1000.0
+            );
+        }
+
+        Ok(Connection {
+            stream,
+            context,
+            mainloop,
+            source_added,
+        })
+    }
+
+    /// Interroge le serveur pour savoir si une source de ce nom existe
+    fn has_source(&mut self, source_name: &str) -> bool {
+        let found = Rc::new(Cell::new(false));
+        let found_in_callback = Rc::clone(&found);
+        let operation = self
+            .context
+            .introspect()
+            .get_source_info_by_name(source_name, move |result| {
+                if let ListResult::Item(_) = result {
+                    found_in_callback.set(true);
                 }
+            });
+        while operation.get_state() == OperationState::Running {
+            if let IterateResult::Quit(_) | IterateResult::Err(_) = self.mainloop.iterate(true) {
+                return false;
             }
+        }
+        found.get()
+    }
+}
 
-            // Fermer le writer WAV
-            wav_writer
-                .finalize()
-                .expect("Erreur lors de la fermeture du fichier WAV");
+impl Drop for Connection {
+    fn drop(&mut self) {
+        // Arrêter le Stream et le Mainloop
+        let _ = self.stream.disconnect();
+        self.context.disconnect();
+        self.mainloop.quit(pulse::def::Retval(0));
+    }
+}
 
-            // Arrêter le Stream et le Mainloop
-            stream.disconnect().unwrap();
-            mainloop.quit(pulse::def::Retval(0));
-        });
+/// Crée le Mainloop et un Context connecté et abonné aux ajouts de sources
+fn connect_context() -> Result<(Mainloop, Context, Rc<Cell<bool>>), CaptureError> {
+    // Créer le Proplist
+    let mut proplist = Proplist::new().ok_or(CaptureError::Initialization("Proplist"))?;
+    proplist
+        .set_str(pulse::proplist::properties::APPLICATION_NAME, "Audio Capture")
+        .map_err(|_| CaptureError::Initialization("Proplist"))?;
+
+    // Créer le Mainloop et le Context
+    let mut mainloop = Mainloop::new().ok_or(CaptureError::Initialization("Mainloop"))?;
+    let mut context = Context::new_with_proplist(&mainloop, "Audio Capture Context", &proplist)
+        .ok_or(CaptureError::Initialization("Context"))?;
+
+    // Se connecter au serveur PulseAudio
+    context
+        .connect(None, ContextFlagSet::NOFLAGS, None)
+        .map_err(|err| CaptureError::ServerUnavailable(format!("{}", err)))?;
+
+    // Attendre que le contexte soit prêt
+    loop {
+        match mainloop.iterate(false) {
+            IterateResult::Success(_) => match context.get_state() {
+                pulse::context::State::Ready => break,
+                pulse::context::State::Failed | pulse::context::State::Terminated => {
+                    return Err(CaptureError::ServerUnavailable(format!("{}", context.errno())));
+                }
+                _ => {}
+            },
+            IterateResult::Quit(_) | IterateResult::Err(_) => return Err(CaptureError::Mainloop),
+        }
+    }
+
+    // Détection de branchement à chaud : être prévenu des nouvelles sources
+    let source_added = Rc::new(Cell::new(false));
+    let flag = Rc::clone(&source_added);
+    context.set_subscribe_callback(Some(Box::new(move |facility, operation, _index| {
+        if facility == Some(Facility::Source) && operation == Some(SubscribeOperation::New) {
+            flag.set(true);
+        }
+    })));
+    context.subscribe(InterestMaskSet::SOURCE, |_| {});
+
+    Ok((mainloop, context, source_added))
+}
+
+/// Attend `delay` avant une nouvelle tentative, en rendant la main plus tôt si une
+/// source est branchée sur le serveur (retourne alors `true`) ou si la capture est arrêtée
+fn wait_for_new_source(delay: Duration, stop: &CaptureStop) -> bool {
+    let deadline = Instant::now() + delay;
+    let Ok((mut mainloop, mut context, source_added)) = connect_context() else {
+        // Serveur injoignable : simple attente
+        sleep_until(deadline, stop);
+        return false;
+    };
+
+    let appeared = loop {
+        if source_added.get() {
+            break true;
+        }
+        let now = Instant::now();
+        if now >= deadline || stop.is_stopped() {
+            break false;
+        }
+        if let IterateResult::Quit(_) | IterateResult::Err(_) = mainloop.iterate(false) {
+            sleep_until(deadline, stop);
+            break false;
+        }
+        thread::sleep(WAIT_POLL_INTERVAL.min(deadline - now));
+    };
+    context.disconnect();
+    appeared
+}
+
+/// Attend jusqu'à `deadline`, par pas de `WAIT_POLL_INTERVAL` pour s'interrompre dès l'arrêt
+fn sleep_until(deadline: Instant, stop: &CaptureStop) {
+    loop {
+        let now = Instant::now();
+        if now >= deadline || stop.is_stopped() {
+            return;
+        }
+        thread::sleep(WAIT_POLL_INTERVAL.min(deadline - now));
+    }
+}
+
+/// Période de scrutation des événements pendant l'attente d'une reconnexion
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Estime l'instant d'entrée dans le micro des données lues, d'après la latence du Stream
+fn capture_instant(stream: &Stream) -> Instant {
+    let now = Instant::now();
+    match stream.get_latency() {
+        Ok(Latency::Positive(usec)) => now.checked_sub(Duration::from_micros(usec.0)).unwrap_or(now),
+        _ => now,
     }
-}
\ No newline at end of file
+}
+
+/// Traduit un code d'erreur PulseAudio en `CaptureError`
+fn stream_error(err: PAErr, source_name: &str) -> CaptureError {
+    match Code::try_from(err) {
+        Ok(Code::NoEntity) => CaptureError::SourceNotFound(source_name.to_string()),
+        Ok(Code::ConnectionRefused | Code::ConnectionTerminated | Code::InvalidServer) => {
+            CaptureError::ServerUnavailable(format!("{}", err))
+        }
+        _ => CaptureError::StreamFailed(format!("{}", err)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn waiting_ends_as_soon_as_the_capture_is_stopped() {
+        let stop = CaptureStop::default();
+        let waiter = stop.clone();
+        let start = Instant::now();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let thread = thread::spawn(move || sleep_until(deadline, &waiter));
+        thread::sleep(Duration::from_millis(100));
+        stop.stop();
+        thread.join().unwrap();
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn waiting_lasts_until_the_deadline() {
+        let start = Instant::now();
+        sleep_until(start + Duration::from_millis(120), &CaptureStop::default());
+        assert!(start.elapsed() >= Duration::from_millis(120));
+    }
+}
@@ -0,0 +1,163 @@
+// src/audio/chord.rs
+
+use crate::utils;
+use std::fmt;
+
+/// Énergie minimale de la fondamentale et de la tierce, relative à la classe de hauteur la
+/// plus forte : une quinte seule (power chord) ou les harmoniques d'une note isolée ne
+/// forment pas un accord
+const MIN_TONE: f32 = 0.25;
+
+/// Type d'accord reconnu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChordQuality {
+    Major,
+    Minor,
+    Dominant7,
+    Major7,
+    Minor7,
+}
+
+impl ChordQuality {
+    const ALL: [ChordQuality; 5] = [
+        ChordQuality::Major,
+        ChordQuality::Minor,
+        ChordQuality::Dominant7,
+        ChordQuality::Major7,
+        ChordQuality::Minor7,
+    ];
+
+    /// Intervalles en demi-tons depuis la fondamentale
+    fn intervals(self) -> &'static [usize] {
+        match self {
+            ChordQuality::Major => &[0, 4, 7],
+            ChordQuality::Minor => &[0, 3, 7],
+            ChordQuality::Dominant7 => &[0, 4, 7, 10],
+            ChordQuality::Major7 => &[0, 4, 7, 11],
+            ChordQuality::Minor7 => &[0, 3, 7, 10],
+        }
+    }
+
+    fn suffix(self) -> &'static str {
+        match self {
+            ChordQuality::Major => "",
+            ChordQuality::Minor => "m",
+            ChordQuality::Dominant7 => "7",
+            ChordQuality::Major7 => "maj7",
+            ChordQuality::Minor7 => "m7",
+        }
+    }
+}
+
+/// Accord reconnu dans un chromagramme
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Chord {
+    /// Classe de hauteur de la fondamentale (0 = C)
+    pub root: u8,
+    pub quality: ChordQuality,
+    /// Similarité cosinus avec le gabarit de l'accord, dans [0, 1]
+    pub score: f32,
+}
+
+impl Chord {
+    /// Même accord, sans tenir compte du score
+    pub fn same_as(&self, other: &Chord) -> bool {
+        self.root == other.root && self.quality == other.quality
+    }
+}
+
+impl fmt::Display for Chord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}",
+            utils::pitch_class_name(self.root),
+            self.quality.suffix()
+        )
+    }
+}
+
+/// Accord dont le gabarit (classes de hauteur de l'accord à 1, les autres à 0)
+/// est le plus proche du chromagramme, par similarité cosinus, parmi ceux dont la
+/// fondamentale et la tierce sont présentes
+pub fn recognize_chord(chroma: &[f32; 12]) -> Option<Chord> {
+    let norm = chroma.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm <= 0.0 {
+        return None;
+    }
+    let strongest = chroma.iter().cloned().fold(0.0, f32::max);
+    let mut best: Option<Chord> = None;
+    for quality in ChordQuality::ALL {
+        let intervals = quality.intervals();
+        for root in 0..12u8 {
+            let third = chroma[(root as usize + intervals[1]) % 12];
+            if chroma[root as usize].min(third) < MIN_TONE * strongest {
+                continue;
+            }
+            let energy: f32 = intervals
+                .iter()
+                .map(|interval| chroma[(root as usize + interval) % 12])
+                .sum();
+            let score = energy / (norm * (intervals.len() as f32).sqrt());
+            if best.is_none_or(|chord| score > chord.score) {
+                best = Some(Chord {
+                    root,
+                    quality,
+                    score,
+                });
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Chromagramme avec les classes de hauteur données à 1
+    fn chroma(classes: &[usize]) -> [f32; 12] {
+        let mut chroma = [0.0; 12];
+        for &class in classes {
+            chroma[class] = 1.0;
+        }
+        chroma
+    }
+
+    #[test]
+    fn triads_are_recognized() {
+        let c_major = recognize_chord(&chroma(&[0, 4, 7])).unwrap();
+        assert_eq!((c_major.root, c_major.quality), (0, ChordQuality::Major));
+        assert!((c_major.score - 1.0).abs() < 1e-6);
+        assert_eq!(c_major.to_string(), "C");
+
+        let a_minor = recognize_chord(&chroma(&[9, 0, 4])).unwrap();
+        assert_eq!((a_minor.root, a_minor.quality), (9, ChordQuality::Minor));
+        assert_eq!(a_minor.to_string(), "Am");
+
+        let g7 = recognize_chord(&chroma(&[7, 11, 2, 5])).unwrap();
+        assert_eq!((g7.root, g7.quality), (7, ChordQuality::Dominant7));
+    }
+
+    #[test]
+    fn chords_need_a_third() {
+        // Do avec ses harmoniques (quinte à 0,3, tierce à 0,1) : 0,77 sur le gabarit de Do
+        // majeur sans la tierce
+        let mut note = [0.0; 12];
+        note[0] = 1.0;
+        note[7] = 0.3;
+        note[4] = 0.1;
+        assert_eq!(recognize_chord(&note), None);
+
+        // Power chord Do-Sol : 0,816 sur le gabarit de Do majeur, 0,707 sur celui de Lam7
+        assert_eq!(recognize_chord(&chroma(&[0, 7])), None);
+
+        // Tierce faible mais présente
+        let mut soft_third = chroma(&[0, 7]);
+        soft_third[4] = 0.3;
+        let chord = recognize_chord(&soft_third).unwrap();
+        assert_eq!((chord.root, chord.quality), (0, ChordQuality::Major));
+
+        assert_eq!(recognize_chord(&[0.0; 12]), None);
+    }
+}
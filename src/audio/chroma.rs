@@ -0,0 +1,63 @@
+// src/audio/chroma.rs
+
+use crate::tuning::Tuning;
+
+/// Fréquences prises en compte pour le chromagramme (de Do1 à ~Si7)
+const MIN_FREQUENCY: f32 = 32.0;
+const MAX_FREQUENCY: f32 = 4000.0;
+
+/// Chromagramme : énergie du spectre repliée sur les 12 classes de hauteur (0 = Do),
+/// normalisée pour que la classe la plus forte vaille 1 ; chaque bin est rattaché à la note
+/// la plus proche selon `tuning`
+pub fn chromagram(magnitudes: &[f32], bin_hz: f32, tuning: &Tuning) -> [f32; 12] {
+    let mut chroma = [0.0f32; 12];
+    for (bin, magnitude) in magnitudes.iter().enumerate().skip(1) {
+        let frequency = bin as f32 * bin_hz;
+        if !(MIN_FREQUENCY..=MAX_FREQUENCY).contains(&frequency) {
+            continue;
+        }
+        if let Some(note) = tuning.note(frequency) {
+            chroma[(note % 12) as usize] += magnitude * magnitude;
+        }
+    }
+    let max = chroma.iter().cloned().fold(0.0, f32::max);
+    if max > 0.0 {
+        for value in &mut chroma {
+            *value /= max;
+        }
+    }
+    chroma
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bins_fold_onto_pitch_classes() {
+        // Bins de 1 Hz : La 440 Hz, Do 261,6 Hz à moitié et Mi 659 Hz, hors plage ignorés
+        let mut magnitudes = vec![0.0; 5000];
+        magnitudes[440] = 1.0;
+        magnitudes[262] = 0.5;
+        magnitudes[659] = 0.5;
+        magnitudes[20] = 10.0;
+        magnitudes[4500] = 10.0;
+        let chroma = chromagram(&magnitudes, 1.0, &Tuning::default());
+        assert_eq!(chroma[9], 1.0);
+        assert!((chroma[0] - 0.25).abs() < 1e-6);
+        assert!((chroma[4] - 0.25).abs() < 1e-6);
+        for class in [1, 2, 3, 5, 6, 7, 8, 10, 11] {
+            assert_eq!(chroma[class], 0.0);
+        }
+    }
+
+    #[test]
+    fn tuning_moves_the_class_boundaries() {
+        // 425 Hz : plus près de Sol# avec La 440, de La avec La 432
+        let mut magnitudes = vec![0.0; 1000];
+        magnitudes[425] = 1.0;
+        assert_eq!(chromagram(&magnitudes, 1.0, &Tuning::default())[8], 1.0);
+        assert_eq!(chromagram(&magnitudes, 1.0, &Tuning::equal(432.0))[9], 1.0);
+        assert_eq!(chromagram(&[0.0; 100], 1.0, &Tuning::default()), [0.0; 12]);
+    }
+}
@@ -0,0 +1,80 @@
+// src/audio/controls.rs
+
+use crate::audio::tracker::TrackerConfig;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Paramètres du détecteur et du gate modifiables pendant la capture
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LiveParams {
+    /// Niveau (dBFS RMS) au-dessus duquel le gate s'ouvre
+    pub gate_open_db: f32,
+    /// Niveau en dessous duquel le gate se referme
+    pub gate_close_db: f32,
+    /// Confiance minimale de la détection
+    pub min_confidence: f32,
+    /// Nombre de trames consécutives sur la même note avant le Note On
+    pub min_frames: usize,
+    /// Bande de recherche du pic par le détecteur, en Hz
+    pub min_frequency: f32,
+    pub max_frequency: f32,
+}
+
+impl LiveParams {
+    /// Valeurs initiales tirées de la configuration du tracker, détecteur sans restriction
+    pub fn from_tracker(config: &TrackerConfig) -> Self {
+        LiveParams {
+            gate_open_db: config.gate_open_db,
+            gate_close_db: config.gate_close_db,
+            min_confidence: config.min_confidence,
+            min_frames: config.min_frames,
+            min_frequency: 20.0,
+            max_frequency: 5000.0,
+        }
+    }
+}
+
+impl Default for LiveParams {
+    fn default() -> Self {
+        Self::from_tracker(&TrackerConfig::default())
+    }
+}
+
+/// Paramètres partagés entre le thread de traitement et une interface de contrôle :
+/// le traitement relit les valeurs à la trame suivante chaque fois que la version change
+#[derive(Debug, Default)]
+pub struct ProcessorControls {
+    params: Mutex<LiveParams>,
+    version: AtomicU64,
+}
+
+impl ProcessorControls {
+    pub fn new(params: LiveParams) -> Self {
+        ProcessorControls {
+            params: Mutex::new(params),
+            version: AtomicU64::new(0),
+        }
+    }
+
+    pub fn get(&self) -> LiveParams {
+        *self.params.lock().unwrap()
+    }
+
+    /// Modifie les paramètres ; le gate de fermeture reste sous le gate d'ouverture
+    /// et la bande du détecteur reste non vide
+    pub fn update(&self, change: impl FnOnce(&mut LiveParams)) {
+        let mut params = self.params.lock().unwrap();
+        change(&mut params);
+        params.gate_close_db = params.gate_close_db.min(params.gate_open_db);
+        params.min_confidence = params.min_confidence.clamp(0.0, 1.0);
+        params.min_frames = params.min_frames.max(1);
+        params.min_frequency = params.min_frequency.max(1.0);
+        params.max_frequency = params.max_frequency.max(params.min_frequency * 2.0);
+        self.version.fetch_add(1, Ordering::Release);
+    }
+
+    /// Compteur incrémenté à chaque modification
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::Acquire)
+    }
+}
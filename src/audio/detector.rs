@@ -0,0 +1,492 @@
+// src/audio/detector.rs
+
+use crate::audio::analysis::SpectrumAnalyzer;
+use crate::audio::window::Window;
+use std::ops::Range;
+
+/// Nombre maximal de hauteurs candidates gardées par trame
+pub const MAX_CANDIDATES: usize = 5;
+
+/// Hauteur détectée sur une trame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pitch {
+    pub frequency: f32,
+    /// Indice de confiance dans [0, 1]
+    pub confidence: f32,
+}
+
+/// Détecteur de fréquence fondamentale sur une trame mono
+pub trait PitchDetector: Send {
+    fn detect(&mut self, frame: &[f32], sample_rate: f32) -> Option<Pitch>;
+
+    /// Spectre d'amplitude de la dernière trame (bins 0 à N/2), si le détecteur en calcule un
+    fn spectrum(&self) -> Option<&[f32]> {
+        None
+    }
+
+    /// Gain cohérent de la fenêtre appliquée avant le calcul du spectre
+    fn window_gain(&self) -> f32 {
+        1.0
+    }
+
+    /// Restreint la recherche de la fondamentale à une bande de fréquences, si le détecteur
+    /// le permet
+    fn set_frequency_range(&mut self, _min_frequency: f32, _max_frequency: f32) {}
+
+    /// Réserve à l'avance ce qu'il faut pour des trames de `frame_size` échantillons,
+    /// afin que `detect` n'alloue pas sur le thread de traitement
+    fn prepare(&mut self, _frame_size: usize) {}
+
+    /// Hauteurs candidates de la dernière trame (au plus `MAX_CANDIDATES`, la plus probable
+    /// en tête), si le détecteur en produit ; le lissage choisit parmi elles
+    fn candidates(&self) -> &[Pitch] {
+        &[]
+    }
+}
+
+/// Détection par pic du spectre FFT, après fenêtrage (Hamming par défaut)
+#[derive(Debug)]
+pub struct FftPitchDetector {
+    analyzer: SpectrumAnalyzer,
+    /// Bande dans laquelle le pic est cherché, en Hz
+    min_frequency: f32,
+    max_frequency: f32,
+    /// Pics les plus hauts de la bande à la dernière trame
+    candidates: Vec<Pitch>,
+}
+
+impl Default for FftPitchDetector {
+    fn default() -> Self {
+        FftPitchDetector {
+            analyzer: SpectrumAnalyzer::new(),
+            min_frequency: 0.0,
+            max_frequency: f32::INFINITY,
+            candidates: Vec::with_capacity(MAX_CANDIDATES),
+        }
+    }
+}
+
+impl FftPitchDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ne cherche le pic qu'entre `min_frequency` et `max_frequency`
+    pub fn with_frequency_range(mut self, min_frequency: f32, max_frequency: f32) -> Self {
+        self.set_frequency_range(min_frequency, max_frequency);
+        self
+    }
+
+    /// Fenêtre appliquée aux trames avant la FFT
+    pub fn with_window(mut self, window: Window) -> Self {
+        self.analyzer = self.analyzer.with_window(window);
+        self
+    }
+
+    pub fn window(&self) -> Window {
+        self.analyzer.window()
+    }
+}
+
+impl PitchDetector for FftPitchDetector {
+    fn detect(&mut self, frame: &[f32], sample_rate: f32) -> Option<Pitch> {
+        // La fenêtre réduit les effets de fuite spectrale
+        let spectrum = self.analyzer.analyze(frame);
+        let bin_hz = sample_rate / frame.len() as f32;
+        let first = ((self.min_frequency / bin_hz).floor() as usize).max(1);
+        let last = (self.max_frequency / bin_hz).ceil().min(spectrum.len() as f32) as usize;
+        spectrum_candidates(spectrum, bin_hz, first..last, &mut self.candidates);
+        spectrum_peak(spectrum, bin_hz, first..last)
+    }
+
+    fn spectrum(&self) -> Option<&[f32]> {
+        Some(self.analyzer.magnitudes())
+    }
+
+    fn window_gain(&self) -> f32 {
+        self.analyzer.window().coherent_gain()
+    }
+
+    fn set_frequency_range(&mut self, min_frequency: f32, max_frequency: f32) {
+        self.min_frequency = min_frequency;
+        self.max_frequency = max_frequency;
+    }
+
+    fn prepare(&mut self, frame_size: usize) {
+        self.analyzer.prepare(frame_size);
+    }
+
+    fn candidates(&self) -> &[Pitch] {
+        &self.candidates
+    }
+}
+
+/// Seuil par défaut de la différence normalisée de YIN
+const YIN_THRESHOLD: f32 = 0.15;
+
+/// Bande de recherche par défaut de YIN, celle des contrôles du traitement : sans borne
+/// basse, le décalage le plus long serait la moitié de la trame
+const YIN_MIN_FREQUENCY: f32 = 20.0;
+const YIN_MAX_FREQUENCY: f32 = 5000.0;
+
+/// Détection par l'algorithme YIN (de Cheveigné et Kawahara) : premier creux de la fonction
+/// de différence normalisée, dans le domaine temporel. Moins sujet que le pic du spectre
+/// aux erreurs d'octave sur les sons riches en harmoniques (voix, cordes graves), il demande
+/// des trames d'au moins deux périodes de la note la plus grave.
+#[derive(Debug)]
+pub struct YinPitchDetector {
+    /// Spectre calculé en plus pour le chromagramme, le tempo et les LEDs
+    analyzer: SpectrumAnalyzer,
+    threshold: f32,
+    min_frequency: f32,
+    max_frequency: f32,
+    /// Fonction de différence normalisée, indexée par le décalage en échantillons
+    difference: Vec<f32>,
+    /// Creux les plus profonds de la bande à la dernière trame
+    candidates: Vec<Pitch>,
+}
+
+impl Default for YinPitchDetector {
+    fn default() -> Self {
+        YinPitchDetector {
+            analyzer: SpectrumAnalyzer::new(),
+            threshold: YIN_THRESHOLD,
+            min_frequency: YIN_MIN_FREQUENCY,
+            max_frequency: YIN_MAX_FREQUENCY,
+            difference: Vec::new(),
+            candidates: Vec::with_capacity(MAX_CANDIDATES),
+        }
+    }
+}
+
+impl YinPitchDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Seuil de la différence normalisée en dessous duquel un creux est retenu (0.1 à 0.2)
+    pub fn with_threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Ne cherche la période qu'entre celles de `max_frequency` et de `min_frequency`
+    pub fn with_frequency_range(mut self, min_frequency: f32, max_frequency: f32) -> Self {
+        self.set_frequency_range(min_frequency, max_frequency);
+        self
+    }
+
+    /// Fenêtre appliquée aux trames avant le calcul du spectre
+    pub fn with_window(mut self, window: Window) -> Self {
+        self.analyzer = self.analyzer.with_window(window);
+        self
+    }
+
+    pub fn window(&self) -> Window {
+        self.analyzer.window()
+    }
+}
+
+impl PitchDetector for YinPitchDetector {
+    fn detect(&mut self, frame: &[f32], sample_rate: f32) -> Option<Pitch> {
+        self.analyzer.analyze(frame);
+        self.candidates.clear();
+
+        // Décalages cherchés : au plus la moitié de la trame, pour comparer assez d'échantillons
+        // (une borne basse nulle donne une période infinie, ramenée à la moitié de la trame)
+        let max_lag = ((sample_rate / self.min_frequency).ceil() as usize)
+            .saturating_add(1)
+            .min(frame.len() / 2);
+        let min_lag = ((sample_rate / self.max_frequency).floor() as usize).max(2);
+        if max_lag <= min_lag + 1 {
+            return None;
+        }
+        let width = frame.len() - max_lag;
+
+        // Fonction de différence, puis normalisation par sa moyenne cumulée
+        let difference = &mut self.difference;
+        difference.clear();
+        difference.push(1.0);
+        let mut sum = 0.0;
+        for lag in 1..=max_lag {
+            let d: f32 = frame[..width]
+                .iter()
+                .zip(&frame[lag..lag + width])
+                .map(|(a, b)| (a - b) * (a - b))
+                .sum();
+            sum += d;
+            difference.push(if sum > 0.0 { d * lag as f32 / sum } else { 1.0 });
+        }
+
+        // Creux de la bande, du plus profond au moins profond
+        for lag in min_lag..max_lag {
+            let depth = difference[lag];
+            if depth < difference[lag - 1] && depth <= difference[lag + 1] {
+                let candidate = Pitch {
+                    frequency: sample_rate / dip_position(difference, lag),
+                    confidence: (1.0 - depth).clamp(0.0, 1.0),
+                };
+                insert_candidate(&mut self.candidates, candidate);
+            }
+        }
+
+        // Premier décalage sous le seuil, suivi jusqu'au fond de son creux ; à défaut, le
+        // minimum global de la bande, avec une confiance d'autant plus faible
+        let lag = match (min_lag..max_lag).find(|&lag| difference[lag] < self.threshold) {
+            Some(mut lag) => {
+                while lag + 1 < max_lag && difference[lag + 1] < difference[lag] {
+                    lag += 1;
+                }
+                lag
+            }
+            None => (min_lag..max_lag).min_by(|&a, &b| difference[a].total_cmp(&difference[b]))?,
+        };
+
+        Some(Pitch {
+            frequency: sample_rate / dip_position(difference, lag),
+            confidence: (1.0 - difference[lag]).clamp(0.0, 1.0),
+        })
+    }
+
+    fn spectrum(&self) -> Option<&[f32]> {
+        Some(self.analyzer.magnitudes())
+    }
+
+    fn window_gain(&self) -> f32 {
+        self.analyzer.window().coherent_gain()
+    }
+
+    fn set_frequency_range(&mut self, min_frequency: f32, max_frequency: f32) {
+        self.min_frequency = min_frequency;
+        self.max_frequency = max_frequency;
+    }
+
+    fn prepare(&mut self, frame_size: usize) {
+        self.analyzer.prepare(frame_size);
+        self.difference.reserve(frame_size / 2 + 1);
+    }
+
+    fn candidates(&self) -> &[Pitch] {
+        &self.candidates
+    }
+}
+
+/// Position fractionnaire du creux au décalage `lag`, par interpolation parabolique
+fn dip_position(difference: &[f32], lag: usize) -> f32 {
+    let (left, center, right) = (difference[lag - 1], difference[lag], difference[lag + 1]);
+    let curvature = left - 2.0 * center + right;
+    match curvature > 0.0 {
+        true => lag as f32 + 0.5 * (left - right) / curvature,
+        false => lag as f32,
+    }
+}
+
+/// Insère un candidat à son rang de confiance en gardant les `MAX_CANDIDATES` meilleurs ;
+/// `candidates` a déjà sa capacité, rien n'est alloué
+fn insert_candidate(candidates: &mut Vec<Pitch>, candidate: Pitch) {
+    if candidates.len() == MAX_CANDIDATES {
+        if candidates[MAX_CANDIDATES - 1].confidence >= candidate.confidence {
+            return;
+        }
+        candidates.pop();
+    }
+    let rank = candidates.partition_point(|c| c.confidence >= candidate.confidence);
+    candidates.insert(rank, candidate);
+}
+
+/// Algorithme de détection de hauteur
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DetectorKind {
+    /// Pic du spectre : rapide et précis sur les sons presque purs (sifflement, flûte)
+    #[default]
+    Fft,
+    /// YIN : robuste aux harmoniques fortes (voix, guitare, basse)
+    Yin,
+}
+
+impl DetectorKind {
+    /// Nom utilisé en ligne de commande : `fft` ou `yin`
+    pub fn parse(name: &str) -> Option<DetectorKind> {
+        match name {
+            "fft" => Some(DetectorKind::Fft),
+            "yin" => Some(DetectorKind::Yin),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DetectorKind::Fft => "fft",
+            DetectorKind::Yin => "yin",
+        }
+    }
+
+    /// Détecteur de cet algorithme, avec la fenêtre d'analyse du spectre
+    pub fn build(self, window: Window) -> Box<dyn PitchDetector> {
+        match self {
+            DetectorKind::Fft => Box::new(FftPitchDetector::new().with_window(window)),
+            DetectorKind::Yin => Box::new(YinPitchDetector::new().with_window(window)),
+        }
+    }
+}
+
+/// Applique une fenêtre de Hamming aux échantillons
+pub fn apply_hamming_window(samples: &mut [f32]) {
+    Window::Hamming.apply(samples);
+}
+
+/// Spectre d'amplitude (bins 0 à N/2) des échantillons, sans fenêtrage ; planifie la FFT
+/// à chaque appel, voir `SpectrumAnalyzer` pour un traitement continu
+pub fn magnitude_spectrum(samples: &[f32]) -> Vec<f32> {
+    SpectrumAnalyzer::new()
+        .with_window(Window::Rectangular)
+        .analyze(samples)
+        .to_vec()
+}
+
+/// Fonction de détection de pitch avec FFT (analyse ponctuelle, voir `FftPitchDetector`)
+pub fn detect_pitch_fft(samples: &[f32], sample_rate: f32) -> Option<Pitch> {
+    if samples.is_empty() {
+        return None;
+    }
+    let amplitudes = magnitude_spectrum(samples);
+    let bins = 1..amplitudes.len();
+    spectrum_peak(&amplitudes, sample_rate / samples.len() as f32, bins)
+}
+
+/// Pic du spectre d'amplitude parmi les bins `bins` ; `bin_hz` est l'écart en Hz entre deux bins
+fn spectrum_peak(amplitudes: &[f32], bin_hz: f32, bins: Range<usize>) -> Option<Pitch> {
+    // Trouver le pic maximal dans la bande (la composante continue en est exclue)
+    let first = bins.start.max(1);
+    let (max_index, &max_amplitude) = amplitudes
+        .get(first..bins.end)?
+        .iter()
+        .enumerate()
+        .map(|(i, amplitude)| (i + first, amplitude))
+        .max_by(|a, b| a.1.total_cmp(b.1))?;
+
+    // La confiance est la part du pic dans l'énergie spectrale totale
+    let total: f32 = amplitudes[1..].iter().sum();
+    if total <= 0.0 {
+        return None;
+    }
+
+    // Calculer la fréquence correspondante
+    let frequency = peak_position(amplitudes, max_index) * bin_hz;
+    Some(Pitch {
+        frequency,
+        confidence: max_amplitude / total,
+    })
+}
+
+/// Pics locaux du spectre parmi les bins `bins`, du plus haut au moins haut
+fn spectrum_candidates(
+    amplitudes: &[f32],
+    bin_hz: f32,
+    bins: Range<usize>,
+    candidates: &mut Vec<Pitch>,
+) {
+    candidates.clear();
+    let total: f32 = amplitudes.get(1..).map_or(0.0, |a| a.iter().sum());
+    if total <= 0.0 {
+        return;
+    }
+    let first = bins.start.max(1);
+    let last = bins.end.min(amplitudes.len().saturating_sub(1));
+    for index in first..last {
+        let amplitude = amplitudes[index];
+        if amplitude > amplitudes[index - 1] && amplitude >= amplitudes[index + 1] {
+            let candidate = Pitch {
+                frequency: peak_position(amplitudes, index) * bin_hz,
+                confidence: amplitude / total,
+            };
+            insert_candidate(candidates, candidate);
+        }
+    }
+}
+
+/// Interpolation parabolique sur les amplitudes logarithmiques autour du pic `index` :
+/// position fractionnaire du maximum, bien plus fine que l'écart entre deux bins
+fn peak_position(amplitudes: &[f32], index: usize) -> f32 {
+    let mut position = index as f32;
+    let neighbours = (amplitudes.get(index - 1), amplitudes.get(index + 1));
+    if let (Some(&left), Some(&right)) = neighbours {
+        let (alpha, beta, gamma) = (
+            left.max(1e-12).ln(),
+            amplitudes[index].max(1e-12).ln(),
+            right.max(1e-12).ln(),
+        );
+        let curvature = alpha - 2.0 * beta + gamma;
+        if curvature < 0.0 {
+            position += 0.5 * (alpha - gamma) / curvature;
+        }
+    }
+    position
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    const SAMPLE_RATE: f32 = 44100.0;
+
+    /// Son de fondamentale `frequency` avec les amplitudes d'harmoniques données
+    fn tone(frequency: f32, harmonics: &[f32], len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|i| {
+                let t = i as f32 / SAMPLE_RATE;
+                harmonics
+                    .iter()
+                    .enumerate()
+                    .map(|(k, a)| a * (2.0 * PI * frequency * (k + 1) as f32 * t).sin())
+                    .sum::<f32>()
+                    * 0.3
+            })
+            .collect()
+    }
+
+    fn cents(frequency: f32, reference: f32) -> f32 {
+        1200.0 * (frequency / reference).log2()
+    }
+
+    #[test]
+    fn yin_detects_with_its_default_band() {
+        let mut detector = YinPitchDetector::new();
+        let pitch = detector
+            .detect(&tone(440.0, &[1.0, 0.5, 0.3], 2048), SAMPLE_RATE)
+            .expect("hauteur détectée");
+        assert!(cents(pitch.frequency, 440.0).abs() < 5.0, "{:?}", pitch);
+    }
+
+    #[test]
+    fn yin_without_lower_bound_does_not_overflow() {
+        let mut detector = YinPitchDetector::new().with_frequency_range(0.0, f32::INFINITY);
+        let pitch = detector.detect(&tone(220.0, &[1.0, 0.5], 2048), SAMPLE_RATE);
+        assert!(cents(pitch.expect("hauteur détectée").frequency, 220.0).abs() < 5.0);
+    }
+
+    #[test]
+    fn yin_finds_weak_fundamental_of_low_bass() {
+        // Deuxième harmonique plus forte que la fondamentale : le pic FFT est à l'octave
+        let frame = tone(41.2, &[0.3, 1.0, 0.8, 0.5], 4096);
+        let mut yin = YinPitchDetector::new().with_frequency_range(30.0, 500.0);
+        let pitch = yin.detect(&frame, SAMPLE_RATE).expect("hauteur détectée");
+        assert!(cents(pitch.frequency, 41.2).abs() < 10.0, "{:?}", pitch);
+        assert!(!yin.candidates().is_empty());
+    }
+
+    #[test]
+    fn fft_candidates_are_ranked_peaks() {
+        let mut detector = FftPitchDetector::new().with_frequency_range(50.0, 2000.0);
+        let pitch = detector
+            .detect(&tone(220.0, &[1.0, 0.6, 0.3], 4096), SAMPLE_RATE)
+            .expect("hauteur détectée");
+        let candidates = detector.candidates();
+        assert!(candidates.len() >= 3 && candidates.len() <= MAX_CANDIDATES);
+        assert!(cents(candidates[0].frequency, pitch.frequency).abs() < 1.0);
+        assert!(candidates
+            .windows(2)
+            .all(|pair| pair[0].confidence >= pair[1].confidence));
+    }
+}
@@ -0,0 +1,395 @@
+// src/audio/filter.rs
+
+use std::f32::consts::PI;
+
+/// Coupure du filtre anti-continu
+const DC_BLOCK_CUTOFF: f32 = 10.0;
+
+/// Coefficient de qualité d'un passe-haut ou passe-bas de Butterworth
+const BUTTERWORTH_Q: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+/// Sélectivité des réjecteurs du secteur : environ 1,7 Hz de large à 50 Hz
+const HUM_Q: f32 = 30.0;
+
+/// Type d'une cellule du filtre
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FilterKind {
+    /// Passe-haut du premier ordre à 10 Hz, supprime la composante continue
+    DcBlock,
+    HighPass {
+        cutoff: f32,
+        q: f32,
+    },
+    LowPass {
+        cutoff: f32,
+        q: f32,
+    },
+    /// Passe-bande de gain unitaire au centre
+    BandPass {
+        center: f32,
+        q: f32,
+    },
+    /// Réjecteur étroit (ronflement du secteur)
+    Notch {
+        frequency: f32,
+        q: f32,
+    },
+}
+
+/// Cellule biquadratique (formules du « Audio EQ Cookbook »), forme directe II transposée
+#[derive(Debug, Clone)]
+pub struct Biquad {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+    z1: f32,
+    z2: f32,
+}
+
+impl Biquad {
+    pub fn new(kind: FilterKind, sample_rate: f32) -> Self {
+        let coefficients = |frequency: f32, q: f32| {
+            // Rester sous la fréquence de Nyquist
+            let frequency = frequency.clamp(1.0, sample_rate * 0.49);
+            let w0 = 2.0 * PI * frequency / sample_rate;
+            (w0.cos(), w0.sin() / (2.0 * q.max(1e-3)))
+        };
+        let (b0, b1, b2, a0, a1, a2) = match kind {
+            FilterKind::DcBlock => {
+                let pole = (-2.0 * PI * DC_BLOCK_CUTOFF / sample_rate).exp();
+                (1.0, -1.0, 0.0, 1.0, -pole, 0.0)
+            }
+            FilterKind::HighPass { cutoff, q } => {
+                let (cos, alpha) = coefficients(cutoff, q);
+                let b = (1.0 + cos) / 2.0;
+                (b, -2.0 * b, b, 1.0 + alpha, -2.0 * cos, 1.0 - alpha)
+            }
+            FilterKind::LowPass { cutoff, q } => {
+                let (cos, alpha) = coefficients(cutoff, q);
+                let b = (1.0 - cos) / 2.0;
+                (b, 2.0 * b, b, 1.0 + alpha, -2.0 * cos, 1.0 - alpha)
+            }
+            FilterKind::BandPass { center, q } => {
+                let (cos, alpha) = coefficients(center, q);
+                (alpha, 0.0, -alpha, 1.0 + alpha, -2.0 * cos, 1.0 - alpha)
+            }
+            FilterKind::Notch { frequency, q } => {
+                let (cos, alpha) = coefficients(frequency, q);
+                (1.0, -2.0 * cos, 1.0, 1.0 + alpha, -2.0 * cos, 1.0 - alpha)
+            }
+        };
+        Biquad {
+            b0: b0 / a0,
+            b1: b1 / a0,
+            b2: b2 / a0,
+            a1: a1 / a0,
+            a2: a2 / a0,
+            z1: 0.0,
+            z2: 0.0,
+        }
+    }
+
+    pub fn process(&mut self, input: f32) -> f32 {
+        let output = self.b0 * input + self.z1;
+        self.z1 = self.b1 * input - self.a1 * output + self.z2;
+        self.z2 = self.b2 * input - self.a2 * output;
+        output
+    }
+
+    /// Oublie l'état (après une coupure du flux)
+    pub fn reset(&mut self) {
+        self.z1 = 0.0;
+        self.z2 = 0.0;
+    }
+}
+
+/// Réglages préconfigurés selon l'instrument joué
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterPreset {
+    /// Tout instrument : anti-continu, anti-grondement à 30 Hz, ronflement, passe-bas à 5 kHz
+    General,
+    Voice,
+    Guitar,
+    /// Sans réjecteur du secteur : 50/60 Hz tombent au milieu des notes graves de la basse
+    Bass,
+    Violin,
+    Flute,
+    Whistle,
+}
+
+impl FilterPreset {
+    /// Nom utilisé en ligne de commande
+    pub fn parse(name: &str) -> Option<FilterPreset> {
+        match name {
+            "general" => Some(FilterPreset::General),
+            "voice" => Some(FilterPreset::Voice),
+            "guitar" => Some(FilterPreset::Guitar),
+            "bass" => Some(FilterPreset::Bass),
+            "violin" => Some(FilterPreset::Violin),
+            "flute" => Some(FilterPreset::Flute),
+            "whistle" => Some(FilterPreset::Whistle),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            FilterPreset::General => "general",
+            FilterPreset::Voice => "voice",
+            FilterPreset::Guitar => "guitar",
+            FilterPreset::Bass => "bass",
+            FilterPreset::Violin => "violin",
+            FilterPreset::Flute => "flute",
+            FilterPreset::Whistle => "whistle",
+        }
+    }
+}
+
+/// Réglages de la chaîne de pré-filtrage appliquée avant l'analyse
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterConfig {
+    pub dc_block: bool,
+    /// Coupure du passe-haut anti-grondement (Hz)
+    pub high_pass: Option<f32>,
+    /// Coupure du passe-bas (Hz), qui atténue les harmoniques aiguës
+    pub low_pass: Option<f32>,
+    /// Centre et facteur de qualité d'un passe-bande
+    pub band_pass: Option<(f32, f32)>,
+    /// Fréquence du secteur (50 ou 60 Hz) dont le ronflement est rejeté
+    pub hum: Option<f32>,
+    /// Harmoniques du secteur rejetées en plus du fondamental
+    pub hum_harmonics: usize,
+}
+
+impl Default for FilterConfig {
+    fn default() -> Self {
+        FilterConfig::preset(FilterPreset::General)
+    }
+}
+
+impl FilterConfig {
+    /// Aucun filtrage
+    pub fn none() -> Self {
+        FilterConfig {
+            dc_block: false,
+            high_pass: None,
+            low_pass: None,
+            band_pass: None,
+            hum: None,
+            hum_harmonics: 0,
+        }
+    }
+
+    /// Plage utile de l'instrument : anti-grondement sous la note la plus grave,
+    /// passe-bas au-dessus des fondamentales les plus aiguës
+    pub fn preset(preset: FilterPreset) -> Self {
+        let (high_pass, low_pass) = match preset {
+            FilterPreset::General => (30.0, 5000.0),
+            // E2 à C6 : la voix n'a guère d'énergie utile sous 70 Hz
+            FilterPreset::Voice => (70.0, 1500.0),
+            // Jusqu'au D2 des accordages abaissés, E6 de la 24e case bien dans la bande passante
+            FilterPreset::Guitar => (60.0, 2000.0),
+            FilterPreset::Bass => (30.0, 500.0),
+            FilterPreset::Violin => (180.0, 4500.0),
+            FilterPreset::Flute => (230.0, 2500.0),
+            FilterPreset::Whistle => (450.0, 5000.0),
+        };
+        FilterConfig {
+            dc_block: true,
+            high_pass: Some(high_pass),
+            low_pass: Some(low_pass),
+            band_pass: None,
+            hum: (preset != FilterPreset::Bass).then_some(50.0),
+            hum_harmonics: 2,
+        }
+    }
+
+    /// Fréquence du secteur : 50 Hz (Europe) ou 60 Hz (Amérique du Nord), `None` sans réjecteur
+    pub fn with_hum(mut self, mains: Option<f32>) -> Self {
+        self.hum = mains;
+        self
+    }
+}
+
+/// Suite de cellules appliquées échantillon par échantillon au flux mono
+#[derive(Debug, Clone)]
+pub struct FilterChain {
+    stages: Vec<Biquad>,
+}
+
+impl FilterChain {
+    pub fn new(config: &FilterConfig, sample_rate: f32) -> Self {
+        let mut kinds = Vec::new();
+        if config.dc_block {
+            kinds.push(FilterKind::DcBlock);
+        }
+        if let Some(cutoff) = config.high_pass {
+            kinds.push(FilterKind::HighPass {
+                cutoff,
+                q: BUTTERWORTH_Q,
+            });
+        }
+        if let Some(mains) = config.hum {
+            for harmonic in 1..=config.hum_harmonics + 1 {
+                kinds.push(FilterKind::Notch {
+                    frequency: mains * harmonic as f32,
+                    q: HUM_Q,
+                });
+            }
+        }
+        if let Some((center, q)) = config.band_pass {
+            kinds.push(FilterKind::BandPass { center, q });
+        }
+        if let Some(cutoff) = config.low_pass {
+            kinds.push(FilterKind::LowPass {
+                cutoff,
+                q: BUTTERWORTH_Q,
+            });
+        }
+        FilterChain {
+            stages: kinds
+                .into_iter()
+                .map(|kind| Biquad::new(kind, sample_rate))
+                .collect(),
+        }
+    }
+
+    /// Chaîne construite à partir de cellules quelconques
+    pub fn from_stages(kinds: &[FilterKind], sample_rate: f32) -> Self {
+        FilterChain {
+            stages: kinds
+                .iter()
+                .map(|&kind| Biquad::new(kind, sample_rate))
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    pub fn process(&mut self, sample: f32) -> f32 {
+        self.stages
+            .iter_mut()
+            .fold(sample, |sample, stage| stage.process(sample))
+    }
+
+    pub fn reset(&mut self) {
+        self.stages.iter_mut().for_each(Biquad::reset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: f32 = 44100.0;
+
+    /// Gain en dB d'un filtre sur une sinusoïde, mesuré sur la dernière demi-seconde de 2 s
+    fn gain_db(mut filter: impl FnMut(f32) -> f32, frequency: f32) -> f32 {
+        let total = (2.0 * SAMPLE_RATE) as usize;
+        let settled = total - (0.5 * SAMPLE_RATE) as usize;
+        let (mut input, mut output) = (0.0f32, 0.0f32);
+        for n in 0..total {
+            let x = (2.0 * PI * frequency * n as f32 / SAMPLE_RATE).sin();
+            let y = filter(x);
+            if n >= settled {
+                input += x * x;
+                output += y * y;
+            }
+        }
+        10.0 * (output / input).log10()
+    }
+
+    fn biquad_gain_db(kind: FilterKind, frequency: f32) -> f32 {
+        let mut biquad = Biquad::new(kind, SAMPLE_RATE);
+        gain_db(|x| biquad.process(x), frequency)
+    }
+
+    #[test]
+    fn dc_block_removes_a_constant() {
+        let mut biquad = Biquad::new(FilterKind::DcBlock, SAMPLE_RATE);
+        let last = (0..SAMPLE_RATE as usize)
+            .map(|_| biquad.process(0.5))
+            .last()
+            .unwrap();
+        assert!(last.abs() < 1e-4, "{}", last);
+        assert!(biquad_gain_db(FilterKind::DcBlock, 440.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn notch_rejects_only_its_frequency() {
+        let notch = FilterKind::Notch {
+            frequency: 50.0,
+            q: HUM_Q,
+        };
+        let rejected = biquad_gain_db(notch, 50.0);
+        assert!(rejected < -30.0, "{} dB à 50 Hz", rejected);
+        let passed = biquad_gain_db(notch, 440.0);
+        assert!(passed.abs() < 0.5, "{} dB à 440 Hz", passed);
+    }
+
+    #[test]
+    fn butterworth_cutoffs_are_at_minus_3_db() {
+        let high = FilterKind::HighPass {
+            cutoff: 100.0,
+            q: BUTTERWORTH_Q,
+        };
+        let low = FilterKind::LowPass {
+            cutoff: 2000.0,
+            q: BUTTERWORTH_Q,
+        };
+        for (kind, cutoff, pass, stop) in
+            [(high, 100.0, 1000.0, 25.0), (low, 2000.0, 200.0, 8000.0)]
+        {
+            let at_cutoff = biquad_gain_db(kind, cutoff);
+            assert!(
+                (at_cutoff + 3.01).abs() < 0.1,
+                "{:?} : {} dB",
+                kind,
+                at_cutoff
+            );
+            assert!(biquad_gain_db(kind, pass).abs() < 0.1);
+            // Deux octaves de l'autre côté : 12 dB par octave
+            assert!(biquad_gain_db(kind, stop) < -20.0);
+        }
+    }
+
+    #[test]
+    fn bass_preset_keeps_the_mains_frequency() {
+        let bass = FilterConfig::preset(FilterPreset::Bass);
+        assert_eq!(bass.hum, None);
+        let mut chain = FilterChain::new(&bass, SAMPLE_RATE);
+        assert!(gain_db(|x| chain.process(x), 50.0) > -1.0);
+
+        let mut general = FilterChain::new(&FilterConfig::default(), SAMPLE_RATE);
+        assert!(gain_db(|x| general.process(x), 50.0) < -30.0);
+        // Les harmoniques 100 et 150 Hz sont aussi rejetées
+        assert!(gain_db(|x| general.process(x), 150.0) < -30.0);
+    }
+
+    #[test]
+    fn presets_differ_by_instrument() {
+        let voice = FilterConfig::preset(FilterPreset::Voice);
+        let guitar = FilterConfig::preset(FilterPreset::Guitar);
+        assert_ne!(voice, guitar);
+        assert!(guitar.low_pass > Some(1400.0));
+        assert!(FilterChain::new(&FilterConfig::none(), SAMPLE_RATE).is_empty());
+    }
+
+    #[test]
+    fn reset_forgets_the_previous_samples() {
+        let mut chain = FilterChain::new(&FilterConfig::default(), SAMPLE_RATE);
+        let mut fresh = chain.clone();
+        for n in 0..1000 {
+            chain.process((n as f32 * 0.1).sin());
+        }
+        chain.reset();
+        for n in 0..100 {
+            let x = if n == 0 { 1.0 } else { 0.0 };
+            assert_eq!(chain.process(x), fresh.process(x));
+        }
+    }
+}
@@ -0,0 +1,451 @@
+// src/audio/flac.rs
+
+use std::fs::File;
+use std::io::{self, BufWriter, Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// Taille de bloc fixe utilisée pour toutes les trames (sauf la dernière)
+const BLOCK_SIZE: usize = 4096;
+
+/// Ordre maximal du prédicteur fixe FLAC
+const MAX_FIXED_ORDER: usize = 4;
+
+/// Position du bloc STREAMINFO (juste après la signature "fLaC")
+const STREAMINFO_OFFSET: u64 = 4;
+
+/// Encodeur FLAC minimal : prédicteurs fixes (ordres 0 à 4) et codage de Rice,
+/// suffisant pour archiver la capture sans perte sans dépendance externe.
+pub struct FlacWriter {
+    writer: BufWriter<File>,
+    channels: usize,
+    bits_per_sample: u32,
+    sample_rate: u32,
+    /// Échantillons entrelacés en attente d'un bloc complet
+    pending: Vec<i32>,
+    /// Tampons réutilisés d'une trame à l'autre : un canal désentrelacé et la trame codée
+    channel: Vec<i64>,
+    bits: BitWriter,
+    frame_number: u64,
+    total_frames: u64,
+    min_frame_size: u32,
+    max_frame_size: u32,
+}
+
+impl FlacWriter {
+    pub fn create<P: AsRef<Path>>(
+        path: P,
+        channels: u16,
+        sample_rate: u32,
+        bits_per_sample: u16,
+    ) -> io::Result<Self> {
+        if channels == 0 || channels > 8 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "FLAC : le nombre de canaux doit être compris entre 1 et 8",
+            ));
+        }
+        if !(4..=24).contains(&bits_per_sample) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "FLAC : la résolution doit être comprise entre 4 et 24 bits",
+            ));
+        }
+
+        let mut flac = FlacWriter {
+            writer: BufWriter::new(File::create(path)?),
+            channels: channels as usize,
+            bits_per_sample: bits_per_sample as u32,
+            sample_rate,
+            pending: Vec::with_capacity(BLOCK_SIZE * channels as usize),
+            channel: Vec::with_capacity(BLOCK_SIZE),
+            bits: BitWriter::new(),
+            frame_number: 0,
+            total_frames: 0,
+            min_frame_size: 0,
+            max_frame_size: 0,
+        };
+        flac.writer.write_all(b"fLaC")?;
+        flac.write_streaminfo()?;
+        Ok(flac)
+    }
+
+    /// Ajoute des échantillons entrelacés ; les trames sont émises par blocs complets,
+    /// sans allocation une fois les tampons à leur taille
+    pub fn write_samples(&mut self, samples: &[i32]) -> io::Result<()> {
+        self.write_converted(samples.iter().copied())
+    }
+
+    /// Comme `write_samples`, pour des échantillons à convertir au vol (16 bits de la
+    /// capture par exemple)
+    pub fn write_converted(&mut self, samples: impl IntoIterator<Item = i32>) -> io::Result<()> {
+        self.pending.extend(samples);
+        let block_len = BLOCK_SIZE * self.channels;
+        // Le tampon d'attente est sorti le temps d'écrire les trames qu'il contient
+        let pending = std::mem::take(&mut self.pending);
+        let mut offset = 0;
+        let mut result = Ok(());
+        while pending.len() - offset >= block_len {
+            result = self.write_frame(&pending[offset..offset + block_len]);
+            if result.is_err() {
+                break;
+            }
+            offset += block_len;
+        }
+        self.pending = pending;
+        self.pending.drain(..offset);
+        result
+    }
+
+    /// Vide le dernier bloc partiel et réécrit STREAMINFO avec les totaux définitifs
+    pub fn finalize(mut self) -> io::Result<()> {
+        let whole = self.pending.len() - self.pending.len() % self.channels;
+        if whole > 0 {
+            let pending = std::mem::take(&mut self.pending);
+            self.write_frame(&pending[..whole])?;
+        }
+        self.writer.seek(SeekFrom::Start(STREAMINFO_OFFSET))?;
+        self.write_streaminfo()?;
+        self.writer.flush()
+    }
+
+    fn write_streaminfo(&mut self) -> io::Result<()> {
+        let mut bits = BitWriter::new();
+        // En-tête de bloc : dernier bloc, type 0 (STREAMINFO), longueur 34 octets
+        bits.put(1, 1);
+        bits.put(0, 7);
+        bits.put(34, 24);
+        bits.put(BLOCK_SIZE as u64, 16);
+        bits.put(BLOCK_SIZE as u64, 16);
+        bits.put(self.min_frame_size as u64, 24);
+        bits.put(self.max_frame_size as u64, 24);
+        bits.put(self.sample_rate as u64, 20);
+        bits.put(self.channels as u64 - 1, 3);
+        bits.put(self.bits_per_sample as u64 - 1, 5);
+        bits.put(self.total_frames, 36);
+        // Signature MD5 inconnue (autorisé par la spécification)
+        for _ in 0..16 {
+            bits.put(0, 8);
+        }
+        self.writer.write_all(&bits.into_bytes())
+    }
+
+    fn write_frame(&mut self, interleaved: &[i32]) -> io::Result<()> {
+        let block_size = interleaved.len() / self.channels;
+        let mut bits = std::mem::take(&mut self.bits);
+        bits.clear();
+
+        // En-tête de trame : synchro, stratégie de bloc fixe
+        bits.put(0b11_1111_1111_1110, 14);
+        bits.put(0, 1);
+        bits.put(0, 1);
+        if block_size == BLOCK_SIZE {
+            bits.put(0b1100, 4); // 256 * 2^4 = 4096
+        } else {
+            bits.put(0b0111, 4); // taille sur 16 bits en fin d'en-tête
+        }
+        bits.put(0b0000, 4); // fréquence lue dans STREAMINFO
+        bits.put(self.channels as u64 - 1, 4); // canaux indépendants
+        bits.put(sample_size_code(self.bits_per_sample), 3);
+        bits.put(0, 1);
+        bits.put_utf8(self.frame_number);
+        if block_size != BLOCK_SIZE {
+            bits.put(block_size as u64 - 1, 16);
+        }
+        let crc8 = crc8(bits.bytes());
+        bits.put(crc8 as u64, 8);
+
+        let mut channel = std::mem::take(&mut self.channel);
+        for ch in 0..self.channels {
+            channel.clear();
+            channel.extend(interleaved.iter().skip(ch).step_by(self.channels).map(|&s| s as i64));
+            self.write_subframe(&mut bits, &channel);
+        }
+
+        self.channel = channel;
+
+        bits.align();
+        let crc16 = crc16(bits.bytes());
+        bits.put(crc16 as u64, 16);
+
+        let size = bits.bytes().len() as u32;
+        self.min_frame_size = if self.frame_number == 0 {
+            size
+        } else {
+            self.min_frame_size.min(size)
+        };
+        self.max_frame_size = self.max_frame_size.max(size);
+        self.frame_number += 1;
+        self.total_frames += block_size as u64;
+        let written = self.writer.write_all(bits.bytes());
+        self.bits = bits;
+        written
+    }
+
+    fn write_subframe(&self, bits: &mut BitWriter, samples: &[i64]) {
+        let bps = self.bits_per_sample;
+
+        // Blocs trop courts pour un prédicteur : sous-trame VERBATIM
+        if samples.len() <= MAX_FIXED_ORDER {
+            bits.put(0b0000_0010, 8);
+            for &s in samples {
+                bits.put_signed(s, bps);
+            }
+            return;
+        }
+
+        // Choisir l'ordre de prédiction minimisant la somme des résidus ; les résidus sont
+        // recalculés au codage plutôt que conservés
+        let residuals =
+            |order: usize| (order..samples.len()).map(move |n| fixed_residual(samples, order, n));
+        let (order, sum) = (0..=MAX_FIXED_ORDER)
+            .map(|order| (order, residuals(order).map(i64::unsigned_abs).sum::<u64>()))
+            .min_by_key(|&(_, sum)| sum)
+            .expect("au moins un ordre de prédiction");
+
+        // Sous-trame FIXED : 0, type 001xxx, pas de bits perdus
+        bits.put(0, 1);
+        bits.put(0b001000 | order as u64, 6);
+        bits.put(0, 1);
+        for &s in &samples[..order] {
+            bits.put_signed(s, bps);
+        }
+
+        // Résidus : codage de Rice à paramètre sur 4 bits, une seule partition
+        let parameter = rice_parameter(sum, samples.len() - order);
+        bits.put(0b00, 2);
+        bits.put(0, 4);
+        bits.put(parameter as u64, 4);
+        for r in residuals(order) {
+            let folded = if r >= 0 { (r as u64) << 1 } else { ((-r as u64) << 1) - 1 };
+            bits.put_unary(folded >> parameter);
+            if parameter > 0 {
+                bits.put(folded & ((1 << parameter) - 1), parameter);
+            }
+        }
+    }
+}
+
+fn sample_size_code(bits_per_sample: u32) -> u64 {
+    match bits_per_sample {
+        8 => 0b001,
+        12 => 0b010,
+        16 => 0b100,
+        20 => 0b101,
+        24 => 0b110,
+        _ => 0b000, // lu dans STREAMINFO
+    }
+}
+
+/// Résidu du prédicteur fixe d'ordre `order` pour l'échantillon `n` (`n >= order`)
+fn fixed_residual(samples: &[i64], order: usize, n: usize) -> i64 {
+    let x = |k: usize| samples[n - k];
+    match order {
+        0 => x(0),
+        1 => x(0) - x(1),
+        2 => x(0) - 2 * x(1) + x(2),
+        3 => x(0) - 3 * x(1) + 3 * x(2) - x(3),
+        _ => x(0) - 4 * x(1) + 6 * x(2) - 4 * x(3) + x(4),
+    }
+}
+
+/// Estime le paramètre de Rice à partir de la moyenne des résidus repliés, connaissant la
+/// somme de leurs valeurs absolues
+fn rice_parameter(sum: u64, count: usize) -> u32 {
+    if count == 0 {
+        return 0;
+    }
+    let mean = sum * 2 / count as u64;
+    let mut parameter = 0;
+    while parameter < 14 && (1u64 << (parameter + 1)) <= mean {
+        parameter += 1;
+    }
+    parameter
+}
+
+fn crc8(data: &[u8]) -> u8 {
+    let mut crc = 0u8;
+    for &byte in data {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 { (crc << 1) ^ 0x07 } else { crc << 1 };
+        }
+    }
+    crc
+}
+
+fn crc16(data: &[u8]) -> u16 {
+    let mut crc = 0u16;
+    for &byte in data {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x8005 } else { crc << 1 };
+        }
+    }
+    crc
+}
+
+/// Accumulateur de bits (poids fort en premier), comme l'exige le format FLAC
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    current: u64,
+    filled: u32,
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        BitWriter {
+            bytes: Vec::new(),
+            current: 0,
+            filled: 0,
+        }
+    }
+
+    /// Repart d'un tampon vide, en gardant sa capacité
+    fn clear(&mut self) {
+        self.bytes.clear();
+        self.current = 0;
+        self.filled = 0;
+    }
+
+    fn put(&mut self, value: u64, count: u32) {
+        for i in (0..count).rev() {
+            self.current = (self.current << 1) | ((value >> i) & 1);
+            self.filled += 1;
+            if self.filled == 8 {
+                self.bytes.push(self.current as u8);
+                self.current = 0;
+                self.filled = 0;
+            }
+        }
+    }
+
+    fn put_signed(&mut self, value: i64, count: u32) {
+        self.put(value as u64 & ((1u64 << count) - 1), count);
+    }
+
+    fn put_unary(&mut self, zeros: u64) {
+        for _ in 0..zeros {
+            self.put(0, 1);
+        }
+        self.put(1, 1);
+    }
+
+    /// Numéro de trame codé « à la UTF-8 », comme le demande l'en-tête FLAC
+    fn put_utf8(&mut self, value: u64) {
+        if value < 0x80 {
+            self.put(value, 8);
+            return;
+        }
+        let mut continuation = 1;
+        while value >> (6 * continuation + 6 - continuation) != 0 {
+            continuation += 1;
+        }
+        let prefix = (0xFF00u64 >> (continuation + 1)) & 0xFF;
+        self.put(prefix | (value >> (6 * continuation)), 8);
+        for i in (0..continuation).rev() {
+            self.put(0x80 | ((value >> (6 * i)) & 0x3F), 8);
+        }
+    }
+
+    fn align(&mut self) {
+        if self.filled > 0 {
+            self.put(0, 8 - self.filled);
+        }
+    }
+
+    /// Octets complets écrits jusqu'ici (pour le calcul des CRC)
+    fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    fn into_bytes(mut self) -> Vec<u8> {
+        self.align();
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("flac-{}-{}.flac", std::process::id(), name))
+    }
+
+    /// Écrit les échantillons entrelacés en FLAC, relit le fichier avec claxon et vérifie
+    /// STREAMINFO et chaque échantillon
+    fn round_trip(name: &str, samples: &[i32], channels: u16, bits_per_sample: u16) {
+        let path = temp_path(name);
+        let mut writer = FlacWriter::create(&path, channels, 44100, bits_per_sample).unwrap();
+        // Par morceaux de tailles irrégulières, comme les blocs de la capture
+        for chunk in samples.chunks(1000 * channels as usize + channels as usize) {
+            writer.write_samples(chunk).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let mut reader = claxon::FlacReader::open(&path).unwrap();
+        let info = reader.streaminfo();
+        assert_eq!(info.sample_rate, 44100);
+        assert_eq!(info.channels, channels as u32);
+        assert_eq!(info.bits_per_sample, bits_per_sample as u32);
+        let frames = samples.len() / channels as usize;
+        assert_eq!(info.samples, Some(frames as u64));
+        assert!(info.min_frame_size.unwrap_or(0) <= info.max_frame_size.unwrap_or(0));
+        let decoded: Vec<i32> = reader.samples().map(Result::unwrap).collect();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(decoded, samples);
+    }
+
+    #[test]
+    fn stereo_sine_round_trip() {
+        // Deux blocs complets et un bloc partiel
+        let samples: Vec<i32> = (0..BLOCK_SIZE * 2 + 1234)
+            .flat_map(|i| {
+                let t = i as f32 / 44100.0;
+                let left = (0.8 * 32767.0 * (2.0 * PI * 440.0 * t).sin()) as i32;
+                let right = (0.5 * 32767.0 * (2.0 * PI * 660.0 * t).sin()) as i32;
+                [left, right]
+            })
+            .collect();
+        round_trip("sine", &samples, 2, 16);
+    }
+
+    #[test]
+    fn silence_round_trip() {
+        round_trip("silence", &vec![0; BLOCK_SIZE * 2], 2, 16);
+    }
+
+    #[test]
+    fn noise_and_extremes_round_trip() {
+        // Résidus grands et de signe quelconque : paramètres de Rice élevés, valeurs extrêmes
+        let mut state = 0x1234_5678u32;
+        let mut samples: Vec<i32> = (0..BLOCK_SIZE + 77)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                (state >> 8) as i32 - (1 << 23)
+            })
+            .collect();
+        samples[10] = (1 << 23) - 1;
+        samples[11] = -(1 << 23);
+        round_trip("noise", &samples, 1, 24);
+    }
+
+    #[test]
+    fn many_frames_use_multibyte_frame_numbers() {
+        // Au-delà de 128 trames, le numéro de trame s'écrit en UTF-8 sur plusieurs octets
+        let samples: Vec<i32> = (0..BLOCK_SIZE * 130)
+            .map(|i| ((i % 200) as i32 - 100) * 50)
+            .collect();
+        round_trip("frames", &samples, 1, 16);
+    }
+
+    #[test]
+    fn crc_check_values() {
+        // Valeurs de contrôle de CRC-8 (poly 0x07) et CRC-16 (poly 0x8005), sur "123456789"
+        assert_eq!(crc8(b"123456789"), 0xF4);
+        assert_eq!(crc16(b"123456789"), 0xFEE8);
+    }
+}
@@ -0,0 +1,116 @@
+// src/audio/framer.rs
+
+use crate::audio::capture::AudioBuffer;
+use crate::audio::filter::FilterChain;
+use crate::latency::Timestamps;
+use std::collections::VecDeque;
+use std::time::Instant;
+
+/// Trame d'analyse mono, normalisée dans [-1, 1]
+#[derive(Debug, Clone)]
+pub struct Frame {
+    pub samples: Vec<f32>,
+    /// Horodatages du bloc de capture qui a complété la trame
+    pub timestamps: Timestamps,
+}
+
+impl Frame {
+    /// Niveau RMS de la trame en dBFS
+    pub fn level_db(&self) -> f32 {
+        if self.samples.is_empty() {
+            return f32::NEG_INFINITY;
+        }
+        let energy: f32 = self.samples.iter().map(|s| s * s).sum();
+        let rms = (energy / self.samples.len() as f32).sqrt();
+        20.0 * rms.max(1e-10).log10()
+    }
+}
+
+/// Découpe le flux de blocs capturés (entrelacés, taille variable) en trames mono
+/// de taille fixe avec recouvrement
+pub struct Framer {
+    channels: usize,
+    frame_size: usize,
+    hop_size: usize,
+    pending: VecDeque<f32>,
+    timestamps: Option<Timestamps>,
+    /// Pré-filtrage du flux mono, avant découpage
+    filter: Option<FilterChain>,
+}
+
+impl Framer {
+    /// `frame_size` échantillons par trame, une nouvelle trame tous les `hop_size` échantillons
+    pub fn new(channels: usize, frame_size: usize, hop_size: usize) -> Self {
+        assert!(channels > 0 && frame_size > 0, "framer : paramètres invalides");
+        assert!(
+            (1..=frame_size).contains(&hop_size),
+            "framer : le pas doit être compris entre 1 et la taille de trame"
+        );
+        Framer {
+            channels,
+            frame_size,
+            hop_size,
+            pending: VecDeque::with_capacity(frame_size * 2),
+            timestamps: None,
+            filter: None,
+        }
+    }
+
+    /// Filtre le flux mono avant de le découper en trames
+    pub fn with_filter(mut self, filter: FilterChain) -> Self {
+        self.set_filter(filter);
+        self
+    }
+
+    pub fn set_filter(&mut self, filter: FilterChain) {
+        self.filter = Some(filter).filter(|chain| !chain.is_empty());
+    }
+
+    pub fn frame_size(&self) -> usize {
+        self.frame_size
+    }
+
+    pub fn hop_size(&self) -> usize {
+        self.hop_size
+    }
+
+    /// Ajoute un bloc capturé, converti en mono par moyenne des canaux puis filtré
+    pub fn push(&mut self, buffer: &AudioBuffer) {
+        for frame in buffer.samples.chunks_exact(self.channels) {
+            let sum: f32 = frame.iter().map(|&s| s as f32).sum();
+            let sample = sum / (self.channels as f32 * 32768.0);
+            self.pending.push_back(match &mut self.filter {
+                Some(filter) => filter.process(sample),
+                None => sample,
+            });
+        }
+        self.timestamps = Some(buffer.timestamps);
+    }
+
+    /// Retourne la prochaine trame complète, horodatée de son assemblage
+    pub fn next_frame(&mut self) -> Option<Frame> {
+        let mut frame = Frame {
+            samples: Vec::with_capacity(self.frame_size),
+            timestamps: Timestamps::new(Instant::now()),
+        };
+        self.next_frame_into(&mut frame).then_some(frame)
+    }
+
+    /// Comme `next_frame`, mais réutilise les échantillons de `frame` : sans allocation
+    /// dès que sa capacité atteint la taille de trame. Retourne `false` sans trame complète.
+    pub fn next_frame_into(&mut self, frame: &mut Frame) -> bool {
+        if self.pending.len() < self.frame_size {
+            return false;
+        }
+        frame.samples.clear();
+        frame
+            .samples
+            .extend(self.pending.iter().take(self.frame_size).copied());
+        self.pending.drain(..self.hop_size);
+
+        let mut timestamps = self.timestamps.unwrap_or_else(|| Timestamps::new(Instant::now()));
+        timestamps.framed = Some(Instant::now());
+        frame.timestamps = timestamps;
+        true
+    }
+}
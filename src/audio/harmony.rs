@@ -0,0 +1,227 @@
+// src/audio/harmony.rs
+
+use crate::audio::chord::{recognize_chord, Chord};
+use crate::audio::key::{Key, KeyEstimator};
+
+/// Changement de contexte harmonique, produit par le `HarmonyAnalyzer`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HarmonyChange {
+    /// Nouvelle tonalité estimée
+    Key(Key),
+    /// Nouvel accord, ou `None` quand plus aucun accord n'est reconnu
+    Chord(Option<Chord>),
+}
+
+/// Réglages de l'analyse harmonique
+#[derive(Debug, Clone)]
+pub struct HarmonyConfig {
+    /// Niveau (dBFS RMS) en dessous duquel une trame est ignorée
+    pub gate_db: f32,
+    /// Trames de la moyenne glissante de l'estimation de tonalité (~8 s par défaut)
+    pub key_window: usize,
+    /// Trames analysées avant la première tonalité annoncée
+    pub key_min_frames: usize,
+    /// Trames consécutives sur le même accord avant de l'annoncer
+    pub chord_min_frames: usize,
+    /// Similarité minimale avec le gabarit d'accord
+    pub chord_min_score: f32,
+}
+
+impl Default for HarmonyConfig {
+    fn default() -> Self {
+        HarmonyConfig {
+            gate_db: -50.0,
+            key_window: 344,
+            key_min_frames: 86,
+            chord_min_frames: 4,
+            chord_min_score: 0.75,
+        }
+    }
+}
+
+/// Suit la tonalité et l'accord en cours à partir du chromagramme de chaque trame
+pub struct HarmonyAnalyzer {
+    config: HarmonyConfig,
+    keys: KeyEstimator,
+    frames: usize,
+    key: Option<Key>,
+    /// Accord candidat et nombre de trames consécutives où il a été reconnu
+    candidate: Option<(Chord, usize)>,
+    chord: Option<Chord>,
+}
+
+impl HarmonyAnalyzer {
+    pub fn new(config: HarmonyConfig) -> Self {
+        HarmonyAnalyzer {
+            keys: KeyEstimator::new(config.key_window),
+            config,
+            frames: 0,
+            key: None,
+            candidate: None,
+            chord: None,
+        }
+    }
+
+    pub fn config(&self) -> &HarmonyConfig {
+        &self.config
+    }
+
+    /// Tonalité actuellement annoncée
+    pub fn key(&self) -> Option<Key> {
+        self.key
+    }
+
+    /// Accord actuellement annoncé
+    pub fn chord(&self) -> Option<Chord> {
+        self.chord
+    }
+
+    /// Met à jour l'analyse avec le chromagramme et le niveau d'une nouvelle trame ;
+    /// retourne au plus un changement de tonalité puis un changement d'accord, sans allouer
+    pub fn update(
+        &mut self,
+        chroma: &[f32; 12],
+        level_db: f32,
+    ) -> impl Iterator<Item = HarmonyChange> {
+        let mut key_change = None;
+        let mut chord_change = None;
+        let audible = level_db >= self.config.gate_db;
+
+        // Tonalité : les trames muettes ne comptent pas dans la moyenne
+        if audible {
+            self.keys.update(chroma, 1.0);
+            self.frames += 1;
+        }
+        if self.frames >= self.config.key_min_frames {
+            if let Some(key) = self.keys.estimate() {
+                if !self.key.is_some_and(|current| current.same_as(&key)) {
+                    key_change = Some(HarmonyChange::Key(key));
+                }
+                self.key = Some(key);
+            }
+        }
+
+        // Accord : annoncé après quelques trames stables, retiré dès qu'il n'est plus reconnu
+        let recognized = recognize_chord(chroma)
+            .filter(|_| audible)
+            .filter(|chord| chord.score >= self.config.chord_min_score);
+        match recognized {
+            Some(chord) => {
+                let count = match self.candidate {
+                    Some((candidate, count)) if candidate.same_as(&chord) => count + 1,
+                    _ => 1,
+                };
+                self.candidate = Some((chord, count));
+                let announced = self.chord.is_some_and(|current| current.same_as(&chord));
+                if count >= self.config.chord_min_frames && !announced {
+                    self.chord = Some(chord);
+                    chord_change = Some(HarmonyChange::Chord(Some(chord)));
+                }
+            }
+            None => {
+                self.candidate = None;
+                if self.chord.take().is_some() {
+                    chord_change = Some(HarmonyChange::Chord(None));
+                }
+            }
+        }
+        [key_change, chord_change].into_iter().flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::chord::ChordQuality;
+    use crate::audio::key::Mode;
+
+    const C_MAJOR: [f32; 12] = [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0];
+    const A_MINOR: [f32; 12] = [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+
+    fn analyzer() -> HarmonyAnalyzer {
+        HarmonyAnalyzer::new(HarmonyConfig {
+            key_min_frames: 3,
+            ..HarmonyConfig::default()
+        })
+    }
+
+    fn chords(changes: impl Iterator<Item = HarmonyChange>) -> Vec<Option<(u8, ChordQuality)>> {
+        changes
+            .filter_map(|change| match change {
+                HarmonyChange::Chord(chord) => Some(chord.map(|c| (c.root, c.quality))),
+                HarmonyChange::Key(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn chord_is_announced_after_min_frames() {
+        let mut harmony = analyzer();
+        for _ in 1..harmony.config().chord_min_frames {
+            assert!(chords(harmony.update(&C_MAJOR, -20.0)).is_empty());
+        }
+        assert_eq!(
+            chords(harmony.update(&C_MAJOR, -20.0)),
+            [Some((0, ChordQuality::Major))]
+        );
+        assert!(chords(harmony.update(&C_MAJOR, -20.0)).is_empty());
+
+        // Un changement bref ne remplace pas l'accord annoncé
+        let _ = harmony.update(&A_MINOR, -20.0);
+        assert!(chords(harmony.update(&C_MAJOR, -20.0)).is_empty());
+        let mut announced = Vec::new();
+        for _ in 0..harmony.config().chord_min_frames {
+            announced.extend(chords(harmony.update(&A_MINOR, -20.0)));
+        }
+        assert_eq!(announced, [Some((9, ChordQuality::Minor))]);
+        assert_eq!(harmony.chord().map(|c| c.root), Some(9));
+    }
+
+    #[test]
+    fn silence_and_single_notes_clear_the_chord() {
+        let mut harmony = analyzer();
+        for _ in 0..harmony.config().chord_min_frames {
+            let _ = harmony.update(&C_MAJOR, -20.0);
+        }
+        assert!(harmony.chord().is_some());
+        assert_eq!(chords(harmony.update(&C_MAJOR, -80.0)), [None]);
+
+        // Une note isolée et ses harmoniques, ou une quinte à vide, ne sont pas des accords
+        let mut note = [0.0; 12];
+        note[0] = 1.0;
+        note[7] = 0.3;
+        note[4] = 0.1;
+        let mut power = [0.0; 12];
+        power[0] = 1.0;
+        power[7] = 1.0;
+        for chroma in [note, power] {
+            for _ in 0..10 {
+                assert!(chords(harmony.update(&chroma, -20.0)).is_empty());
+            }
+            assert_eq!(harmony.chord(), None);
+        }
+    }
+
+    #[test]
+    fn key_is_announced_once_after_min_frames() {
+        let mut harmony = analyzer();
+        let mut scale = [0.0; 12];
+        for class in [0, 2, 4, 5, 7, 9, 11] {
+            scale[class] = 0.6;
+        }
+        scale[0] = 1.0;
+        scale[7] = 0.8;
+
+        let mut keys = Vec::new();
+        for _ in 0..10 {
+            for change in harmony.update(&scale, -20.0) {
+                if let HarmonyChange::Key(key) = change {
+                    keys.push((key.tonic, key.mode));
+                }
+            }
+            // Les trames muettes ne comptent pas
+            let _ = harmony.update(&[1.0; 12], -80.0);
+        }
+        assert_eq!(keys, [(0, Mode::Major)]);
+    }
+}
@@ -0,0 +1,158 @@
+// src/audio/key.rs
+
+use crate::utils;
+use std::fmt;
+
+/// Profils de tonalité de Krumhansl-Kessler (degré 0 = tonique)
+const MAJOR_PROFILE: [f32; 12] = [
+    6.35, 2.23, 3.48, 2.33, 4.38, 4.09, 2.52, 5.19, 2.39, 3.66, 2.29, 2.88,
+];
+const MINOR_PROFILE: [f32; 12] = [
+    6.33, 2.68, 3.52, 5.38, 2.60, 3.53, 2.54, 4.75, 3.98, 2.69, 3.34, 3.17,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Major,
+    Minor,
+}
+
+/// Tonalité estimée
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Key {
+    /// Classe de hauteur de la tonique (0 = C)
+    pub tonic: u8,
+    pub mode: Mode,
+    /// Corrélation avec le profil de la tonalité, dans [-1, 1]
+    pub correlation: f32,
+}
+
+impl Key {
+    /// Même tonalité, sans tenir compte de la corrélation
+    pub fn same_as(&self, other: &Key) -> bool {
+        self.tonic == other.tonic && self.mode == other.mode
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mode = match self.mode {
+            Mode::Major => "majeur",
+            Mode::Minor => "mineur",
+        };
+        write!(f, "{} {}", utils::pitch_class_name(self.tonic), mode)
+    }
+}
+
+/// Estimation glissante de la tonalité (algorithme de Krumhansl-Schmuckler) : le chromagramme
+/// est moyenné sur une fenêtre exponentielle puis corrélé aux 24 profils majeurs et mineurs
+#[derive(Debug, Clone)]
+pub struct KeyEstimator {
+    accumulated: [f32; 12],
+    /// Poids des trames passées à chaque mise à jour
+    decay: f32,
+}
+
+impl KeyEstimator {
+    /// `window` : nombre de trames de la moyenne glissante
+    pub fn new(window: usize) -> Self {
+        KeyEstimator {
+            accumulated: [0.0; 12],
+            decay: 1.0 - 1.0 / window.max(1) as f32,
+        }
+    }
+
+    /// Ajoute le chromagramme d'une trame, pondéré par `weight` (0 pour une trame muette)
+    pub fn update(&mut self, chroma: &[f32; 12], weight: f32) {
+        for (accumulated, value) in self.accumulated.iter_mut().zip(chroma) {
+            *accumulated = *accumulated * self.decay + value * weight * (1.0 - self.decay);
+        }
+    }
+
+    /// Tonalité la plus corrélée au chromagramme accumulé, ou `None` sans données
+    pub fn estimate(&self) -> Option<Key> {
+        if self.accumulated.iter().all(|&v| v <= 0.0) {
+            return None;
+        }
+        let mut best: Option<Key> = None;
+        for (mode, profile) in [(Mode::Major, &MAJOR_PROFILE), (Mode::Minor, &MINOR_PROFILE)] {
+            for tonic in 0..12u8 {
+                let rotated: [f32; 12] =
+                    std::array::from_fn(|pc| profile[(pc + 12 - tonic as usize) % 12]);
+                let correlation = pearson(&self.accumulated, &rotated);
+                if best.is_none_or(|key| correlation > key.correlation) {
+                    best = Some(Key {
+                        tonic,
+                        mode,
+                        correlation,
+                    });
+                }
+            }
+        }
+        best
+    }
+}
+
+/// Coefficient de corrélation de Pearson entre deux vecteurs de 12 valeurs
+fn pearson(a: &[f32; 12], b: &[f32; 12]) -> f32 {
+    let mean_a = a.iter().sum::<f32>() / 12.0;
+    let mean_b = b.iter().sum::<f32>() / 12.0;
+    let (mut covariance, mut variance_a, mut variance_b) = (0.0, 0.0, 0.0);
+    for (x, y) in a.iter().zip(b) {
+        covariance += (x - mean_a) * (y - mean_b);
+        variance_a += (x - mean_a) * (x - mean_a);
+        variance_b += (y - mean_b) * (y - mean_b);
+    }
+    let denominator = (variance_a * variance_b).sqrt();
+    if denominator > 0.0 {
+        covariance / denominator
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn estimate(chroma: &[f32; 12]) -> Key {
+        let mut keys = KeyEstimator::new(10);
+        for _ in 0..20 {
+            keys.update(chroma, 1.0);
+        }
+        keys.estimate().unwrap()
+    }
+
+    #[test]
+    fn major_scale_gives_its_key() {
+        // Gamme de Do majeur, tonique et dominante accentuées
+        let mut scale = [0.0; 12];
+        for class in [0, 2, 4, 5, 7, 9, 11] {
+            scale[class] = 0.6;
+        }
+        scale[0] = 1.0;
+        scale[7] = 0.8;
+        let key = estimate(&scale);
+        assert_eq!((key.tonic, key.mode), (0, Mode::Major));
+        assert_eq!(key.to_string(), "C majeur");
+
+        // La même gamme transposée en Ré
+        let transposed: [f32; 12] = std::array::from_fn(|pc| scale[(pc + 10) % 12]);
+        assert_eq!(estimate(&transposed).tonic, 2);
+    }
+
+    #[test]
+    fn minor_profile_gives_a_minor_key() {
+        let profile: [f32; 12] = std::array::from_fn(|pc| MINOR_PROFILE[(pc + 3) % 12]);
+        let key = estimate(&profile);
+        assert_eq!((key.tonic, key.mode), (9, Mode::Minor));
+        assert!(key.correlation > 0.99);
+    }
+
+    #[test]
+    fn silent_frames_give_no_key() {
+        let mut keys = KeyEstimator::new(10);
+        keys.update(&[1.0; 12], 0.0);
+        assert_eq!(keys.estimate(), None);
+    }
+}
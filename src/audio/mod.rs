@@ -1,4 +1,25 @@
 // src/audio/mod.rs
 
+pub mod analysis;
+pub mod calibration;
 pub mod capture;
-pub mod process;
\ No newline at end of file
+pub mod chord;
+pub mod chroma;
+pub mod controls;
+pub mod detector;
+pub mod filter;
+pub mod flac;
+pub mod framer;
+pub mod harmony;
+pub mod key;
+pub mod onset;
+pub mod process;
+pub mod profile;
+pub mod quantize;
+pub mod realtime;
+pub mod ring;
+pub mod smoothing;
+pub mod tee;
+pub mod tempo;
+pub mod tracker;
+pub mod window;
\ No newline at end of file
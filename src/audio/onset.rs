@@ -0,0 +1,146 @@
+// src/audio/onset.rs
+
+use std::collections::VecDeque;
+
+/// Durée (s) de la moyenne récente du flux à laquelle chaque trame est comparée
+const HISTORY_SECONDS: f32 = 1.0;
+
+/// Trames par seconde avec le pas de 1024 échantillons à 44,1 kHz
+const DEFAULT_FRAME_RATE: f32 = 44100.0 / 1024.0;
+
+/// Détection d'attaques par flux spectral : hausse de l'amplitude (compressée en log)
+/// d'une trame à l'autre, comparée à la moyenne récente du flux
+#[derive(Debug, Clone)]
+pub struct OnsetDetector {
+    previous: Vec<f32>,
+    /// Spectre compressé de la trame courante, échangé avec `previous` après chaque trame
+    current: Vec<f32>,
+    history: VecDeque<f32>,
+    history_len: usize,
+    /// Multiplicateur de la moyenne récente au-dessus duquel une trame est une attaque
+    sensitivity: f32,
+    /// Flux minimal, pour ne pas déclencher sur le bruit de fond
+    min_flux: f32,
+    /// Trames à ignorer après une attaque
+    refractory: usize,
+    since_onset: usize,
+}
+
+impl Default for OnsetDetector {
+    fn default() -> Self {
+        let history_len = history_len(DEFAULT_FRAME_RATE);
+        OnsetDetector {
+            previous: Vec::new(),
+            current: Vec::new(),
+            history: VecDeque::with_capacity(history_len + 1),
+            history_len,
+            sensitivity: 1.5,
+            min_flux: 0.02,
+            refractory: 3,
+            since_onset: usize::MAX,
+        }
+    }
+}
+
+impl OnsetDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_sensitivity(mut self, sensitivity: f32) -> Self {
+        self.sensitivity = sensitivity;
+        self
+    }
+
+    /// Nombre de trames d'analyse par seconde, qui fixe la longueur de la moyenne récente
+    pub fn with_frame_rate(mut self, frame_rate: f32) -> Self {
+        self.history_len = history_len(frame_rate);
+        self.history = VecDeque::with_capacity(self.history_len + 1);
+        self
+    }
+
+    /// Flux spectral de la trame : moyenne des hausses d'amplitude par rapport à la précédente
+    pub fn flux(&mut self, magnitudes: &[f32]) -> f32 {
+        self.current.clear();
+        self.current.extend(magnitudes.iter().map(|m| m.ln_1p()));
+        let flux = if self.previous.len() == self.current.len() && !self.current.is_empty() {
+            let rise: f32 = self
+                .current
+                .iter()
+                .zip(&self.previous)
+                .map(|(current, previous)| (current - previous).max(0.0))
+                .sum();
+            rise / self.current.len() as f32
+        } else {
+            0.0
+        };
+        std::mem::swap(&mut self.previous, &mut self.current);
+        flux
+    }
+
+    /// Met à jour le détecteur avec le spectre d'une trame ; `true` si la trame est une attaque
+    pub fn process(&mut self, magnitudes: &[f32]) -> bool {
+        let flux = self.flux(magnitudes);
+        let mean = if self.history.is_empty() {
+            0.0
+        } else {
+            self.history.iter().sum::<f32>() / self.history.len() as f32
+        };
+        self.history.push_back(flux);
+        if self.history.len() > self.history_len {
+            self.history.pop_front();
+        }
+
+        self.since_onset = self.since_onset.saturating_add(1);
+        let onset = flux > self.min_flux
+            && flux > mean * self.sensitivity
+            && self.since_onset > self.refractory;
+        if onset {
+            self.since_onset = 0;
+        }
+        onset
+    }
+}
+
+/// Nombre de trames couvrant `HISTORY_SECONDS` à `frame_rate` trames par seconde
+fn history_len(frame_rate: f32) -> usize {
+    (HISTORY_SECONDS * frame_rate).round().max(1.0) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flux_measures_only_rising_magnitudes() {
+        let mut onsets = OnsetDetector::new();
+        assert_eq!(onsets.flux(&[0.0; 8]), 0.0);
+        assert_eq!(onsets.flux(&[0.0; 8]), 0.0);
+        // Marche : chaque bin passe de 0 à 1, soit ln(2) en amplitude compressée
+        let step = onsets.flux(&[1.0; 8]);
+        assert!((step - 2f32.ln()).abs() < 1e-6, "flux {}", step);
+        assert_eq!(onsets.flux(&[1.0; 8]), 0.0);
+        // La retombée ne compte pas
+        assert_eq!(onsets.flux(&[0.0; 8]), 0.0);
+        // Une hausse sur la moitié des bins donne la moitié du flux
+        let half = onsets.flux(&[1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0]);
+        assert!((half - 2f32.ln() / 2.0).abs() < 1e-6, "flux {}", half);
+    }
+
+    #[test]
+    fn history_follows_the_frame_rate() {
+        assert_eq!(OnsetDetector::new().history_len, 43);
+        assert_eq!(OnsetDetector::new().with_frame_rate(100.0).history_len, 100);
+        assert_eq!(OnsetDetector::new().with_frame_rate(0.0).history_len, 1);
+    }
+
+    #[test]
+    fn step_is_an_onset_after_silence() {
+        let mut onsets = OnsetDetector::new();
+        for _ in 0..10 {
+            assert!(!onsets.process(&[0.0; 8]));
+        }
+        assert!(onsets.process(&[1.0; 8]));
+        assert!(!onsets.process(&[1.0; 8]));
+    }
+}
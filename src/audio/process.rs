@@ -1,104 +1,340 @@
 // src/audio/process.rs
 
-use std::sync::mpsc::Receiver;
-use rustfft::{FftPlanner, num_complex::Complex};
-use crate::utils; // Import correct du module utils
-
-pub struct AudioProcessor<F>
-where
-    F: FnMut(u8) + Send + 'static,
-{
-    receiver: Receiver<Vec<i16>>,
-    note_callback: F,
+use std::sync::Arc;
+use std::time::Instant;
+use crate::audio::capture::AudioBuffer;
+use crate::audio::chroma::chromagram;
+use crate::audio::controls::ProcessorControls;
+use crate::audio::detector::{FftPitchDetector, PitchDetector};
+use crate::audio::filter::{FilterChain, FilterConfig};
+use crate::audio::framer::{Frame, Framer};
+use crate::audio::harmony::{HarmonyAnalyzer, HarmonyChange, HarmonyConfig};
+use crate::audio::realtime::{count_allocations, ThreadConfig};
+use crate::audio::ring::RingReceiver;
+use crate::audio::smoothing::{PitchSmoother, SmoothingConfig};
+use crate::audio::tempo::{BeatTracker, TempoConfig};
+use crate::audio::tracker::{NoteChange, NoteTracker, TrackerConfig};
+use crate::events::{
+    BeatEvent, ChordEvent, ChromaEvent, Event, EventBus, KeyEvent, NoteEvent, NoteState,
+    PitchEvent, SpectrumEvent,
+};
+use crate::latency::Timestamps;
+use crate::tuning::Tuning;
+
+/// Fréquence d'échantillonnage de la capture par défaut
+const SAMPLE_RATE: f32 = 44100.0;
+
+/// Nombre de canaux entrelacés dans les blocs capturés
+const CHANNELS: usize = 2;
+
+/// Taille des trames d'analyse et pas entre deux trames (en échantillons mono)
+const FRAME_SIZE: usize = 2048;
+const HOP_SIZE: usize = 1024;
+
+/// Pipeline d'analyse : assemblage des trames, détection de hauteur, segmentation en notes
+/// et analyses harmonique et rythmique, dont les résultats sont publiés sur le bus d'événements
+pub struct AudioProcessor {
+    receiver: RingReceiver<AudioBuffer>,
+    bus: EventBus,
+    sample_rate: f32,
+    framer: Framer,
+    detector: Box<dyn PitchDetector>,
+    smoother: PitchSmoother,
+    tracker: NoteTracker,
+    harmony: HarmonyAnalyzer,
+    beats: BeatTracker,
+    /// Tampons des spectres publiés, réutilisés quand plus aucun abonné ne les lit
+    spectra: SpectrumPool,
+    controls: Option<Arc<ProcessorControls>>,
+    /// Pré-filtrage installé sur le framer au démarrage
+    filters: Option<FilterConfig>,
+    /// Version des contrôles déjà appliquée
+    controls_version: Option<u64>,
+    /// Ordonnancement du thread qui exécute `start`
+    thread: ThreadConfig,
 }
 
-impl<F> AudioProcessor<F>
-where
-    F: FnMut(u8) + Send + 'static,
-{
-    pub fn new(receiver: Receiver<Vec<i16>>, note_callback: F) -> Self {
+impl AudioProcessor {
+    pub fn new(receiver: RingReceiver<AudioBuffer>, bus: EventBus) -> Self {
         AudioProcessor {
             receiver,
-            note_callback,
+            bus,
+            sample_rate: SAMPLE_RATE,
+            framer: Framer::new(CHANNELS, FRAME_SIZE, HOP_SIZE),
+            detector: Box::new(FftPitchDetector::new()),
+            smoother: PitchSmoother::new(SmoothingConfig::default()),
+            tracker: NoteTracker::new(TrackerConfig::default()),
+            harmony: HarmonyAnalyzer::new(HarmonyConfig::default()),
+            beats: BeatTracker::new(TempoConfig::default()),
+            spectra: SpectrumPool::default(),
+            controls: None,
+            filters: None,
+            controls_version: None,
+            thread: ThreadConfig::default(),
+        }
+    }
+
+    /// Fréquence d'échantillonnage des blocs reçus, celle de la capture
+    pub fn with_sample_rate(mut self, sample_rate: f32) -> Self {
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    pub fn with_framer(mut self, framer: Framer) -> Self {
+        self.framer = framer;
+        self
+    }
+
+    pub fn with_detector(mut self, detector: Box<dyn PitchDetector>) -> Self {
+        self.detector = detector;
+        self
+    }
+
+    /// Lissage de la hauteur (médian, correction d'octave, Viterbi) avant la segmentation
+    pub fn with_smoothing(mut self, config: SmoothingConfig) -> Self {
+        self.smoother = PitchSmoother::new(config);
+        self
+    }
+
+    pub fn with_tracker(mut self, config: TrackerConfig) -> Self {
+        self.tracker = NoteTracker::new(config);
+        self
+    }
+
+    pub fn with_harmony(mut self, config: HarmonyConfig) -> Self {
+        self.harmony = HarmonyAnalyzer::new(config);
+        self
+    }
+
+    pub fn with_tempo(mut self, config: TempoConfig) -> Self {
+        self.beats = BeatTracker::new(config);
+        self
+    }
+
+    /// Chaîne de filtres (anti-continu, anti-grondement, passe-bas, réjecteur du secteur)
+    /// appliquée au flux mono avant l'analyse
+    pub fn with_filters(mut self, config: FilterConfig) -> Self {
+        self.filters = Some(config);
+        self
+    }
+
+    /// Paramètres du détecteur et du gate réglables pendant le traitement
+    pub fn with_controls(mut self, controls: Arc<ProcessorControls>) -> Self {
+        self.controls = Some(controls);
+        self.controls_version = None;
+        self
+    }
+
+    /// Priorité temps réel et cœurs du thread de traitement, appliqués au début de `start`
+    pub fn with_thread(mut self, config: ThreadConfig) -> Self {
+        self.thread = config;
+        self
+    }
+
+    /// Applique les contrôles s'ils ont changé depuis la dernière trame
+    fn apply_controls(&mut self) {
+        let Some(controls) = &self.controls else {
+            return;
+        };
+        let version = controls.version();
+        if self.controls_version == Some(version) {
+            return;
+        }
+        let params = controls.get();
+        let config = self.tracker.config_mut();
+        config.gate_open_db = params.gate_open_db;
+        config.gate_close_db = params.gate_close_db;
+        config.min_confidence = params.min_confidence;
+        config.min_frames = params.min_frames;
+        self.detector
+            .set_frequency_range(params.min_frequency, params.max_frequency);
+        self.controls_version = Some(version);
+    }
+
+    /// Analyse la trame suivante si le framer en a une complète et publie ses résultats ;
+    /// retourne `false` quand il faut attendre le bloc suivant
+    fn process_frame(&mut self, frame: &mut Frame, window_gain: f32) -> bool {
+        self.apply_controls();
+        let sample_rate = self.sample_rate;
+        if !self.framer.next_frame_into(frame) {
+            return false;
+        }
+        // Détecter la fréquence fondamentale et la lisser ; la continuité repart de zéro à
+        // chaque ouverture du gate
+        if !self.tracker.gate_open() {
+            self.smoother.reset();
+        }
+        let pitch = self.detector.detect(&frame.samples, sample_rate);
+        let pitch = self.smoother.update(pitch, self.detector.candidates());
+        let mut timestamps = frame.timestamps;
+        timestamps.detected = Some(Instant::now());
+
+        let level_db = frame.level_db();
+        self.bus.publish(Event::Pitch(PitchEvent {
+            pitch,
+            level_db,
+            timestamps,
+        }));
+        if let Some(magnitudes) = self.detector.spectrum() {
+            let bin_hz = sample_rate / frame.samples.len() as f32;
+            let chroma = chromagram(magnitudes, bin_hz, &self.tracker.config().tuning);
+            self.bus.publish(Event::Spectrum(SpectrumEvent {
+                magnitudes: self.spectra.share(magnitudes),
+                bin_hz,
+                window_gain,
+                level_db,
+                timestamps,
+            }));
+            self.bus.publish(Event::Chroma(ChromaEvent {
+                chroma,
+                level_db,
+                timestamps,
+            }));
+            for change in self.harmony.update(&chroma, level_db) {
+                self.bus.publish(harmony_event(change, timestamps));
+            }
+
+            let frame_rate = sample_rate / self.framer.hop_size() as f32;
+            if let Some(beat) = self.beats.update(magnitudes, frame_rate) {
+                self.bus.publish(Event::Beat(BeatEvent {
+                    bpm: beat.bpm,
+                    number: beat.number,
+                    timestamps,
+                }));
+            }
+        }
+
+        for change in self.tracker.update(pitch, level_db) {
+            let tuning = &self.tracker.config().tuning;
+            self.bus
+                .publish(Event::Note(note_event(change, timestamps, tuning)));
         }
+        true
     }
 
+    /// Traite les blocs jusqu'à la fermeture du canal, puis ferme le bus
     pub fn start(mut self) {
-        println!("Démarrage du thread de traitement audio");
+        log::info!("Démarrage du thread de traitement audio");
+        self.thread.apply_or_warn("traitement");
+        if let Some(config) = self.filters.take() {
+            self.framer.set_filter(FilterChain::new(&config, self.sample_rate));
+        }
+        self.detector.prepare(self.framer.frame_size());
+        let window_gain = self.detector.window_gain();
+        let mut frame = Frame {
+            samples: Vec::with_capacity(self.framer.frame_size()),
+            timestamps: Timestamps::new(Instant::now()),
+        };
         loop {
             match self.receiver.recv() {
-                Ok(samples) => {
-                    println!("Échantillons reçus : {} échantillons", samples.len());
-
-                    // Appliquer une fenêtre de Hamming pour réduire les effets de fuite spectrale
-                    let mut windowed_samples = samples.clone();
-                    apply_hamming_window(&mut windowed_samples);
-
-                    // Détecter la fréquence fondamentale avec FFT
-                    if let Some(freq) = detect_pitch_fft(&windowed_samples, 44100.0) {
-                        println!("Fréquence détectée : {:.2} Hz", freq);
-
-                        if let Some(note_number) = utils::frequency_to_midi_note_number(freq) {
-                            if note_number >= 21 && note_number <= 108 {
-                                let note_name = utils::midi_note_number_to_name(note_number);
-                                println!("Note détectée : {} (MIDI {})", note_name, note_number);
-
-                                // Appeler le callback avec le numéro de note MIDI
-                                (self.note_callback)(note_number);
-                            } else {
-                                println!("Note MIDI en dehors de la plage de piano : {}", note_number);
-                            }
-                        } else {
-                            println!("Conversion de fréquence en note MIDI a échoué");
+                Ok(buffer) => {
+                    // Chemin critique surveillé : filtrage, assemblage des trames, détection,
+                    // analyses et publication ne doivent pas allouer
+                    let ((), mut allocations) = count_allocations(|| self.framer.push(&buffer));
+                    loop {
+                        let (more, count) =
+                            count_allocations(|| self.process_frame(&mut frame, window_gain));
+                        allocations += count;
+                        if !more {
+                            break;
                         }
-                    } else {
-                        println!("Aucune fréquence détectée");
+                    }
+                    if allocations > 0 {
+                        log::warn!(
+                            "{} allocation(s) dans le chemin critique du traitement",
+                            allocations
+                        );
                     }
                 }
                 Err(_) => {
-                    eprintln!("Le canal a été fermé");
+                    log::info!("Le canal a été fermé");
+                    if let Some(change) = self.tracker.flush() {
+                        let timestamps = Timestamps::new(Instant::now());
+                        let tuning = &self.tracker.config().tuning;
+                        self.bus
+                            .publish(Event::Note(note_event(change, timestamps, tuning)));
+                    }
                     break;
                 }
             }
         }
+        self.bus.shutdown();
     }
 }
 
-/// Applique une fenêtre de Hamming aux échantillons
-fn apply_hamming_window(samples: &mut [i16]) {
-    let len = samples.len() as f32;
-    for (n, sample) in samples.iter_mut().enumerate() {
-        let window = 0.54 - 0.46 * (2.0 * std::f32::consts::PI * n as f32 / (len - 1.0)).cos();
-        *sample = ((*sample as f32) * window).round() as i16;
-    }
+/// Réserve de tampons partagés pour les spectres publiés : un tampon redevient disponible
+/// quand tous les abonnés l'ont relâché, ce qui évite une allocation par trame
+#[derive(Default)]
+struct SpectrumPool {
+    buffers: Vec<Arc<[f32]>>,
 }
 
-/// Fonction de détection de pitch avec FFT
-fn detect_pitch_fft(samples: &[i16], sample_rate: f32) -> Option<f32> {
-    let len = samples.len();
-    if len == 0 {
-        return None;
+impl SpectrumPool {
+    /// Copie `magnitudes` dans un tampon libre (alloué seulement si aucun ne l'est)
+    fn share(&mut self, magnitudes: &[f32]) -> Arc<[f32]> {
+        for buffer in &mut self.buffers {
+            if let Some(free) = Arc::get_mut(buffer).filter(|b| b.len() == magnitudes.len()) {
+                free.copy_from_slice(magnitudes);
+                return Arc::clone(buffer);
+            }
+        }
+        let buffer: Arc<[f32]> = magnitudes.into();
+        self.buffers.push(Arc::clone(&buffer));
+        buffer
     }
+}
 
-    let mut planner = FftPlanner::new();
-    let fft = planner.plan_fft_forward(len);
+/// Construit l'événement diffusé pour un changement de note, les fréquences nominales
+/// et les écarts en cents suivant l'accord du tracker
+fn note_event(change: NoteChange, timestamps: Timestamps, tuning: &Tuning) -> NoteEvent {
+    match change {
+        NoteChange::On { note, frequency, velocity, confidence } => NoteEvent {
+            state: NoteState::On,
+            note,
+            frequency,
+            cents: tuning.cents(frequency, note),
+            velocity,
+            confidence,
+            timestamps,
+        },
+        NoteChange::Off { note } => NoteEvent {
+            state: NoteState::Off,
+            note,
+            frequency: tuning.frequency(note).unwrap_or(0.0),
+            cents: 0.0,
+            velocity: 0,
+            confidence: 0.0,
+            timestamps,
+        },
+    }
+}
 
-    // Convertir les échantillons en complexes
-    let mut buffer: Vec<Complex<f32>> = samples.iter().map(|&s| Complex::new(s as f32, 0.0)).collect();
-    // Appliquer FFT
-    fft.process(&mut buffer);
+/// Construit l'événement diffusé pour un changement de tonalité ou d'accord
+fn harmony_event(change: HarmonyChange, timestamps: Timestamps) -> Event {
+    match change {
+        HarmonyChange::Key(key) => Event::Key(KeyEvent { key, timestamps }),
+        HarmonyChange::Chord(chord) => Event::Chord(ChordEvent { chord, timestamps }),
+    }
+}
 
-    // Calculer les amplitudes
-    let amplitudes: Vec<f32> = buffer.iter().map(|c| c.norm()).collect();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    // Trouver le pic maximal dans les amplitudes (exclure la DC component)
-    let max_index = amplitudes[1..]
-        .iter()
-        .enumerate()
-        .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
-        .map(|(i, _)| i + 1)?;
+    #[test]
+    fn spectrum_pool_reuses_released_buffers() {
+        let mut pool = SpectrumPool::default();
+        let first = pool.share(&[1.0, 2.0]);
+        let held = pool.share(&[3.0, 4.0]);
+        assert!(!Arc::ptr_eq(&first, &held));
+        assert_eq!(&*first, &[1.0, 2.0]);
 
-    // Calculer la fréquence correspondante
-    let freq = max_index as f32 * sample_rate / len as f32;
-    Some(freq)
+        let address = Arc::as_ptr(&first);
+        drop(first);
+        let reused = pool.share(&[5.0, 6.0]);
+        assert_eq!(Arc::as_ptr(&reused), address);
+        assert_eq!(&*reused, &[5.0, 6.0]);
+        assert_eq!(&*held, &[3.0, 4.0]);
+        assert_eq!(pool.buffers.len(), 2);
+    }
 }
@@ -0,0 +1,268 @@
+// src/audio/profile.rs
+
+use crate::audio::detector::DetectorKind;
+use crate::audio::filter::FilterPreset;
+use crate::audio::framer::Framer;
+use crate::audio::tracker::TrackerConfig;
+use crate::tuning::Tuning;
+use std::ops::RangeInclusive;
+
+/// Instrument ou tessiture pour lequel la détection est réglée
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InstrumentProfile {
+    /// Étendue du piano, réglages historiques du programme
+    #[default]
+    General,
+    /// Toutes les tessitures, de la basse à la soprano
+    Voice,
+    Soprano,
+    MezzoSoprano,
+    Alto,
+    Tenor,
+    Baritone,
+    BassVoice,
+    Guitar,
+    /// Basse électrique à quatre cordes
+    Bass,
+    Flute,
+    Violin,
+    Whistle,
+}
+
+impl InstrumentProfile {
+    /// Nom utilisé en ligne de commande et dans la configuration
+    pub fn parse(name: &str) -> Option<InstrumentProfile> {
+        match name {
+            "general" => Some(InstrumentProfile::General),
+            "voice" => Some(InstrumentProfile::Voice),
+            "soprano" => Some(InstrumentProfile::Soprano),
+            "mezzo-soprano" => Some(InstrumentProfile::MezzoSoprano),
+            "alto" => Some(InstrumentProfile::Alto),
+            "tenor" => Some(InstrumentProfile::Tenor),
+            "baritone" => Some(InstrumentProfile::Baritone),
+            "bass-voice" => Some(InstrumentProfile::BassVoice),
+            "guitar" => Some(InstrumentProfile::Guitar),
+            "bass" => Some(InstrumentProfile::Bass),
+            "flute" => Some(InstrumentProfile::Flute),
+            "violin" => Some(InstrumentProfile::Violin),
+            "whistle" => Some(InstrumentProfile::Whistle),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            InstrumentProfile::General => "general",
+            InstrumentProfile::Voice => "voice",
+            InstrumentProfile::Soprano => "soprano",
+            InstrumentProfile::MezzoSoprano => "mezzo-soprano",
+            InstrumentProfile::Alto => "alto",
+            InstrumentProfile::Tenor => "tenor",
+            InstrumentProfile::Baritone => "baritone",
+            InstrumentProfile::BassVoice => "bass-voice",
+            InstrumentProfile::Guitar => "guitar",
+            InstrumentProfile::Bass => "bass",
+            InstrumentProfile::Flute => "flute",
+            InstrumentProfile::Violin => "violin",
+            InstrumentProfile::Whistle => "whistle",
+        }
+    }
+}
+
+/// Réglages de la détection pour un instrument : bande des fondamentales, algorithme,
+/// trames assez longues pour les notes les plus graves et assez courtes pour suivre
+/// les plus aiguës, seuils du gate et pré-filtrage
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetectionProfile {
+    pub instrument: InstrumentProfile,
+    /// Bande de recherche de la fondamentale, en Hz ; seules ses notes sont transcrites
+    pub min_frequency: f32,
+    pub max_frequency: f32,
+    pub detector: DetectorKind,
+    /// Taille des trames et pas entre deux trames, en échantillons mono à 44,1 ou 48 kHz
+    pub frame_size: usize,
+    pub hop_size: usize,
+    /// Seuils du gate, en dBFS
+    pub gate_open_db: f32,
+    pub gate_close_db: f32,
+    pub filter: FilterPreset,
+}
+
+impl Default for DetectionProfile {
+    fn default() -> Self {
+        DetectionProfile::preset(InstrumentProfile::General)
+    }
+}
+
+impl DetectionProfile {
+    /// Réglages de l'instrument ; les bandes couvrent sa tessiture avec un demi-ton de marge,
+    /// et YIN a besoin d'au moins deux périodes de la note la plus grave par trame
+    pub fn preset(instrument: InstrumentProfile) -> Self {
+        use DetectorKind::{Fft, Yin};
+        use InstrumentProfile::*;
+        let (min_frequency, max_frequency, detector, frame_size, hop_size) = match instrument {
+            // A0 à C8
+            General => (27.5, 4186.0, Fft, 2048, 1024),
+            // E2 à C6
+            Voice => (78.0, 1100.0, Yin, 2048, 512),
+            // C4 à C6
+            Soprano => (250.0, 1100.0, Yin, 1024, 256),
+            // A3 à A5
+            MezzoSoprano => (210.0, 930.0, Yin, 1024, 256),
+            // F3 à F5
+            Alto => (165.0, 740.0, Yin, 1024, 256),
+            // C3 à C5
+            Tenor => (125.0, 550.0, Yin, 1024, 256),
+            // A2 à A4
+            Baritone => (105.0, 460.0, Yin, 2048, 512),
+            // E2 à E4
+            BassVoice => (78.0, 350.0, Yin, 2048, 512),
+            // E2 à E6 (corde aiguë, 24e case)
+            Guitar => (78.0, 1400.0, Yin, 2048, 512),
+            // E1 à G4 (corde de sol, 24e case) : ~1100 échantillons par période de E1
+            Bass => (39.0, 420.0, Yin, 4096, 1024),
+            // C4 à D7, son presque pur : le pic du spectre suffit
+            Flute => (250.0, 2450.0, Fft, 2048, 256),
+            // G3 à A7
+            Violin => (185.0, 3600.0, Yin, 1024, 256),
+            // Sinusoïde presque pure, aiguë et rapide
+            Whistle => (500.0, 4500.0, Fft, 1024, 256),
+        };
+        let (gate_open_db, gate_close_db) = match instrument {
+            Voice | Soprano | MezzoSoprano | Alto | Tenor | Baritone | BassVoice => (-40.0, -46.0),
+            Flute | Whistle => (-42.0, -48.0),
+            General | Guitar | Bass | Violin => (-45.0, -50.0),
+        };
+        let filter = match instrument {
+            General => FilterPreset::General,
+            Voice | Soprano | MezzoSoprano | Alto | Tenor | Baritone | BassVoice => {
+                FilterPreset::Voice
+            }
+            Guitar => FilterPreset::Guitar,
+            Bass => FilterPreset::Bass,
+            Flute => FilterPreset::Flute,
+            Violin => FilterPreset::Violin,
+            Whistle => FilterPreset::Whistle,
+        };
+        DetectionProfile {
+            instrument,
+            min_frequency,
+            max_frequency,
+            detector,
+            frame_size,
+            hop_size,
+            gate_open_db,
+            gate_close_db,
+            filter,
+        }
+    }
+
+    pub fn framer(&self, channels: usize) -> Framer {
+        Framer::new(channels, self.frame_size, self.hop_size)
+    }
+
+    /// Segmentation limitée aux notes de la bande, avec les seuils du gate du profil
+    pub fn tracker(&self, tuning: Tuning) -> TrackerConfig {
+        TrackerConfig {
+            gate_open_db: self.gate_open_db,
+            gate_close_db: self.gate_close_db,
+            note_range: note_range(&tuning, self.min_frequency, self.max_frequency),
+            tuning,
+            ..TrackerConfig::default()
+        }
+    }
+}
+
+/// Notes MIDI les plus proches des bornes d'une bande de fréquences, dans un accord donné
+pub fn note_range(tuning: &Tuning, min_frequency: f32, max_frequency: f32) -> RangeInclusive<u8> {
+    let low = tuning.note(min_frequency).unwrap_or(0);
+    let high = tuning.note(max_frequency).unwrap_or(127);
+    low..=high
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [InstrumentProfile; 13] = [
+        InstrumentProfile::General,
+        InstrumentProfile::Voice,
+        InstrumentProfile::Soprano,
+        InstrumentProfile::MezzoSoprano,
+        InstrumentProfile::Alto,
+        InstrumentProfile::Tenor,
+        InstrumentProfile::Baritone,
+        InstrumentProfile::BassVoice,
+        InstrumentProfile::Guitar,
+        InstrumentProfile::Bass,
+        InstrumentProfile::Flute,
+        InstrumentProfile::Violin,
+        InstrumentProfile::Whistle,
+    ];
+
+    #[test]
+    fn names_round_trip() {
+        for instrument in ALL {
+            assert_eq!(
+                InstrumentProfile::parse(instrument.name()),
+                Some(instrument)
+            );
+        }
+        assert_eq!(InstrumentProfile::parse("piano"), None);
+    }
+
+    #[test]
+    fn bands_fit_their_frames() {
+        for sample_rate in [44100.0, 48000.0] {
+            for instrument in ALL {
+                let profile = DetectionProfile::preset(instrument);
+                let frame_size = profile.frame_size as f32;
+                assert!(
+                    profile.min_frequency < profile.max_frequency,
+                    "{:?}",
+                    instrument
+                );
+                assert!(
+                    profile.max_frequency <= sample_rate / 2.0,
+                    "{:?}",
+                    instrument
+                );
+                assert!(profile.hop_size <= profile.frame_size, "{:?}", instrument);
+                match profile.detector {
+                    // Deux périodes de la note la plus grave dans chaque trame
+                    DetectorKind::Yin => {
+                        let period = sample_rate / profile.min_frequency;
+                        assert!(
+                            2.0 * period <= frame_size,
+                            "{:?} à {} Hz",
+                            instrument,
+                            sample_rate
+                        );
+                    }
+                    // La note la plus grave tombe au-delà du premier intervalle du spectre
+                    _ => {
+                        let bin_hz = sample_rate / frame_size;
+                        assert!(
+                            bin_hz < profile.min_frequency,
+                            "{:?} à {} Hz",
+                            instrument,
+                            sample_rate
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn note_range_covers_the_band() {
+        let tuning = Tuning::default();
+        assert_eq!(note_range(&tuning, 27.5, 4186.0), 21..=108);
+        let general = DetectionProfile::preset(InstrumentProfile::General);
+        assert_eq!(general.tracker(tuning.clone()).note_range, 21..=108);
+
+        // E2 à E6 avec un demi-ton de marge de chaque côté
+        let guitar = DetectionProfile::preset(InstrumentProfile::Guitar);
+        assert_eq!(guitar.tracker(tuning).note_range, 39..=89);
+    }
+}
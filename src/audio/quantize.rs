@@ -0,0 +1,340 @@
+// src/audio/quantize.rs
+
+use std::time::{Duration, Instant};
+
+/// Subdivision du temps sur laquelle les notes sont alignées
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Grid {
+    /// Croches (1/8)
+    Eighth,
+    /// Doubles croches (1/16)
+    Sixteenth,
+    /// Triolets de croches (1/8t)
+    EighthTriplet,
+    /// Triolets de doubles croches (1/16t)
+    SixteenthTriplet,
+}
+
+impl Grid {
+    /// Nombre de pas de grille par temps (noire)
+    pub fn steps_per_beat(self) -> u32 {
+        match self {
+            Grid::Eighth => 2,
+            Grid::Sixteenth => 4,
+            Grid::EighthTriplet => 3,
+            Grid::SixteenthTriplet => 6,
+        }
+    }
+
+    fn is_triplet(self) -> bool {
+        matches!(self, Grid::EighthTriplet | Grid::SixteenthTriplet)
+    }
+
+    /// Grille écrite comme `1/8`, `1/16`, `1/8t` ou `1/16t`
+    pub fn parse(text: &str) -> Option<Grid> {
+        match text {
+            "1/8" => Some(Grid::Eighth),
+            "1/16" => Some(Grid::Sixteenth),
+            "1/8t" => Some(Grid::EighthTriplet),
+            "1/16t" => Some(Grid::SixteenthTriplet),
+            _ => None,
+        }
+    }
+}
+
+/// Origine du tempo de la grille
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TempoSource {
+    /// Tempo fixe en BPM, grille calée sur le début de la première note
+    Fixed(f32),
+    /// Tempo et position des temps suivis par le `BeatTracker`
+    Detected,
+}
+
+/// Réglages de la quantification
+#[derive(Debug, Clone)]
+pub struct QuantizeConfig {
+    pub grid: Grid,
+    /// Part du déplacement vers la grille : 0 ne change rien, 1 aligne exactement
+    pub strength: f32,
+    /// Retard des pas impairs (grilles binaires) : 0 droit, 1 ternaire
+    pub swing: f32,
+    pub tempo: TempoSource,
+}
+
+impl QuantizeConfig {
+    pub fn new(grid: Grid, tempo: TempoSource) -> Self {
+        QuantizeConfig {
+            grid,
+            strength: 1.0,
+            swing: 0.0,
+            tempo,
+        }
+    }
+
+    pub fn with_strength(mut self, strength: f32) -> Self {
+        self.strength = strength.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_swing(mut self, swing: f32) -> Self {
+        self.swing = swing.clamp(0.0, 1.0);
+        self
+    }
+}
+
+/// Note complète, de son Note On à son Note Off
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoteSpan {
+    pub note: u8,
+    pub frequency: f32,
+    pub velocity: u8,
+    pub start: Instant,
+    pub end: Instant,
+}
+
+/// Aligne les débuts et fins de notes sur une grille rythmique, à tempo fixe ou
+/// calée sur les battements détectés
+#[derive(Debug, Clone)]
+pub struct Quantizer {
+    config: QuantizeConfig,
+    /// Battements reçus (instant, tempo), dans l'ordre
+    beats: Vec<(Instant, f32)>,
+    origin: Option<Instant>,
+}
+
+impl Quantizer {
+    pub fn new(config: QuantizeConfig) -> Self {
+        Quantizer {
+            config,
+            beats: Vec::new(),
+            origin: None,
+        }
+    }
+
+    pub fn config(&self) -> &QuantizeConfig {
+        &self.config
+    }
+
+    /// Cale la grille à tempo fixe sur cet instant (par défaut le début de la première note)
+    pub fn set_origin(&mut self, origin: Instant) {
+        self.origin = Some(origin);
+    }
+
+    /// Enregistre un battement détecté (utilisé avec `TempoSource::Detected`)
+    pub fn add_beat(&mut self, at: Instant, bpm: f32) {
+        self.beats.push((at, bpm));
+    }
+
+    /// Temps de référence et tempo de la grille autour de `time`
+    fn anchor(&self, time: Instant) -> Option<(Instant, f32)> {
+        match self.config.tempo {
+            TempoSource::Fixed(bpm) => self.origin.map(|origin| (origin, bpm)),
+            TempoSource::Detected => {
+                // Dernier battement avant `time`, ou le premier si la note le précède
+                let index = self.beats.partition_point(|(at, _)| *at <= time);
+                self.beats.get(index.saturating_sub(1)).copied()
+            }
+        }
+    }
+
+    /// Instant aligné sur la grille, ou inchangé sans tempo connu
+    pub fn quantize(&self, time: Instant) -> Instant {
+        let Some((anchor, bpm)) = self.anchor(time).filter(|(_, bpm)| *bpm > 0.0) else {
+            return time;
+        };
+        let step = 60.0 / bpm as f64 / self.config.grid.steps_per_beat() as f64;
+        let offset = signed_seconds(time, anchor);
+
+        let swing = if self.config.grid.is_triplet() {
+            0.0
+        } else {
+            self.config.swing as f64 * step / 3.0
+        };
+        let point = |k: i64| k as f64 * step + if k % 2 != 0 { swing } else { 0.0 };
+        let nearest = (offset / step).floor() as i64;
+        let target = (nearest - 1..=nearest + 1)
+            .map(point)
+            .min_by(|a, b| (a - offset).abs().total_cmp(&(b - offset).abs()))
+            .unwrap_or(offset);
+
+        let moved = offset + (target - offset) * self.config.strength as f64;
+        shift(anchor, moved)
+    }
+
+    /// Aligne le début et la fin d'une note ; une note réduite à rien dure au moins un pas
+    pub fn quantize_note(&mut self, note: &mut NoteSpan) {
+        if self.origin.is_none() {
+            self.origin = Some(note.start);
+        }
+        let start = self.quantize(note.start);
+        let mut end = self.quantize(note.end);
+        if end <= start {
+            let bpm = self.anchor(note.start).map(|(_, bpm)| bpm).unwrap_or(120.0);
+            let step = 60.0 / bpm / self.config.grid.steps_per_beat() as f32;
+            end = start + Duration::from_secs_f32(step * self.config.strength.max(0.1));
+        }
+        note.start = start;
+        note.end = end;
+    }
+}
+
+/// `time - reference` en secondes, négatif si `time` précède la référence
+fn signed_seconds(time: Instant, reference: Instant) -> f64 {
+    if time >= reference {
+        (time - reference).as_secs_f64()
+    } else {
+        -(reference - time).as_secs_f64()
+    }
+}
+
+/// `reference + seconds`, avec `seconds` éventuellement négatif
+fn shift(reference: Instant, seconds: f64) -> Instant {
+    if seconds >= 0.0 {
+        reference + Duration::from_secs_f64(seconds)
+    } else {
+        reference
+            .checked_sub(Duration::from_secs_f64(-seconds))
+            .unwrap_or(reference)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Instant à `seconds` secondes de `origin`
+    fn at(origin: Instant, seconds: f64) -> Instant {
+        shift(origin, seconds)
+    }
+
+    fn assert_at(time: Instant, origin: Instant, expected: f64) {
+        let seconds = signed_seconds(time, origin);
+        assert!(
+            (seconds - expected).abs() < 1e-6,
+            "{} s au lieu de {} s",
+            seconds,
+            expected
+        );
+    }
+
+    /// Quantificateur à tempo fixe calé sur `origin`, une seconde après un instant quelconque
+    /// pour pouvoir tester les notes qui précèdent l'origine
+    fn fixed(grid: Grid, bpm: f32) -> (Quantizer, Instant) {
+        let origin = Instant::now() + Duration::from_secs(1);
+        let mut quantizer = Quantizer::new(QuantizeConfig::new(grid, TempoSource::Fixed(bpm)));
+        quantizer.set_origin(origin);
+        (quantizer, origin)
+    }
+
+    #[test]
+    fn parse_grids() {
+        assert_eq!(Grid::parse("1/8"), Some(Grid::Eighth));
+        assert_eq!(Grid::parse("1/16t"), Some(Grid::SixteenthTriplet));
+        assert_eq!(Grid::parse("1/32"), None);
+        assert_eq!(Grid::EighthTriplet.steps_per_beat(), 3);
+    }
+
+    #[test]
+    fn snaps_to_the_nearest_step() {
+        // 120 BPM en doubles croches : un pas toutes les 125 ms
+        let (quantizer, origin) = fixed(Grid::Sixteenth, 120.0);
+        for (time, expected) in [(0.13, 0.125), (0.19, 0.25), (0.0, 0.0), (1.03, 1.0)] {
+            assert_at(quantizer.quantize(at(origin, time)), origin, expected);
+        }
+        // Avant l'origine, la grille se prolonge vers le passé
+        assert_at(quantizer.quantize(at(origin, -0.1)), origin, -0.125);
+    }
+
+    #[test]
+    fn strength_moves_part_of_the_way() {
+        let (quantizer, origin) = fixed(Grid::Sixteenth, 120.0);
+        let mut quantizer = Quantizer::new(quantizer.config().clone().with_strength(0.5));
+        quantizer.set_origin(origin);
+        assert_at(quantizer.quantize(at(origin, 0.15)), origin, 0.1375);
+    }
+
+    #[test]
+    fn triplet_grid() {
+        // 1/8t à 60 BPM : un pas par tiers de seconde
+        let (quantizer, origin) = fixed(Grid::EighthTriplet, 60.0);
+        assert_at(quantizer.quantize(at(origin, 0.3)), origin, 1.0 / 3.0);
+        assert_at(quantizer.quantize(at(origin, 0.6)), origin, 2.0 / 3.0);
+    }
+
+    #[test]
+    fn swing_delays_odd_steps_only() {
+        // Croches à 120 BPM (250 ms), swing complet : les pas impairs passent au tiers
+        let config = QuantizeConfig::new(Grid::Eighth, TempoSource::Fixed(120.0)).with_swing(1.0);
+        let mut quantizer = Quantizer::new(config);
+        let origin = Instant::now();
+        quantizer.set_origin(origin);
+        let third = 0.25 + 0.25 / 3.0;
+        assert_at(quantizer.quantize(at(origin, 0.27)), origin, third);
+        assert_at(quantizer.quantize(at(origin, 0.2)), origin, third);
+        assert_at(quantizer.quantize(at(origin, 0.45)), origin, 0.5);
+        assert_at(quantizer.quantize(at(origin, 0.52)), origin, 0.5);
+        assert_at(
+            quantizer.quantize(at(origin, 0.8)),
+            origin,
+            0.75 + 0.25 / 3.0,
+        );
+    }
+
+    #[test]
+    fn swing_is_ignored_on_triplet_grids() {
+        let config =
+            QuantizeConfig::new(Grid::EighthTriplet, TempoSource::Fixed(60.0)).with_swing(1.0);
+        let mut quantizer = Quantizer::new(config);
+        let origin = Instant::now();
+        quantizer.set_origin(origin);
+        assert_at(quantizer.quantize(at(origin, 0.3)), origin, 1.0 / 3.0);
+    }
+
+    #[test]
+    fn detected_beats_anchor_the_grid() {
+        let origin = Instant::now() + Duration::from_secs(1);
+        let mut quantizer =
+            Quantizer::new(QuantizeConfig::new(Grid::Sixteenth, TempoSource::Detected));
+        // Sans battement, rien ne bouge
+        assert_at(quantizer.quantize(at(origin, 0.33)), origin, 0.33);
+
+        quantizer.add_beat(at(origin, 0.1), 60.0);
+        quantizer.add_beat(at(origin, 1.1), 120.0);
+        // Premier battement : pas de 250 ms depuis 0,1 s
+        assert_at(quantizer.quantize(at(origin, 0.33)), origin, 0.35);
+        // Avant le premier battement, la grille de celui-ci
+        assert_at(quantizer.quantize(at(origin, 0.0)), origin, 0.1);
+        // Après le deuxième, son tempo : pas de 125 ms depuis 1,1 s
+        assert_at(quantizer.quantize(at(origin, 1.3)), origin, 1.35);
+    }
+
+    #[test]
+    fn quantize_note_keeps_at_least_one_step() {
+        let mut quantizer = Quantizer::new(QuantizeConfig::new(
+            Grid::Sixteenth,
+            TempoSource::Fixed(120.0),
+        ));
+        let start = Instant::now() + Duration::from_secs(1);
+        let mut first = NoteSpan {
+            note: 60,
+            frequency: 261.6,
+            velocity: 100,
+            start,
+            end: at(start, 0.3),
+        };
+        quantizer.quantize_note(&mut first);
+        // La grille est calée sur le début de la première note
+        assert_at(first.start, start, 0.0);
+        assert_at(first.end, start, 0.25);
+
+        let mut short = NoteSpan {
+            start: at(start, 0.51),
+            end: at(start, 0.54),
+            ..first.clone()
+        };
+        quantizer.quantize_note(&mut short);
+        assert_at(short.start, start, 0.5);
+        assert_at(short.end, start, 0.625);
+    }
+}
@@ -0,0 +1,302 @@
+// src/audio/realtime.rs
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::mem;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Politique d'ordonnancement d'un thread audio
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Scheduling {
+    /// Ordonnancement partagé par défaut du système
+    #[default]
+    Normal,
+    /// `SCHED_FIFO` : garde le processeur jusqu'à ce qu'il se bloque
+    Fifo(i32),
+    /// `SCHED_RR` : comme FIFO, mais partage le processeur entre threads de même priorité
+    RoundRobin(i32),
+}
+
+impl Scheduling {
+    /// Priorité utilisée quand elle n'est pas précisée, sous celle des threads du noyau
+    pub const DEFAULT_PRIORITY: i32 = 70;
+
+    /// Nom utilisé en ligne de commande : `normal`, `fifo`, `rr`, suivis
+    /// éventuellement de `:<priorité>` (1 à 99) ; `normal` n'a pas de priorité
+    pub fn parse(name: &str) -> Option<Scheduling> {
+        if name == "normal" {
+            return Some(Scheduling::Normal);
+        }
+        let (policy, priority) = match name.split_once(':') {
+            Some((policy, priority)) => (policy, priority.parse().ok()?),
+            None => (name, Self::DEFAULT_PRIORITY),
+        };
+        match policy {
+            "fifo" => Some(Scheduling::Fifo(priority)),
+            "rr" => Some(Scheduling::RoundRobin(priority)),
+            _ => None,
+        }
+    }
+
+    /// Politique et priorité au sens de `pthread_setschedparam`
+    fn policy(self) -> (libc::c_int, libc::c_int) {
+        match self {
+            Scheduling::Normal => (libc::SCHED_OTHER, 0),
+            Scheduling::Fifo(priority) => (libc::SCHED_FIFO, priority),
+            Scheduling::RoundRobin(priority) => (libc::SCHED_RR, priority),
+        }
+    }
+}
+
+/// Échec d'un réglage temps réel
+#[derive(Debug)]
+pub enum RealtimeError {
+    /// Priorité hors des bornes de la politique
+    InvalidPriority {
+        priority: i32,
+        min: i32,
+        max: i32,
+    },
+    /// Numéro de cœur hors de ceux que le système sait désigner
+    InvalidCpu(usize),
+    Scheduling(io::Error),
+    Affinity(io::Error),
+    LockMemory(io::Error),
+}
+
+impl fmt::Display for RealtimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RealtimeError::InvalidPriority { priority, min, max } => write!(
+                f,
+                "priorité {} invalide (attendu : {} à {})",
+                priority, min, max
+            ),
+            RealtimeError::InvalidCpu(cpu) => write!(f, "cœur {} invalide", cpu),
+            RealtimeError::Scheduling(err) => write!(
+                f,
+                "impossible de changer l'ordonnancement : {}{}",
+                err,
+                permission_hint(err, "CAP_SYS_NICE ou limite RLIMIT_RTPRIO")
+            ),
+            RealtimeError::Affinity(err) => {
+                write!(f, "impossible de fixer les cœurs du thread : {}", err)
+            }
+            RealtimeError::LockMemory(err) => write!(
+                f,
+                "impossible de verrouiller la mémoire : {}{}",
+                err,
+                permission_hint(err, "CAP_IPC_LOCK ou limite RLIMIT_MEMLOCK")
+            ),
+        }
+    }
+}
+
+impl Error for RealtimeError {}
+
+fn permission_hint(err: &io::Error, what: &'static str) -> String {
+    match err.raw_os_error() {
+        Some(libc::EPERM) | Some(libc::ENOMEM) => format!(" ({} nécessaire)", what),
+        _ => String::new(),
+    }
+}
+
+/// Réglages d'ordonnancement d'un thread audio (capture ou traitement)
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ThreadConfig {
+    pub scheduling: Scheduling,
+    /// Cœurs sur lesquels le thread peut tourner ; tous si la liste est vide
+    pub cpus: Vec<usize>,
+}
+
+impl ThreadConfig {
+    /// Applique les réglages au thread appelant
+    pub fn apply(&self) -> Result<(), RealtimeError> {
+        if !self.cpus.is_empty() {
+            set_affinity(&self.cpus)?;
+        }
+        if self.scheduling != Scheduling::Normal {
+            set_scheduling(self.scheduling)?;
+        }
+        Ok(())
+    }
+
+    /// Applique les réglages au thread appelant ; un échec est signalé dans le journal
+    /// et le thread continue avec l'ordonnancement par défaut
+    pub fn apply_or_warn(&self, thread: &str) {
+        if *self == ThreadConfig::default() {
+            return;
+        }
+        match self.apply() {
+            Ok(()) => log::info!(
+                "Thread de {} : {:?}, cœurs {:?}",
+                thread,
+                self.scheduling,
+                self.cpus
+            ),
+            Err(err) => log::warn!("Thread de {} : {}", thread, err),
+        }
+    }
+}
+
+/// Liste de cœurs en ligne de commande : `2`, `0,2`, `2-3`
+pub fn parse_cpus(spec: &str) -> Option<Vec<usize>> {
+    let mut cpus = Vec::new();
+    for part in spec.split(',').map(str::trim) {
+        match part.split_once('-') {
+            Some((first, last)) => {
+                let (first, last): (usize, usize) = (first.parse().ok()?, last.parse().ok()?);
+                if first > last {
+                    return None;
+                }
+                cpus.extend(first..=last);
+            }
+            None => cpus.push(part.parse().ok()?),
+        }
+    }
+    Some(cpus)
+}
+
+fn set_scheduling(scheduling: Scheduling) -> Result<(), RealtimeError> {
+    let (policy, priority) = scheduling.policy();
+    let (min, max) = unsafe {
+        (
+            libc::sched_get_priority_min(policy),
+            libc::sched_get_priority_max(policy),
+        )
+    };
+    if !(min..=max).contains(&priority) {
+        return Err(RealtimeError::InvalidPriority { priority, min, max });
+    }
+    let param = libc::sched_param {
+        sched_priority: priority,
+    };
+    match unsafe { libc::pthread_setschedparam(libc::pthread_self(), policy, &param) } {
+        0 => Ok(()),
+        code => Err(RealtimeError::Scheduling(io::Error::from_raw_os_error(
+            code,
+        ))),
+    }
+}
+
+fn set_affinity(cpus: &[usize]) -> Result<(), RealtimeError> {
+    let mut set: libc::cpu_set_t = unsafe { mem::zeroed() };
+    for &cpu in cpus {
+        if cpu >= libc::CPU_SETSIZE as usize {
+            return Err(RealtimeError::InvalidCpu(cpu));
+        }
+        unsafe { libc::CPU_SET(cpu, &mut set) };
+    }
+    // Le pid 0 désigne le thread appelant
+    match unsafe { libc::sched_setaffinity(0, mem::size_of::<libc::cpu_set_t>(), &set) } {
+        0 => Ok(()),
+        _ => Err(RealtimeError::Affinity(io::Error::last_os_error())),
+    }
+}
+
+/// Verrouille en mémoire vive toutes les pages du processus, présentes et à venir, pour
+/// qu'aucun défaut de page ne bloque les threads audio
+pub fn lock_memory() -> Result<(), RealtimeError> {
+    match unsafe { libc::mlockall(libc::MCL_CURRENT | libc::MCL_FUTURE) } {
+        0 => Ok(()),
+        _ => Err(RealtimeError::LockMemory(io::Error::last_os_error())),
+    }
+}
+
+thread_local! {
+    /// Le thread exécute une section surveillée par `count_allocations`
+    static WATCHING: Cell<bool> = const { Cell::new(false) };
+    /// Allocations faites dans les sections surveillées de ce thread
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+}
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+/// Allocateur du système qui compte les allocations faites dans les sections surveillées
+/// par `count_allocations` ; à installer par le programme :
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOCATOR: CountingAllocator = CountingAllocator;
+/// ```
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn record() {
+        INSTALLED.store(true, Ordering::Relaxed);
+        // `try_with` : les variables du thread peuvent être déjà détruites
+        let _ = WATCHING.try_with(|watching| {
+            if watching.get() {
+                ALLOCATIONS.with(|count| count.set(count.get() + 1));
+            }
+        });
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        Self::record();
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        Self::record();
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        Self::record();
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// `CountingAllocator` est l'allocateur du programme (constaté dès sa première allocation)
+pub fn allocations_counted() -> bool {
+    INSTALLED.load(Ordering::Relaxed)
+}
+
+/// Exécute `f` et retourne le nombre d'allocations qu'il a faites sur ce thread
+/// (toujours 0 si `CountingAllocator` n'est pas installé)
+pub fn count_allocations<R>(f: impl FnOnce() -> R) -> (R, u64) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let outer = WATCHING.with(|watching| watching.replace(true));
+    let result = f();
+    WATCHING.with(|watching| watching.set(outer));
+    (result, ALLOCATIONS.with(Cell::get) - before)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scheduling_names() {
+        assert_eq!(Scheduling::parse("normal"), Some(Scheduling::Normal));
+        assert_eq!(Scheduling::parse("fifo:99"), Some(Scheduling::Fifo(99)));
+        assert_eq!(
+            Scheduling::parse("rr"),
+            Some(Scheduling::RoundRobin(Scheduling::DEFAULT_PRIORITY))
+        );
+        assert_eq!(Scheduling::parse("fifo:x"), None);
+        assert_eq!(Scheduling::parse("normal:5"), None);
+        assert_eq!(Scheduling::parse("deadline"), None);
+    }
+
+    #[test]
+    fn cpu_lists() {
+        assert_eq!(parse_cpus("0,2-3"), Some(vec![0, 2, 3]));
+        assert_eq!(parse_cpus(" 1 , 4-4"), Some(vec![1, 4]));
+        assert_eq!(parse_cpus("3-1"), None);
+        assert_eq!(parse_cpus("0,,2"), None);
+        assert_eq!(parse_cpus("1,"), None);
+        assert_eq!(parse_cpus(""), None);
+        assert_eq!(parse_cpus("a-2"), None);
+    }
+}
@@ -0,0 +1,373 @@
+// src/audio/ring.rs
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{RecvError, SendError};
+use std::sync::{Arc, Condvar, Mutex};
+
+/// Comportement lorsque le tampon est plein (le traitement ne suit pas la capture)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Jeter le bloc le plus ancien : privilégie la fraîcheur (latence bornée)
+    DropOldest,
+    /// Jeter le bloc entrant : privilégie la continuité du début de la file
+    DropNewest,
+    /// Bloquer l'émetteur jusqu'à ce qu'une place se libère
+    Block,
+}
+
+/// Compteurs du canal, lisibles depuis n'importe quel thread
+#[derive(Debug, Default)]
+pub struct ChannelMetrics {
+    sent: AtomicU64,
+    received: AtomicU64,
+    dropped: AtomicU64,
+    overruns: AtomicU64,
+    max_depth: AtomicUsize,
+}
+
+/// Valeurs des compteurs à un instant donné
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MetricsSnapshot {
+    /// Blocs acceptés dans le tampon
+    pub sent: u64,
+    /// Blocs remis au récepteur
+    pub received: u64,
+    /// Blocs jetés (anciens ou entrants selon la politique)
+    pub dropped: u64,
+    /// Envois ayant trouvé le tampon plein
+    pub overruns: u64,
+    /// Remplissage maximal observé
+    pub max_depth: usize,
+}
+
+impl ChannelMetrics {
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            sent: self.sent.load(Ordering::Relaxed),
+            received: self.received.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+            overruns: self.overruns.load(Ordering::Relaxed),
+            max_depth: self.max_depth.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl fmt::Display for MetricsSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} envoyés, {} reçus, {} perdus, {} débordements, remplissage max {}",
+            self.sent, self.received, self.dropped, self.overruns, self.max_depth
+        )
+    }
+}
+
+struct State<T> {
+    items: VecDeque<T>,
+    senders: usize,
+    receiver_alive: bool,
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    not_empty: Condvar,
+    not_full: Condvar,
+    capacity: usize,
+    policy: OverflowPolicy,
+    /// Éléments jamais jetés par `DropOldest` ni `DropNewest`
+    keep: Option<fn(&T) -> bool>,
+    metrics: Arc<ChannelMetrics>,
+}
+
+/// Crée un canal borné à `capacity` blocs, remplaçant `mpsc::channel` entre la capture
+/// et le traitement pour que la mémoire et la latence ne croissent pas sans limite
+pub fn bounded<T>(capacity: usize, policy: OverflowPolicy) -> (RingSender<T>, RingReceiver<T>) {
+    channel(capacity, policy, None)
+}
+
+/// Comme `bounded`, mais les éléments pour lesquels `keep` est vrai ne sont jamais jetés :
+/// `DropOldest` jette le plus ancien des autres, `DropNewest` jette l'entrant s'il n'est pas
+/// à garder et le plus ancien des autres sinon. Si la file ne contient que des éléments
+/// à garder, elle dépasse sa capacité ; ils doivent donc être rares.
+pub fn bounded_keeping<T>(
+    capacity: usize,
+    policy: OverflowPolicy,
+    keep: fn(&T) -> bool,
+) -> (RingSender<T>, RingReceiver<T>) {
+    channel(capacity, policy, Some(keep))
+}
+
+fn channel<T>(
+    capacity: usize,
+    policy: OverflowPolicy,
+    keep: Option<fn(&T) -> bool>,
+) -> (RingSender<T>, RingReceiver<T>) {
+    assert!(capacity > 0, "la capacité du canal doit être non nulle");
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            items: VecDeque::with_capacity(capacity),
+            senders: 1,
+            receiver_alive: true,
+        }),
+        not_empty: Condvar::new(),
+        not_full: Condvar::new(),
+        capacity,
+        policy,
+        keep,
+        metrics: Arc::new(ChannelMetrics::default()),
+    });
+    (
+        RingSender {
+            shared: Arc::clone(&shared),
+        },
+        RingReceiver { shared },
+    )
+}
+
+pub struct RingSender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> RingSender<T> {
+    /// Envoie un bloc selon la politique de débordement ; échoue si le récepteur a disparu
+    pub fn send(&self, item: T) -> Result<(), SendError<T>> {
+        let shared = &*self.shared;
+        let metrics = &shared.metrics;
+        let mut state = shared.state.lock().unwrap();
+        if !state.receiver_alive {
+            return Err(SendError(item));
+        }
+
+        if state.items.len() >= shared.capacity {
+            metrics.overruns.fetch_add(1, Ordering::Relaxed);
+            let kept = |item: &T| shared.keep.is_some_and(|keep| keep(item));
+            match shared.policy {
+                OverflowPolicy::DropNewest if !kept(&item) => {
+                    metrics.dropped.fetch_add(1, Ordering::Relaxed);
+                    return Ok(());
+                }
+                OverflowPolicy::DropOldest | OverflowPolicy::DropNewest => {
+                    if let Some(index) = state.items.iter().position(|item| !kept(item)) {
+                        state.items.remove(index);
+                        metrics.dropped.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+                OverflowPolicy::Block => {
+                    while state.items.len() >= shared.capacity && state.receiver_alive {
+                        state = shared.not_full.wait(state).unwrap();
+                    }
+                    if !state.receiver_alive {
+                        return Err(SendError(item));
+                    }
+                }
+            }
+        }
+
+        state.items.push_back(item);
+        metrics.sent.fetch_add(1, Ordering::Relaxed);
+        metrics.max_depth.fetch_max(state.items.len(), Ordering::Relaxed);
+        drop(state);
+        shared.not_empty.notify_one();
+        Ok(())
+    }
+
+    pub fn metrics(&self) -> Arc<ChannelMetrics> {
+        Arc::clone(&self.shared.metrics)
+    }
+}
+
+impl<T> Clone for RingSender<T> {
+    fn clone(&self) -> Self {
+        self.shared.state.lock().unwrap().senders += 1;
+        RingSender {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T> Drop for RingSender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.senders -= 1;
+        if state.senders == 0 {
+            // Réveiller le récepteur pour qu'il constate la fermeture
+            self.shared.not_empty.notify_all();
+        }
+    }
+}
+
+pub struct RingReceiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> RingReceiver<T> {
+    /// Attend le prochain bloc ; échoue quand la file est vide et tous les émetteurs fermés
+    pub fn recv(&self) -> Result<T, RecvError> {
+        let shared = &*self.shared;
+        let mut state = shared.state.lock().unwrap();
+        loop {
+            if let Some(item) = state.items.pop_front() {
+                drop(state);
+                shared.metrics.received.fetch_add(1, Ordering::Relaxed);
+                shared.not_full.notify_one();
+                return Ok(item);
+            }
+            if state.senders == 0 {
+                return Err(RecvError);
+            }
+            state = shared.not_empty.wait(state).unwrap();
+        }
+    }
+
+    /// Nombre de blocs en attente
+    pub fn len(&self) -> usize {
+        self.shared.state.lock().unwrap().items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn metrics(&self) -> Arc<ChannelMetrics> {
+        Arc::clone(&self.shared.metrics)
+    }
+}
+
+impl<T> Drop for RingReceiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.receiver_alive = false;
+        state.items.clear();
+        // Débloquer un émetteur en attente (politique `Block`)
+        self.shared.not_full.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use std::time::Duration;
+
+    fn drain<T>(receiver: &RingReceiver<T>) -> Vec<T> {
+        let mut items = Vec::new();
+        while !receiver.is_empty() {
+            items.push(receiver.recv().unwrap());
+        }
+        items
+    }
+
+    #[test]
+    fn drop_oldest_keeps_the_latest_items() {
+        let (sender, receiver) = bounded(3, OverflowPolicy::DropOldest);
+        for i in 0..5 {
+            sender.send(i).unwrap();
+        }
+        assert_eq!(drain(&receiver), vec![2, 3, 4]);
+        let metrics = receiver.metrics().snapshot();
+        assert_eq!(
+            metrics,
+            MetricsSnapshot {
+                sent: 5,
+                received: 3,
+                dropped: 2,
+                overruns: 2,
+                max_depth: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn drop_newest_keeps_the_first_items() {
+        let (sender, receiver) = bounded(3, OverflowPolicy::DropNewest);
+        for i in 0..5 {
+            sender.send(i).unwrap();
+        }
+        assert_eq!(drain(&receiver), vec![0, 1, 2]);
+        let metrics = sender.metrics().snapshot();
+        assert_eq!((metrics.sent, metrics.dropped, metrics.overruns), (3, 2, 2));
+        assert_eq!(metrics.max_depth, 3);
+    }
+
+    #[test]
+    fn kept_items_are_never_dropped() {
+        let (sender, receiver) = bounded_keeping(2, OverflowPolicy::DropOldest, |i: &i32| *i < 0);
+        for i in [-1, 1, 2, -2, 3] {
+            sender.send(i).unwrap();
+        }
+        assert_eq!(drain(&receiver), vec![-1, -2, 3]);
+
+        // Plein d'éléments à garder : la file dépasse sa capacité plutôt que d'en jeter
+        let (sender, receiver) = bounded_keeping(2, OverflowPolicy::DropNewest, |i: &i32| *i < 0);
+        for i in [-1, -2, 1, -3] {
+            sender.send(i).unwrap();
+        }
+        assert_eq!(drain(&receiver), vec![-1, -2, -3]);
+        assert_eq!(receiver.metrics().snapshot().dropped, 1);
+    }
+
+    #[test]
+    fn block_waits_for_the_receiver() {
+        let (sender, receiver) = bounded(2, OverflowPolicy::Block);
+        let producer = thread::spawn(move || {
+            for i in 0..100 {
+                sender.send(i).unwrap();
+            }
+        });
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(
+            receiver.len(),
+            2,
+            "l'émetteur doit attendre une place libre"
+        );
+        let received: Vec<i32> = (0..100).map(|_| receiver.recv().unwrap()).collect();
+        producer.join().unwrap();
+        assert_eq!(received, (0..100).collect::<Vec<_>>());
+        let metrics = receiver.metrics().snapshot();
+        assert_eq!((metrics.dropped, metrics.max_depth), (0, 2));
+        assert!(metrics.overruns > 0);
+    }
+
+    #[test]
+    fn receiver_drains_then_sees_disconnection() {
+        let (sender, receiver) = bounded(4, OverflowPolicy::DropOldest);
+        let second = sender.clone();
+        sender.send(1).unwrap();
+        drop(sender);
+        second.send(2).unwrap();
+        drop(second);
+        assert_eq!(receiver.recv(), Ok(1));
+        assert_eq!(receiver.recv(), Ok(2));
+        assert_eq!(receiver.recv(), Err(RecvError));
+    }
+
+    #[test]
+    fn recv_wakes_up_when_the_last_sender_closes() {
+        let (sender, receiver) = bounded::<i32>(4, OverflowPolicy::DropOldest);
+        let closer = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            drop(sender);
+        });
+        assert_eq!(receiver.recv(), Err(RecvError));
+        closer.join().unwrap();
+    }
+
+    #[test]
+    fn send_fails_once_the_receiver_is_gone() {
+        let (sender, receiver) = bounded(1, OverflowPolicy::DropOldest);
+        drop(receiver);
+        assert_eq!(sender.send(7), Err(SendError(7)));
+    }
+
+    #[test]
+    fn blocked_sender_is_released_when_the_receiver_drops() {
+        let (sender, receiver) = bounded(1, OverflowPolicy::Block);
+        sender.send(0).unwrap();
+        let producer = thread::spawn(move || sender.send(1));
+        thread::sleep(Duration::from_millis(20));
+        drop(receiver);
+        assert_eq!(producer.join().unwrap(), Err(SendError(1)));
+    }
+}
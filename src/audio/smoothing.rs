@@ -0,0 +1,389 @@
+// src/audio/smoothing.rs
+
+use crate::audio::detector::{Pitch, MAX_CANDIDATES};
+use std::collections::VecDeque;
+
+/// Taille maximale de la fenêtre du filtre médian, en trames
+pub const MAX_MEDIAN: usize = 15;
+
+/// Écart maximal à un nombre entier d'octaves pour qu'un saut soit pris pour une erreur
+const OCTAVE_TOLERANCE_CENTS: f32 = 50.0;
+
+/// Écart en dessous duquel deux hauteurs sont le même état du lissage de Viterbi
+const SAME_STATE_CENTS: f32 = 50.0;
+
+/// Vraisemblance relative des hypothèses « octave au-dessus » et « octave en dessous »
+/// ajoutées autour de la détection
+const OCTAVE_PRIOR: f32 = 0.1;
+
+/// États par trame : détection, candidats du détecteur et ses deux octaves voisines
+const MAX_STATES: usize = MAX_CANDIDATES + 3;
+
+/// Réglages du lissage de la suite des hauteurs, appliqué entre la détection
+/// et la segmentation en notes ; tout est désactivé par défaut
+#[derive(Debug, Clone, PartialEq)]
+pub struct SmoothingConfig {
+    /// Fenêtre du filtre médian en trames (0 ou 1 sans filtre, au plus `MAX_MEDIAN`)
+    pub median: usize,
+    /// Ramène à l'octave de la trame précédente les sauts d'une ou deux octaves
+    pub octave_correction: bool,
+    /// Trames consécutives à la nouvelle octave au-delà desquelles le saut est accepté
+    pub octave_frames: usize,
+    /// Choisit parmi les candidats du détecteur le chemin le plus vraisemblable (Viterbi)
+    pub viterbi: bool,
+    /// Écart en cents qui coûte autant, entre deux trames, qu'un facteur e de vraisemblance :
+    /// plus il est grand, plus le chemin suit volontiers les changements de note
+    pub transition_cents: f32,
+}
+
+impl Default for SmoothingConfig {
+    fn default() -> Self {
+        SmoothingConfig {
+            median: 0,
+            octave_correction: false,
+            octave_frames: 4,
+            viterbi: false,
+            transition_cents: 200.0,
+        }
+    }
+}
+
+impl SmoothingConfig {
+    /// Liste en ligne de commande : `median:5`, `octave`, `octave:6` (trames avant
+    /// d'accepter un saut), `viterbi`, `viterbi:300` (cents par unité de coût), séparés
+    /// par des virgules ; `none` sans lissage
+    pub fn parse(spec: &str) -> Option<SmoothingConfig> {
+        let mut config = SmoothingConfig::default();
+        if spec == "none" {
+            return Some(config);
+        }
+        for part in spec.split(',').map(str::trim) {
+            let (stage, value) = match part.split_once(':') {
+                Some((stage, value)) => (stage, Some(value)),
+                None => (part, None),
+            };
+            match stage {
+                "median" => config.median = value.unwrap_or("5").parse().ok()?,
+                "octave" => {
+                    config.octave_correction = true;
+                    if let Some(frames) = value {
+                        config.octave_frames = frames.parse().ok()?;
+                    }
+                }
+                "viterbi" => {
+                    config.viterbi = true;
+                    if let Some(cents) = value {
+                        config.transition_cents = cents.parse().ok()?;
+                    }
+                }
+                _ => return None,
+            }
+        }
+        (config.median <= MAX_MEDIAN && config.transition_cents > 0.0).then_some(config)
+    }
+}
+
+/// Stabilise la suite des hauteurs détectées trame par trame : choix du chemin le plus
+/// vraisemblable parmi les candidats (Viterbi), correction des sauts d'octave isolés,
+/// puis filtre médian. Tous les tampons sont réservés à la construction : `update`
+/// n'alloue pas et peut tourner sur le thread de traitement.
+#[derive(Debug)]
+pub struct PitchSmoother {
+    config: SmoothingConfig,
+    /// Dernières fréquences, pour le filtre médian
+    history: VecDeque<f32>,
+    sorted: Vec<f32>,
+    /// Fréquence de référence de la correction d'octave et trames passées à une autre octave
+    reference: Option<f32>,
+    octave_count: usize,
+    /// États de la trame précédente (fréquence) et coût du meilleur chemin qui y mène
+    states: [f32; MAX_STATES],
+    costs: [f32; MAX_STATES],
+    state_count: usize,
+}
+
+impl PitchSmoother {
+    pub fn new(mut config: SmoothingConfig) -> Self {
+        config.median = config.median.min(MAX_MEDIAN);
+        PitchSmoother {
+            history: VecDeque::with_capacity(config.median),
+            sorted: Vec::with_capacity(config.median),
+            config,
+            reference: None,
+            octave_count: 0,
+            states: [0.0; MAX_STATES],
+            costs: [0.0; MAX_STATES],
+            state_count: 0,
+        }
+    }
+
+    pub fn config(&self) -> &SmoothingConfig {
+        &self.config
+    }
+
+    /// Hauteur lissée de la trame, à partir de la détection et des candidats du détecteur ;
+    /// une trame sans hauteur interrompt la continuité
+    pub fn update(&mut self, pitch: Option<Pitch>, candidates: &[Pitch]) -> Option<Pitch> {
+        let Some(mut pitch) = pitch.filter(|p| p.frequency > 0.0) else {
+            self.reset();
+            return None;
+        };
+        if self.config.viterbi {
+            pitch.frequency = self.viterbi(pitch, candidates);
+        }
+        if self.config.octave_correction {
+            pitch.frequency = self.correct_octave(pitch.frequency);
+        }
+        if self.config.median > 1 {
+            pitch.frequency = self.median(pitch.frequency);
+        }
+        Some(pitch)
+    }
+
+    /// Oublie les trames précédentes (silence, gate fermé)
+    pub fn reset(&mut self) {
+        self.history.clear();
+        self.reference = None;
+        self.octave_count = 0;
+        self.state_count = 0;
+    }
+
+    /// Étape de Viterbi en ligne : coût du meilleur chemin menant à chaque état de la trame,
+    /// et fréquence de l'état le moins coûteux. La décision porte sur la trame courante,
+    /// sans attendre les suivantes, pour ne pas retarder les notes.
+    fn viterbi(&mut self, pitch: Pitch, candidates: &[Pitch]) -> f32 {
+        let mut states = [(0.0, 0.0); MAX_STATES];
+        let mut count = 0;
+        push_state(&mut states, &mut count, pitch.frequency, pitch.confidence);
+        for candidate in candidates {
+            push_state(
+                &mut states,
+                &mut count,
+                candidate.frequency,
+                candidate.confidence,
+            );
+        }
+        let best_confidence = match states[..count].iter().map(|s| s.1).fold(0.0, f32::max) {
+            confidence if confidence > 0.0 => confidence,
+            _ => 1.0,
+        };
+        let prior = best_confidence * OCTAVE_PRIOR;
+        push_state(&mut states, &mut count, pitch.frequency / 2.0, prior);
+        push_state(&mut states, &mut count, pitch.frequency * 2.0, prior);
+
+        // Coût d'émission : -ln de la confiance relative ; coût de transition : écart en cents
+        // depuis l'état précédent le plus favorable (aucun à la première trame)
+        let previous = &self.states[..self.state_count];
+        let mut costs = [0.0; MAX_STATES];
+        for (cost, &(frequency, confidence)) in costs.iter_mut().zip(&states[..count]) {
+            let emission = -(confidence / best_confidence).max(1e-3).ln();
+            let transition = previous
+                .iter()
+                .zip(&self.costs)
+                .map(|(&from, &cost)| {
+                    cost + cents(frequency, from).abs() / self.config.transition_cents
+                })
+                .reduce(f32::min)
+                .unwrap_or(0.0);
+            *cost = emission + transition;
+        }
+
+        // Coûts ramenés au meilleur, pour qu'ils ne croissent pas sans fin
+        let (best, &lowest) = costs[..count]
+            .iter()
+            .enumerate()
+            .min_by(|a, b| a.1.total_cmp(b.1))
+            .expect("au moins l'état de la détection");
+        for (i, &(frequency, _)) in states[..count].iter().enumerate() {
+            self.states[i] = frequency;
+            self.costs[i] = costs[i] - lowest;
+        }
+        self.state_count = count;
+        states[best].0
+    }
+
+    /// Ramène un saut d'une ou deux octaves à l'octave de référence, tant qu'il n'a pas
+    /// duré `octave_frames` trames
+    fn correct_octave(&mut self, frequency: f32) -> f32 {
+        let Some(reference) = self.reference else {
+            self.reference = Some(frequency);
+            return frequency;
+        };
+        let octaves = (frequency / reference).log2();
+        let jump = octaves.round();
+        let off_octave = 1200.0 * (octaves - jump);
+        if jump != 0.0 && jump.abs() <= 2.0 && off_octave.abs() <= OCTAVE_TOLERANCE_CENTS {
+            self.octave_count += 1;
+            if self.octave_count < self.config.octave_frames {
+                return frequency / jump.exp2();
+            }
+        }
+        self.octave_count = 0;
+        self.reference = Some(frequency);
+        frequency
+    }
+
+    /// Médiane des `median` dernières fréquences
+    fn median(&mut self, frequency: f32) -> f32 {
+        if self.history.len() == self.config.median {
+            self.history.pop_front();
+        }
+        self.history.push_back(frequency);
+        self.sorted.clear();
+        self.sorted.extend(&self.history);
+        self.sorted.sort_unstable_by(f32::total_cmp);
+        self.sorted[self.sorted.len() / 2]
+    }
+}
+
+/// Ajoute un état s'il n'est pas déjà présent (à moins de `SAME_STATE_CENTS`)
+fn push_state(
+    states: &mut [(f32, f32); MAX_STATES],
+    count: &mut usize,
+    frequency: f32,
+    confidence: f32,
+) {
+    let known = states[..*count]
+        .iter()
+        .any(|&(f, _)| cents(frequency, f).abs() < SAME_STATE_CENTS);
+    if frequency > 0.0 && *count < MAX_STATES && !known {
+        states[*count] = (frequency, confidence);
+        *count += 1;
+    }
+}
+
+/// Écart en cents de `frequency` au-dessus de `reference`
+fn cents(frequency: f32, reference: f32) -> f32 {
+    1200.0 * (frequency / reference).log2()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pitch(frequency: f32, confidence: f32) -> Pitch {
+        Pitch {
+            frequency,
+            confidence,
+        }
+    }
+
+    /// Fréquences lissées d'une suite de détections sans candidats
+    fn smooth(config: SmoothingConfig, track: &[f32]) -> Vec<f32> {
+        let mut smoother = PitchSmoother::new(config);
+        track
+            .iter()
+            .map(|&f| smoother.update(Some(pitch(f, 0.9)), &[]).unwrap().frequency)
+            .collect()
+    }
+
+    #[test]
+    fn parse_stages() {
+        let config = SmoothingConfig::parse("median:7,octave:3,viterbi:300").unwrap();
+        assert_eq!(config.median, 7);
+        assert!(config.octave_correction && config.viterbi);
+        assert_eq!(config.octave_frames, 3);
+        assert_eq!(config.transition_cents, 300.0);
+        assert_eq!(
+            SmoothingConfig::parse("none"),
+            Some(SmoothingConfig::default())
+        );
+        assert_eq!(SmoothingConfig::parse("median:16"), None);
+        assert_eq!(SmoothingConfig::parse("viterbi:0"), None);
+        assert_eq!(SmoothingConfig::parse("kalman"), None);
+    }
+
+    #[test]
+    fn disabled_smoothing_passes_pitches_through() {
+        let track = [220.0, 440.0, 220.0, 330.0];
+        assert_eq!(smooth(SmoothingConfig::default(), &track), track);
+    }
+
+    #[test]
+    fn median_removes_outliers_and_delays_steps() {
+        let config = SmoothingConfig {
+            median: 3,
+            ..SmoothingConfig::default()
+        };
+        let track = [100.0, 100.0, 400.0, 100.0, 100.0, 200.0, 200.0, 200.0];
+        assert_eq!(
+            smooth(config, &track),
+            [100.0, 100.0, 100.0, 100.0, 100.0, 100.0, 200.0, 200.0]
+        );
+    }
+
+    #[test]
+    fn missing_pitch_resets_the_history() {
+        let mut smoother = PitchSmoother::new(SmoothingConfig {
+            median: 5,
+            ..SmoothingConfig::default()
+        });
+        for _ in 0..4 {
+            smoother.update(Some(pitch(100.0, 0.9)), &[]);
+        }
+        assert_eq!(smoother.update(None, &[]), None);
+        let after = smoother.update(Some(pitch(300.0, 0.9)), &[]).unwrap();
+        assert_eq!(after.frequency, 300.0);
+    }
+
+    #[test]
+    fn octave_jump_is_accepted_after_octave_frames() {
+        let config = SmoothingConfig {
+            octave_correction: true,
+            octave_frames: 3,
+            ..SmoothingConfig::default()
+        };
+        // Saut isolé corrigé, saut tenu accepté à sa troisième trame
+        let track = [220.0, 440.0, 220.0, 440.0, 440.0, 440.0, 440.0];
+        assert_eq!(
+            smooth(config.clone(), &track),
+            [220.0, 220.0, 220.0, 220.0, 220.0, 440.0, 440.0]
+        );
+        // Deux octaves en dessous, puis un changement de note qui n'est pas une octave
+        let track = [440.0, 110.0, 440.0, 660.0];
+        assert_eq!(smooth(config, &track), [440.0, 440.0, 440.0, 660.0]);
+    }
+
+    #[test]
+    fn viterbi_prefers_the_continuous_candidate() {
+        let mut smoother = PitchSmoother::new(SmoothingConfig {
+            viterbi: true,
+            ..SmoothingConfig::default()
+        });
+        for _ in 0..5 {
+            smoother.update(Some(pitch(220.0, 0.9)), &[pitch(220.0, 0.9)]);
+        }
+        // Le détecteur donne l'octave, la fondamentale n'est que deuxième candidate
+        let frame = smoother.update(
+            Some(pitch(440.0, 0.9)),
+            &[pitch(440.0, 0.9), pitch(220.0, 0.8)],
+        );
+        assert_eq!(frame.unwrap().frequency, 220.0);
+    }
+
+    #[test]
+    fn viterbi_smooths_isolated_octave_jumps_and_follows_notes() {
+        let config = SmoothingConfig {
+            viterbi: true,
+            ..SmoothingConfig::default()
+        };
+        let track = [
+            220.0, 220.0, 440.0, 220.0, 110.0, 220.0, 220.0, 330.0, 330.0, 660.0, 330.0,
+        ];
+        assert_eq!(
+            smooth(config, &track),
+            [220.0, 220.0, 220.0, 220.0, 220.0, 220.0, 220.0, 330.0, 330.0, 330.0, 330.0]
+        );
+    }
+
+    #[test]
+    fn all_stages_together() {
+        let config = SmoothingConfig::parse("median:3,octave:4,viterbi").unwrap();
+        let track = [
+            196.0, 196.0, 392.0, 196.0, 196.0, 98.0, 196.0, 196.0, 294.0, 294.0, 294.0, 294.0,
+        ];
+        let smoothed = smooth(config, &track);
+        assert!(smoothed[..8].iter().all(|&f| f == 196.0), "{:?}", smoothed);
+        assert_eq!(smoothed[track.len() - 1], 294.0);
+    }
+}
@@ -0,0 +1,340 @@
+// src/audio/tee.rs
+
+use crate::audio::flac::FlacWriter;
+use chrono::Local;
+use hound::{SampleFormat, WavSpec, WavWriter};
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Format du fichier de copie brute de la capture
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TeeFormat {
+    /// WAV PCM 16 bits (format natif de la capture)
+    Wav16,
+    /// WAV PCM 24 bits
+    Wav24,
+    /// WAV flottant 32 bits, normalisé dans [-1, 1]
+    WavFloat,
+    /// FLAC 16 bits sans perte
+    Flac,
+}
+
+impl TeeFormat {
+    /// Nom utilisé dans la configuration : `wav16`, `wav24`, `wav-float` ou `flac`
+    pub fn parse(name: &str) -> Option<TeeFormat> {
+        match name {
+            "wav16" => Some(TeeFormat::Wav16),
+            "wav24" => Some(TeeFormat::Wav24),
+            "wav-float" => Some(TeeFormat::WavFloat),
+            "flac" => Some(TeeFormat::Flac),
+            _ => None,
+        }
+    }
+
+    fn bytes_per_sample(self) -> u64 {
+        match self {
+            TeeFormat::Wav16 | TeeFormat::Flac => 2,
+            TeeFormat::Wav24 => 3,
+            TeeFormat::WavFloat => 4,
+        }
+    }
+}
+
+/// Configuration de la copie brute (« tee ») des échantillons capturés
+#[derive(Clone, Debug)]
+pub struct TeeConfig {
+    /// Modèle de chemin : les motifs `strftime` de chrono (`%Y`, `%H`…) sont
+    /// remplacés à l'ouverture de chaque fichier, et `{n}` par le numéro de segment.
+    /// Avec une rotation, `-{n}` est ajouté avant l'extension si le modèle n'en a pas,
+    /// pour qu'un segment n'écrase jamais le précédent.
+    pub path_template: String,
+    pub format: TeeFormat,
+    /// Taille maximale (en octets de données audio) avant rotation
+    pub max_bytes: Option<u64>,
+    /// Durée maximale d'un fichier avant rotation
+    pub max_duration: Option<Duration>,
+}
+
+impl TeeConfig {
+    pub fn new(path_template: &str, format: TeeFormat) -> Self {
+        TeeConfig {
+            path_template: path_template.to_string(),
+            format,
+            max_bytes: None,
+            max_duration: None,
+        }
+    }
+
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    pub fn with_max_duration(mut self, max_duration: Duration) -> Self {
+        self.max_duration = Some(max_duration);
+        self
+    }
+}
+
+enum TeeWriter {
+    Wav(WavWriter<BufWriter<File>>),
+    Flac(FlacWriter),
+}
+
+/// Écrit les échantillons capturés dans une suite de fichiers, avec rotation
+pub struct CaptureTee {
+    config: TeeConfig,
+    /// Modèle de chemin effectif, avec le numéro de segment si la rotation est active
+    template: String,
+    channels: u16,
+    sample_rate: u32,
+    writer: Option<TeeWriter>,
+    segment: u32,
+    /// Échantillons (tous canaux confondus) écrits dans le segment courant
+    segment_samples: u64,
+}
+
+impl CaptureTee {
+    pub fn new(config: TeeConfig, channels: u16, sample_rate: u32) -> Self {
+        CaptureTee {
+            template: segment_template(&config),
+            config,
+            channels,
+            sample_rate,
+            writer: None,
+            segment: 0,
+            segment_samples: 0,
+        }
+    }
+
+    /// Écrit un bloc d'échantillons entrelacés, en ouvrant un nouveau segment si nécessaire
+    pub fn write(&mut self, samples: &[i16]) -> io::Result<()> {
+        let mut remaining = samples;
+        while !remaining.is_empty() {
+            if self.writer.is_none() {
+                self.open_segment()?;
+            }
+
+            // Couper le bloc à la limite de rotation, sur une frontière de trame
+            let room = self.samples_until_rotation();
+            let count = room.min(remaining.len() as u64) as usize;
+            let (chunk, rest) = remaining.split_at(count);
+
+            self.write_chunk(chunk)?;
+            self.segment_samples += chunk.len() as u64;
+            remaining = rest;
+
+            if self.samples_until_rotation() == 0 {
+                self.close_segment()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Finalise le segment en cours (en-têtes WAV/FLAC)
+    pub fn finalize(&mut self) -> io::Result<()> {
+        self.close_segment()
+    }
+
+    fn samples_until_rotation(&self) -> u64 {
+        let bytes_per_sample = self.config.format.bytes_per_sample();
+        let by_size = self.config.max_bytes.map(|max| max / bytes_per_sample);
+        let by_duration = self.config.max_duration.map(|max| {
+            (max.as_secs_f64() * self.sample_rate as f64) as u64 * self.channels as u64
+        });
+
+        let limit = match (by_size, by_duration) {
+            (Some(a), Some(b)) => a.min(b),
+            (Some(a), None) | (None, Some(a)) => a,
+            (None, None) => return u64::MAX,
+        };
+        // Toujours arrondir à une trame complète pour ne pas désentrelacer les canaux
+        let limit = limit - limit % self.channels as u64;
+        limit
+            .max(self.channels as u64)
+            .saturating_sub(self.segment_samples)
+    }
+
+    fn write_chunk(&mut self, chunk: &[i16]) -> io::Result<()> {
+        let format = self.config.format;
+        match self.writer.as_mut() {
+            Some(TeeWriter::Wav(writer)) => {
+                for &sample in chunk {
+                    match format {
+                        TeeFormat::Wav24 => writer.write_sample((sample as i32) << 8),
+                        TeeFormat::WavFloat => writer.write_sample(sample as f32 / 32768.0),
+                        _ => writer.write_sample(sample),
+                    }
+                    .map_err(hound_to_io)?;
+                }
+                Ok(())
+            }
+            Some(TeeWriter::Flac(writer)) => {
+                writer.write_converted(chunk.iter().map(|&s| s as i32))
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn open_segment(&mut self) -> io::Result<()> {
+        let path = self.render_path()?;
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+
+        let writer = match self.config.format {
+            TeeFormat::Flac => {
+                TeeWriter::Flac(FlacWriter::create(&path, self.channels, self.sample_rate, 16)?)
+            }
+            format => {
+                let (bits_per_sample, sample_format) = match format {
+                    TeeFormat::Wav24 => (24, SampleFormat::Int),
+                    TeeFormat::WavFloat => (32, SampleFormat::Float),
+                    _ => (16, SampleFormat::Int),
+                };
+                let spec = WavSpec {
+                    channels: self.channels,
+                    sample_rate: self.sample_rate,
+                    bits_per_sample,
+                    sample_format,
+                };
+                TeeWriter::Wav(WavWriter::create(&path, spec).map_err(hound_to_io)?)
+            }
+        };
+
+        log::info!("Copie de la capture vers {}", path.display());
+        self.writer = Some(writer);
+        self.segment_samples = 0;
+        self.segment += 1;
+        Ok(())
+    }
+
+    fn close_segment(&mut self) -> io::Result<()> {
+        match self.writer.take() {
+            Some(TeeWriter::Wav(writer)) => writer.finalize().map_err(hound_to_io),
+            Some(TeeWriter::Flac(writer)) => writer.finalize(),
+            None => Ok(()),
+        }
+    }
+
+    fn render_path(&self) -> io::Result<PathBuf> {
+        let template = self.template.replace("{n}", &self.segment.to_string());
+        let mut path = String::new();
+        write!(path, "{}", Local::now().format(&template)).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Modèle de chemin invalide : {}", self.config.path_template),
+            )
+        })?;
+        Ok(PathBuf::from(path))
+    }
+}
+
+/// Modèle de chemin des segments : avec une rotation, chaque segment doit avoir son nom,
+/// même si les motifs `strftime` donnent le même à deux segments consécutifs
+fn segment_template(config: &TeeConfig) -> String {
+    let template = &config.path_template;
+    let rotates = config.max_bytes.is_some() || config.max_duration.is_some();
+    if !rotates || template.contains("{n}") {
+        return template.clone();
+    }
+    match Path::new(template).extension().and_then(|ext| ext.to_str()) {
+        Some(extension) => {
+            let stem = &template[..template.len() - extension.len() - 1];
+            format!("{}-{{n}}.{}", stem, extension)
+        }
+        None => format!("{}-{{n}}", template),
+    }
+}
+
+fn hound_to_io(err: hound::Error) -> io::Error {
+    match err {
+        hound::Error::IoError(err) => err,
+        other => io::Error::other(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("tee-{}-{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn wav_samples(path: &Path) -> Vec<i16> {
+        hound::WavReader::open(path)
+            .unwrap()
+            .into_samples::<i16>()
+            .map(Result::unwrap)
+            .collect()
+    }
+
+    #[test]
+    fn segment_number_is_added_only_when_rotating() {
+        let template = |path: &str, rotate: bool| {
+            let config = TeeConfig::new(path, TeeFormat::Wav16);
+            let config = if rotate {
+                config.with_max_bytes(1024)
+            } else {
+                config
+            };
+            segment_template(&config)
+        };
+        assert_eq!(template("capture.wav", false), "capture.wav");
+        assert_eq!(template("capture.wav", true), "capture-{n}.wav");
+        assert_eq!(
+            template("%Y-%m-%d/capture.flac", true),
+            "%Y-%m-%d/capture-{n}.flac"
+        );
+        assert_eq!(
+            template("archives.d/capture", true),
+            "archives.d/capture-{n}"
+        );
+        assert_eq!(template("capture_{n}.wav", true), "capture_{n}.wav");
+    }
+
+    #[test]
+    fn rotation_without_segment_number_keeps_every_segment() {
+        let dir = temp_dir("rotation");
+        let template = dir.join("capture.wav");
+        // 8 octets : deux trames stéréo 16 bits par segment
+        let config = TeeConfig::new(template.to_str().unwrap(), TeeFormat::Wav16).with_max_bytes(8);
+        let mut tee = CaptureTee::new(config, 2, 44100);
+        let samples: Vec<i16> = (0..10).collect();
+        tee.write(&samples).unwrap();
+        tee.finalize().unwrap();
+
+        assert!(!template.exists());
+        assert_eq!(wav_samples(&dir.join("capture-0.wav")), [0, 1, 2, 3]);
+        assert_eq!(wav_samples(&dir.join("capture-1.wav")), [4, 5, 6, 7]);
+        assert_eq!(wav_samples(&dir.join("capture-2.wav")), [8, 9]);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn constant_strftime_pattern_does_not_overwrite_segments() {
+        let dir = temp_dir("strftime");
+        // Même nom toute l'année : sans numéro, chaque segment écraserait le précédent
+        let template = dir.join("capture-%Y.wav");
+        let config = TeeConfig::new(template.to_str().unwrap(), TeeFormat::Wav16)
+            .with_max_duration(Duration::from_millis(1));
+        let mut tee = CaptureTee::new(config, 1, 8000);
+        tee.write(&[1; 20]).unwrap();
+        tee.finalize().unwrap();
+
+        let year = Local::now().format("%Y").to_string();
+        let segments = std::fs::read_dir(&dir).unwrap().count();
+        assert_eq!(segments, 3, "8 échantillons par segment");
+        let last = dir.join(format!("capture-{}-2.wav", year));
+        assert_eq!(wav_samples(&last).len(), 4);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,306 @@
+// src/audio/tempo.rs
+
+use crate::audio::onset::OnsetDetector;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Trames entre deux estimations du tempo
+const ESTIMATE_INTERVAL: u64 = 8;
+
+/// Nombre de multiples de la période sommés par le filtre en peigne
+const COMB_HARMONICS: usize = 4;
+
+/// Pas (en trames) entre deux périodes candidates : le tempo tombe rarement sur un nombre
+/// entier de trames, et ses multiples s'écarteraient des décalages entiers
+const LAG_STEP: f32 = 0.25;
+
+/// Réglages de l'estimation du tempo
+#[derive(Debug, Clone)]
+pub struct TempoConfig {
+    pub min_bpm: f32,
+    pub max_bpm: f32,
+    /// Tempo privilégié en cas d'ambiguïté (double ou moitié)
+    pub prior_bpm: f32,
+    /// Durée de l'enveloppe d'attaques analysée
+    pub window: Duration,
+}
+
+impl Default for TempoConfig {
+    fn default() -> Self {
+        TempoConfig {
+            min_bpm: 60.0,
+            max_bpm: 200.0,
+            prior_bpm: 120.0,
+            window: Duration::from_secs(6),
+        }
+    }
+}
+
+/// Estimation du tempo par autocorrélation de l'enveloppe d'attaques, renforcée par un
+/// filtre en peigne (multiples de la période) et pondérée autour du tempo privilégié
+#[derive(Debug, Clone)]
+pub struct TempoEstimator {
+    config: TempoConfig,
+    /// Enveloppe lissée et centrée, réutilisée d'une estimation à l'autre
+    centered: Vec<f32>,
+    /// Autocorrélation de l'enveloppe pour chaque décalage entier
+    correlation: Vec<f32>,
+    /// Score de chaque période candidate
+    scores: Vec<f32>,
+}
+
+impl TempoEstimator {
+    pub fn new(config: TempoConfig) -> Self {
+        TempoEstimator {
+            config,
+            centered: Vec::new(),
+            correlation: Vec::new(),
+            scores: Vec::new(),
+        }
+    }
+
+    pub fn config(&self) -> &TempoConfig {
+        &self.config
+    }
+
+    /// Tempo (BPM) de l'enveloppe échantillonnée à `frame_rate` trames par seconde
+    pub fn estimate(&mut self, envelope: &[f32], frame_rate: f32) -> Option<f32> {
+        let min_lag = (60.0 * frame_rate / self.config.max_bpm).floor().max(1.0) as usize;
+        let max_lag = (60.0 * frame_rate / self.config.min_bpm).ceil() as usize;
+        if envelope.len() <= max_lag * 2 {
+            return None;
+        }
+
+        // Lissage de l'enveloppe : une attaque ne dure qu'une trame, et une période
+        // fractionnaire la ferait tomber entre deux décalages entiers
+        self.centered.clear();
+        self.centered.extend((0..envelope.len()).map(|i| {
+            let previous = envelope[i.saturating_sub(1)];
+            let next = envelope[(i + 1).min(envelope.len() - 1)];
+            0.25 * previous + 0.5 * envelope[i] + 0.25 * next
+        }));
+        let mean = self.centered.iter().sum::<f32>() / self.centered.len() as f32;
+        for value in &mut self.centered {
+            *value -= mean;
+        }
+        let centered = &self.centered;
+        let autocorrelation = |lag: usize| -> f32 {
+            if lag >= centered.len() {
+                return 0.0;
+            }
+            let sum: f32 = centered[lag..]
+                .iter()
+                .zip(centered)
+                .map(|(a, b)| a * b)
+                .sum();
+            sum / (centered.len() - lag) as f32
+        };
+        self.correlation.clear();
+        self.correlation
+            .extend((0..=max_lag * COMB_HARMONICS + 1).map(autocorrelation));
+
+        // Autocorrélation à un décalage fractionnaire, interpolée entre les décalages entiers
+        let correlation = &self.correlation;
+        let interpolated = |lag: f32| -> f32 {
+            let index = lag.floor() as usize;
+            let fraction = lag - index as f32;
+            match (correlation.get(index), correlation.get(index + 1)) {
+                (Some(a), Some(b)) => a + fraction * (b - a),
+                (Some(a), None) => *a,
+                _ => 0.0,
+            }
+        };
+
+        let prior_bpm = self.config.prior_bpm;
+        let steps = ((max_lag - min_lag) as f32 / LAG_STEP) as usize;
+        self.scores.clear();
+        self.scores.extend((0..=steps).map(|step| {
+            let lag = min_lag as f32 + step as f32 * LAG_STEP;
+            let comb: f32 = (1..=COMB_HARMONICS)
+                .map(|m| interpolated(lag * m as f32) / m as f32)
+                .sum();
+            let bpm = 60.0 * frame_rate / lag;
+            let octaves = (bpm / prior_bpm).log2();
+            comb * (-0.5 * octaves * octaves).exp()
+        }));
+        let scores = &self.scores;
+
+        let (best, &score) = scores
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))?;
+        if score <= 0.0 {
+            return None;
+        }
+
+        // Interpolation parabolique autour du pic, entre deux périodes candidates
+        let mut lag = min_lag as f32 + best as f32 * LAG_STEP;
+        if best > 0 && best + 1 < scores.len() {
+            let (left, right) = (scores[best - 1], scores[best + 1]);
+            let curvature = left - 2.0 * score + right;
+            if curvature < 0.0 {
+                lag += LAG_STEP * 0.5 * (left - right) / curvature;
+            }
+        }
+        Some(60.0 * frame_rate / lag)
+    }
+}
+
+/// Battement détecté par le `BeatTracker`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Beat {
+    pub bpm: f32,
+    /// Numéro du battement depuis le début du flux
+    pub number: u64,
+}
+
+/// Suit les battements : enveloppe d'attaques par flux spectral, tempo estimé périodiquement
+/// et phase recalée à chaque battement sur les attaques passées
+pub struct BeatTracker {
+    onsets: OnsetDetector,
+    estimator: TempoEstimator,
+    envelope: VecDeque<f32>,
+    frame: u64,
+    /// Période en trames du tempo actuel
+    period: Option<f32>,
+    next_beat: Option<f32>,
+    beats: u64,
+}
+
+impl BeatTracker {
+    pub fn new(config: TempoConfig) -> Self {
+        BeatTracker {
+            onsets: OnsetDetector::new(),
+            estimator: TempoEstimator::new(config),
+            envelope: VecDeque::new(),
+            frame: 0,
+            period: None,
+            next_beat: None,
+            beats: 0,
+        }
+    }
+
+    /// Tempo actuel en BPM, pour `frame_rate` trames par seconde
+    pub fn bpm(&self, frame_rate: f32) -> Option<f32> {
+        self.period.map(|period| 60.0 * frame_rate / period)
+    }
+
+    /// Met à jour le suivi avec le spectre d'une trame ; `frame_rate` est le nombre de
+    /// trames d'analyse par seconde (fréquence d'échantillonnage / pas du framer)
+    pub fn update(&mut self, magnitudes: &[f32], frame_rate: f32) -> Option<Beat> {
+        let capacity = (self.estimator.config().window.as_secs_f32() * frame_rate) as usize;
+        self.envelope.push_back(self.onsets.flux(magnitudes));
+        while self.envelope.len() > capacity.max(1) {
+            self.envelope.pop_front();
+        }
+        let current = self.frame as f32;
+        self.frame += 1;
+
+        if self.frame.is_multiple_of(ESTIMATE_INTERVAL) {
+            let envelope = self.envelope.make_contiguous();
+            if let Some(bpm) = self.estimator.estimate(envelope, frame_rate) {
+                self.period = Some(60.0 * frame_rate / bpm);
+                if self.next_beat.is_none() {
+                    self.next_beat = self.predict(current);
+                }
+            }
+        }
+
+        let (period, next) = (self.period?, self.next_beat?);
+        if current < next {
+            return None;
+        }
+        let beat = Beat {
+            bpm: 60.0 * frame_rate / period,
+            number: self.beats,
+        };
+        self.beats += 1;
+        // Pas de nouveau battement avant une demi-période : si la phase a glissé et que la
+        // prédiction retombe sur le battement qui vient d'être annoncé, viser le suivant
+        let mut predicted = self.predict(current).unwrap_or(current + period);
+        if predicted < current + period / 2.0 {
+            predicted += period;
+        }
+        self.next_beat = Some(predicted);
+        Some(beat)
+    }
+
+    /// Prochain battement après `current` : la phase est celle qui maximise l'enveloppe
+    /// cumulée sur les multiples de la période dans l'historique
+    fn predict(&self, current: f32) -> Option<f32> {
+        let period = self.period?;
+        let len = self.envelope.len();
+        let score = |phase: usize| -> f32 {
+            (0..)
+                .map(|k| (phase as f32 + k as f32 * period).round() as usize)
+                .take_while(|&offset| offset < len)
+                .map(|offset| self.envelope[len - 1 - offset])
+                .sum()
+        };
+        let best_phase =
+            (0..period.round().max(1.0) as usize).max_by(|&a, &b| score(a).total_cmp(&score(b)))?;
+        let mut next = current - best_phase as f32;
+        while next <= current {
+            next += period;
+        }
+        Some(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Trames par seconde avec le pas de 1024 échantillons à 44,1 kHz
+    const FRAME_RATE: f32 = 44100.0 / 1024.0;
+
+    /// Piste de clics : un spectre plein sur la trame de chaque battement, le silence ailleurs
+    fn click_track(bpm: f32, seconds: f32) -> Vec<Vec<f32>> {
+        let period = 60.0 * FRAME_RATE / bpm;
+        let frames = (seconds * FRAME_RATE) as usize;
+        let mut next_click = 0.0;
+        (0..frames)
+            .map(|frame| {
+                if frame as f32 >= next_click {
+                    next_click += period;
+                    vec![1.0; 64]
+                } else {
+                    vec![0.0; 64]
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn click_track_at_120_bpm() {
+        let mut tracker = BeatTracker::new(TempoConfig::default());
+        let mut beats = Vec::new();
+        for (frame, spectrum) in click_track(120.0, 20.0).iter().enumerate() {
+            if let Some(beat) = tracker.update(spectrum, FRAME_RATE) {
+                beats.push((frame, beat));
+            }
+        }
+
+        let bpm = tracker.bpm(FRAME_RATE).unwrap();
+        assert!((bpm - 120.0).abs() <= 2.0, "tempo {}", bpm);
+        assert!(beats.len() > 10, "{} battements", beats.len());
+        for (number, (_, beat)) in beats.iter().enumerate() {
+            assert_eq!(beat.number, number as u64);
+        }
+        // Une fois le tempo établi, les battements sont espacés d'une période (~21,5 trames)
+        let period = 60.0 * FRAME_RATE / 120.0;
+        for pair in beats[beats.len() / 2..].windows(2) {
+            let spacing = (pair[1].0 - pair[0].0) as f32;
+            assert!((spacing - period).abs() <= 2.0, "espacement {}", spacing);
+            assert!((pair[1].1.bpm - 120.0).abs() <= 2.0, "tempo {}", pair[1].1.bpm);
+        }
+    }
+
+    #[test]
+    fn estimator_needs_two_of_the_longest_periods() {
+        let mut estimator = TempoEstimator::new(TempoConfig::default());
+        // 60 BPM au plus lent : 43 trames par période, 86 au moins
+        assert_eq!(estimator.estimate(&[0.0; 80], FRAME_RATE), None);
+        assert_eq!(estimator.estimate(&[0.0; 200], FRAME_RATE), None);
+    }
+}
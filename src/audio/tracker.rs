@@ -0,0 +1,147 @@
+// src/audio/tracker.rs
+
+use crate::audio::detector::Pitch;
+use crate::tuning::Tuning;
+use std::ops::RangeInclusive;
+
+/// Changement d'état d'une note, produit par le `NoteTracker`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NoteChange {
+    On { note: u8, frequency: f32, velocity: u8, confidence: f32 },
+    Off { note: u8 },
+}
+
+/// Réglages de la segmentation en notes
+#[derive(Debug, Clone)]
+pub struct TrackerConfig {
+    /// Niveau (dBFS RMS) au-dessus duquel le gate s'ouvre
+    pub gate_open_db: f32,
+    /// Niveau en dessous duquel le gate se referme (hystérésis)
+    pub gate_close_db: f32,
+    /// Nombre de trames consécutives sur la même note avant le Note On
+    pub min_frames: usize,
+    /// Confiance minimale de la détection
+    pub min_confidence: f32,
+    /// Plage de notes MIDI acceptées (piano par défaut)
+    pub note_range: RangeInclusive<u8>,
+    /// Accord utilisé pour associer une fréquence à une note
+    pub tuning: Tuning,
+}
+
+impl Default for TrackerConfig {
+    fn default() -> Self {
+        TrackerConfig {
+            gate_open_db: -45.0,
+            gate_close_db: -50.0,
+            min_frames: 2,
+            min_confidence: 0.0,
+            note_range: 21..=108,
+            tuning: Tuning::default(),
+        }
+    }
+}
+
+/// Transforme la suite des hauteurs détectées trame par trame en événements Note On / Note Off
+pub struct NoteTracker {
+    config: TrackerConfig,
+    gate_open: bool,
+    /// Note candidate et nombre de trames consécutives où elle a été vue
+    candidate: Option<(u8, usize)>,
+    active: Option<u8>,
+}
+
+impl NoteTracker {
+    pub fn new(config: TrackerConfig) -> Self {
+        NoteTracker {
+            config,
+            gate_open: false,
+            candidate: None,
+            active: None,
+        }
+    }
+
+    pub fn config(&self) -> &TrackerConfig {
+        &self.config
+    }
+
+    /// Réglages modifiables en cours de suivi (gate, confiance, nombre de trames)
+    pub fn config_mut(&mut self) -> &mut TrackerConfig {
+        &mut self.config
+    }
+
+    /// Le gate était ouvert à la dernière trame
+    pub fn gate_open(&self) -> bool {
+        self.gate_open
+    }
+
+    /// Note actuellement tenue
+    pub fn active_note(&self) -> Option<u8> {
+        self.active
+    }
+
+    /// Met à jour le suivi avec la hauteur et le niveau d'une nouvelle trame ; retourne au
+    /// plus un Note Off puis un Note On, sans allouer
+    pub fn update(
+        &mut self,
+        pitch: Option<Pitch>,
+        level_db: f32,
+    ) -> impl Iterator<Item = NoteChange> {
+        let threshold = if self.gate_open {
+            self.config.gate_close_db
+        } else {
+            self.config.gate_open_db
+        };
+        self.gate_open = level_db >= threshold;
+
+        let note = pitch
+            .filter(|_| self.gate_open)
+            .filter(|p| p.confidence >= self.config.min_confidence)
+            .and_then(|p| {
+                self.config
+                    .tuning
+                    .note(p.frequency)
+                    .filter(|n| self.config.note_range.contains(n))
+                    .map(|n| (n, p))
+            });
+
+        let mut off = None;
+        let mut on = None;
+        match note {
+            Some((number, pitch)) => {
+                let count = match self.candidate {
+                    Some((candidate, count)) if candidate == number => count + 1,
+                    _ => 1,
+                };
+                self.candidate = Some((number, count));
+
+                if count >= self.config.min_frames && self.active != Some(number) {
+                    off = self.active.take().map(|previous| NoteChange::Off { note: previous });
+                    self.active = Some(number);
+                    on = Some(NoteChange::On {
+                        note: number,
+                        frequency: pitch.frequency,
+                        velocity: velocity_from_level(level_db),
+                        confidence: pitch.confidence,
+                    });
+                }
+            }
+            None => {
+                self.candidate = None;
+                off = self.active.take().map(|previous| NoteChange::Off { note: previous });
+            }
+        }
+        [off, on].into_iter().flatten()
+    }
+
+    /// Termine la note en cours (fin du flux)
+    pub fn flush(&mut self) -> Option<NoteChange> {
+        self.candidate = None;
+        self.active.take().map(|note| NoteChange::Off { note })
+    }
+}
+
+/// Vélocité MIDI proportionnelle au niveau, de -60 dBFS (1) à 0 dBFS (127)
+fn velocity_from_level(level_db: f32) -> u8 {
+    let normalized = ((level_db + 60.0) / 60.0).clamp(0.0, 1.0);
+    (1.0 + normalized * 126.0).round() as u8
+}
@@ -0,0 +1,205 @@
+// src/audio/window.rs
+
+use std::collections::HashMap;
+use std::f32::consts::PI;
+
+/// Paramètre de la fenêtre de Kaiser quand il n'est pas précisé : lobes secondaires
+/// proches de ceux d'une fenêtre de Blackman
+const KAISER_BETA: f32 = 8.6;
+
+/// Longueur sur laquelle le gain cohérent est calculé, assez grande pour que les fenêtres
+/// symétriques rejoignent leur valeur théorique
+const COHERENT_GAIN_LEN: usize = 4096;
+
+/// Fenêtre d'analyse appliquée à une trame avant la FFT
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Window {
+    /// Aucune pondération : lobe principal le plus étroit, fuite spectrale la plus forte
+    Rectangular,
+    Hann,
+    #[default]
+    Hamming,
+    /// Blackman-Harris à 4 termes : lobes secondaires à -92 dB, lobe principal large
+    BlackmanHarris,
+    /// Compromis réglable : `beta` plus grand élargit le lobe principal et abaisse les
+    /// lobes secondaires
+    Kaiser {
+        beta: f32,
+    },
+    /// Sommet plat : amplitude d'un pic juste même entre deux bins, fréquence peu précise
+    FlatTop,
+}
+
+impl Window {
+    /// Nom utilisé en ligne de commande : `rectangular`, `hann`, `hamming`,
+    /// `blackman-harris`, `kaiser` ou `kaiser:<beta>`, `flat-top`
+    pub fn parse(name: &str) -> Option<Window> {
+        match name {
+            "rectangular" => Some(Window::Rectangular),
+            "hann" => Some(Window::Hann),
+            "hamming" => Some(Window::Hamming),
+            "blackman-harris" => Some(Window::BlackmanHarris),
+            "kaiser" => Some(Window::Kaiser { beta: KAISER_BETA }),
+            "flat-top" => Some(Window::FlatTop),
+            _ => {
+                let beta: f32 = name.strip_prefix("kaiser:")?.parse().ok()?;
+                (beta >= 0.0).then_some(Window::Kaiser { beta })
+            }
+        }
+    }
+
+    /// Coefficients (symétriques) pour une trame de `len` échantillons
+    pub fn coefficients(self, len: usize) -> Vec<f32> {
+        if len <= 1 {
+            return vec![1.0; len];
+        }
+        (0..len).map(|n| self.coefficient(n, len)).collect()
+    }
+
+    /// Gain cohérent : moyenne des coefficients, par laquelle la fenêtre divise l'amplitude
+    /// d'une sinusoïde dans le spectre (0,54 pour Hamming, 0,5 pour Hann)
+    pub fn coherent_gain(self) -> f32 {
+        let sum: f64 = (0..COHERENT_GAIN_LEN)
+            .map(|n| self.coefficient(n, COHERENT_GAIN_LEN) as f64)
+            .sum();
+        (sum / COHERENT_GAIN_LEN as f64) as f32
+    }
+
+    /// Coefficient `n` d'une fenêtre de `len` échantillons (`len` > 1)
+    fn coefficient(self, n: usize, len: usize) -> f32 {
+        let last = (len - 1) as f32;
+        let phase = 2.0 * PI * n as f32 / last;
+        match self {
+            Window::Rectangular => 1.0,
+            Window::Hann => 0.5 - 0.5 * phase.cos(),
+            Window::Hamming => 0.54 - 0.46 * phase.cos(),
+            Window::BlackmanHarris => cosine_sum(&[0.35875, 0.48829, 0.14128, 0.01168], phase),
+            Window::Kaiser { beta } => {
+                let x = 2.0 * n as f32 / last - 1.0;
+                bessel_i0(beta * (1.0 - x * x).max(0.0).sqrt()) / bessel_i0(beta)
+            }
+            Window::FlatTop => cosine_sum(
+                &[
+                    0.215_578_95,
+                    0.416_631_58,
+                    0.277_263_16,
+                    0.083_578_95,
+                    0.006_947_37,
+                ],
+                phase,
+            ),
+        }
+    }
+
+    /// Applique la fenêtre en calculant ses coefficients ; voir `WindowTables` pour
+    /// les traitements répétés
+    pub fn apply(self, samples: &mut [f32]) {
+        let coefficients = self.coefficients(samples.len());
+        multiply(samples, &coefficients);
+    }
+}
+
+/// Tables de coefficients d'une fenêtre, calculées une seule fois par taille de trame
+#[derive(Debug, Clone)]
+pub struct WindowTables {
+    window: Window,
+    tables: HashMap<usize, Vec<f32>>,
+}
+
+impl WindowTables {
+    pub fn new(window: Window) -> Self {
+        WindowTables {
+            window,
+            tables: HashMap::new(),
+        }
+    }
+
+    pub fn window(&self) -> Window {
+        self.window
+    }
+
+    /// Coefficients pour une trame de `len` échantillons
+    pub fn coefficients(&mut self, len: usize) -> &[f32] {
+        let window = self.window;
+        self.tables
+            .entry(len)
+            .or_insert_with(|| window.coefficients(len))
+    }
+
+    /// Multiplie les échantillons par la fenêtre
+    pub fn apply(&mut self, samples: &mut [f32]) {
+        let coefficients = self.coefficients(samples.len());
+        multiply(samples, coefficients);
+    }
+}
+
+impl Default for WindowTables {
+    fn default() -> Self {
+        WindowTables::new(Window::default())
+    }
+}
+
+fn multiply(samples: &mut [f32], coefficients: &[f32]) {
+    for (sample, coefficient) in samples.iter_mut().zip(coefficients) {
+        *sample *= coefficient;
+    }
+}
+
+/// Somme de cosinus `a0 - a1 cos(φ) + a2 cos(2φ) - ...`
+fn cosine_sum(terms: &[f32], phase: f32) -> f32 {
+    terms
+        .iter()
+        .enumerate()
+        .map(|(k, a)| {
+            let sign = if k % 2 == 0 { 1.0 } else { -1.0 };
+            sign * a * (k as f32 * phase).cos()
+        })
+        .sum()
+}
+
+/// Fonction de Bessel modifiée de première espèce d'ordre 0 (série entière)
+fn bessel_i0(x: f32) -> f32 {
+    let half = x / 2.0;
+    let mut term = 1.0f32;
+    let mut sum = 1.0f32;
+    for k in 1..50 {
+        term *= (half / k as f32) * (half / k as f32);
+        sum += term;
+        if term < sum * 1e-9 {
+            break;
+        }
+    }
+    sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coherent_gain_of_cosine_windows_is_their_first_term() {
+        for (window, gain) in [
+            (Window::Rectangular, 1.0),
+            (Window::Hann, 0.5),
+            (Window::Hamming, 0.54),
+            (Window::BlackmanHarris, 0.35875),
+            (Window::FlatTop, 0.215_578_95),
+        ] {
+            assert!(
+                (window.coherent_gain() - gain).abs() < 1e-3,
+                "{:?} : {}",
+                window,
+                window.coherent_gain()
+            );
+        }
+    }
+
+    #[test]
+    fn kaiser_gain_decreases_with_beta() {
+        let gain = |beta| Window::Kaiser { beta }.coherent_gain();
+        assert!((gain(0.0) - 1.0).abs() < 1e-4);
+        assert!(gain(5.0) < gain(2.0) && gain(8.6) < gain(5.0));
+        // Kaiser β = 8,6 : proche de Blackman (0,42)
+        assert!((gain(8.6) - 0.42).abs() < 0.03, "{}", gain(8.6));
+    }
+}
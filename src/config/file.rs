@@ -0,0 +1,994 @@
+// src/config/file.rs
+
+use crate::audio::capture::LatencyConfig;
+use crate::audio::controls::LiveParams;
+use crate::audio::detector::DetectorKind;
+use crate::audio::filter::{FilterConfig, FilterPreset};
+use crate::audio::framer::Framer;
+use crate::audio::profile::{self, DetectionProfile, InstrumentProfile};
+use crate::audio::quantize::{Grid, QuantizeConfig, TempoSource};
+use crate::audio::smoothing::{SmoothingConfig, MAX_MEDIAN};
+use crate::audio::tee::{TeeConfig, TeeFormat};
+use crate::audio::tracker::TrackerConfig;
+use crate::audio::window::Window;
+use crate::leds::{ChannelOrder, DmxMapping, DmxProtocol, LedConfig, LedMode, Palette};
+use crate::tuning::Tuning;
+use serde::Deserialize;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+/// Nombre de canaux entrelacés de la capture
+const CHANNELS: usize = 2;
+
+/// Fichier de configuration illisible ou invalide
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(io::Error),
+    /// Syntaxe TOML, clé inconnue ou valeur du mauvais type
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// Valeur refusée, avec le chemin complet de sa clé (`detector.window`)
+    Invalid {
+        key: &'static str,
+        message: String,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(err) => write!(f, "configuration illisible : {}", err),
+            ConfigError::Parse {
+                line,
+                column,
+                message,
+            } => write!(
+                f,
+                "configuration, ligne {} colonne {} : {}",
+                line, column, message
+            ),
+            ConfigError::Invalid { key, message } => {
+                write!(f, "configuration, clé « {} » : {}", key, message)
+            }
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+fn invalid(key: &'static str, message: impl Into<String>) -> ConfigError {
+    ConfigError::Invalid {
+        key,
+        message: message.into(),
+    }
+}
+
+/// Source PulseAudio et format de capture
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SourceSection {
+    pub name: String,
+    pub sample_rate: u32,
+    /// Budget de latence de la capture, en millisecondes
+    pub latency_ms: u64,
+    /// Copie brute de la capture ; vide pour ne pas la garder
+    pub tee: String,
+    /// Format de la copie : `wav16`, `wav24`, `wav-float` ou `flac`
+    pub tee_format: String,
+    /// Taille (octets de données audio) et durée (secondes) d'un fichier de la copie avant
+    /// de passer au suivant ; sans rotation si absentes
+    pub tee_max_bytes: Option<u64>,
+    pub tee_max_duration: Option<u64>,
+}
+
+impl Default for SourceSection {
+    fn default() -> Self {
+        SourceSection {
+            name: "bluez_source.DC_E5_5B_25_03_64.a2dp_source".to_string(),
+            sample_rate: 44100,
+            latency_ms: 20,
+            tee: "enregistrement.wav".to_string(),
+            tee_format: "wav16".to_string(),
+            tee_max_bytes: None,
+            tee_max_duration: None,
+        }
+    }
+}
+
+/// Découpage du flux en trames d'analyse
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FramingSection {
+    pub frame_size: usize,
+    pub hop_size: usize,
+}
+
+impl Default for FramingSection {
+    fn default() -> Self {
+        FramingSection {
+            frame_size: 2048,
+            hop_size: 1024,
+        }
+    }
+}
+
+/// Pré-filtrage et détection de hauteur
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DetectorSection {
+    /// Profil d'instrument (`guitar`, `bass`, `soprano`...) : il donne leur valeur aux clés
+    /// de détection, de découpage et du gate absentes du fichier
+    pub profile: Option<String>,
+    /// Algorithme de détection : `fft` ou `yin`
+    pub algorithm: String,
+    /// Fenêtre d'analyse (`hamming`, `hann`, `blackman-harris`, `kaiser:6`...)
+    pub window: String,
+    /// Préréglage du pré-filtrage (`general`, `guitar`...) ou `none`
+    pub filter: String,
+    /// Fréquence du secteur rejetée, 0 sans réjecteur ; celle du préréglage si absente
+    pub mains: Option<f32>,
+    /// Bande de recherche de la fondamentale, en Hz (rechargée à chaud)
+    pub min_frequency: f32,
+    pub max_frequency: f32,
+}
+
+impl Default for DetectorSection {
+    fn default() -> Self {
+        let params = LiveParams::default();
+        DetectorSection {
+            profile: None,
+            algorithm: DetectorKind::default().name().to_string(),
+            window: "hamming".to_string(),
+            filter: "general".to_string(),
+            mains: None,
+            min_frequency: params.min_frequency,
+            max_frequency: params.max_frequency,
+        }
+    }
+}
+
+/// Lissage de la hauteur avant la segmentation en notes
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SmoothingSection {
+    /// Fenêtre du filtre médian en trames, 0 sans filtre
+    pub median: usize,
+    /// Correction des sauts d'octave, acceptés après `octave_frames` trames
+    pub octave: bool,
+    pub octave_frames: usize,
+    /// Choix du chemin le plus vraisemblable parmi les candidats du détecteur
+    pub viterbi: bool,
+    pub transition_cents: f32,
+}
+
+impl Default for SmoothingSection {
+    fn default() -> Self {
+        let smoothing = SmoothingConfig::default();
+        SmoothingSection {
+            median: smoothing.median,
+            octave: smoothing.octave_correction,
+            octave_frames: smoothing.octave_frames,
+            viterbi: smoothing.viterbi,
+            transition_cents: smoothing.transition_cents,
+        }
+    }
+}
+
+/// Seuils du gate, en dBFS (rechargés à chaud)
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GateSection {
+    pub open_db: f32,
+    pub close_db: f32,
+}
+
+impl Default for GateSection {
+    fn default() -> Self {
+        let tracker = TrackerConfig::default();
+        GateSection {
+            open_db: tracker.gate_open_db,
+            close_db: tracker.gate_close_db,
+        }
+    }
+}
+
+/// Segmentation en notes (rechargée à chaud)
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TrackerSection {
+    pub min_frames: usize,
+    pub min_confidence: f32,
+}
+
+impl Default for TrackerSection {
+    fn default() -> Self {
+        let tracker = TrackerConfig::default();
+        TrackerSection {
+            min_frames: tracker.min_frames,
+            min_confidence: tracker.min_confidence,
+        }
+    }
+}
+
+/// Sortie MIDI
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MidiSection {
+    /// Numéro ou partie du nom du port ; demandé au lancement s'il est absent
+    pub port: Option<String>,
+    /// Canal 0 à 15 (rechargé à chaud)
+    pub channel: u8,
+}
+
+/// Enregistrement des notes en WAV, quantifié ou non
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RecorderSection {
+    pub path: String,
+    pub sample_rate: u32,
+    /// Grille de quantification (`1/8`, `1/16`, `1/8t`, `1/16t`) ; sans quantification si absente
+    pub quantize: Option<String>,
+    /// Tempo fixe de la grille ; tempo détecté s'il est absent
+    pub bpm: Option<f32>,
+    /// Part du chemin vers la grille parcourue par chaque note, dans [0, 1]
+    pub strength: f32,
+    pub swing: f32,
+}
+
+impl Default for RecorderSection {
+    fn default() -> Self {
+        RecorderSection {
+            path: "recorded_notes_all.wav".to_string(),
+            sample_rate: 44100,
+            quantize: None,
+            bpm: None,
+            strength: 1.0,
+            swing: 0.0,
+        }
+    }
+}
+
+/// Bande de LEDs
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LedSection {
+    /// `terminal`, `/dev/spidev0.0`, `sacn[:ip]` ou `artnet[:ip]` ; sans LEDs si absente
+    pub output: Option<String>,
+    pub mode: String,
+    pub count: usize,
+    pub brightness: f32,
+    pub flash: bool,
+    pub beat: bool,
+    /// Dégradé du mode spectre : `rainbow`, `fire` ou `ocean`
+    pub palette: String,
+    /// Lissage de la retombée des modes spectre et chroma, dans [0, 1[
+    pub smoothing: f32,
+    /// Bande de fréquences couverte par le mode spectre
+    pub min_frequency: f32,
+    pub max_frequency: f32,
+    /// Univers de la première LED des nœuds DMX ; 1 en sACN, 0 en Art-Net si absent
+    pub universe: Option<u16>,
+    /// Canal de la première LED dans son univers, à partir de 1
+    pub start_channel: u16,
+    /// `rgb`, `grb` ou `bgr`
+    pub order: String,
+    pub leds_per_universe: usize,
+}
+
+impl Default for LedSection {
+    fn default() -> Self {
+        let leds = LedConfig::default();
+        let dmx = DmxMapping::default();
+        LedSection {
+            output: None,
+            mode: "notes".to_string(),
+            count: leds.led_count,
+            brightness: leds.brightness,
+            flash: leds.onset_flash,
+            beat: leds.beat_pulse,
+            palette: "rainbow".to_string(),
+            smoothing: leds.smoothing,
+            min_frequency: leds.min_frequency,
+            max_frequency: leds.max_frequency,
+            universe: None,
+            start_channel: dmx.start_channel,
+            order: "rgb".to_string(),
+            leds_per_universe: dmx.leds_per_universe,
+        }
+    }
+}
+
+/// Configuration complète du programme, lue depuis un fichier TOML ; les clés absentes
+/// gardent leur valeur par défaut, les clés inconnues sont refusées
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub source: SourceSection,
+    pub framing: FramingSection,
+    pub detector: DetectorSection,
+    pub smoothing: SmoothingSection,
+    pub gate: GateSection,
+    pub tracker: TrackerSection,
+    pub midi: MidiSection,
+    pub recorder: RecorderSection,
+    pub leds: LedSection,
+}
+
+impl Config {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let text = fs::read_to_string(path).map_err(ConfigError::Io)?;
+        Self::parse(&text)
+    }
+
+    /// Lit et valide une configuration TOML
+    pub fn parse(text: &str) -> Result<Self, ConfigError> {
+        let mut config: Config = toml::from_str(text).map_err(|err| parse_error(text, err))?;
+        if let Some(instrument) = config.profile()? {
+            // Le fichier est déjà lu sans erreur : seules ses clés présentes sont recherchées
+            let table: toml::Table = toml::from_str(text).map_err(|err| parse_error(text, err))?;
+            let given = |section: &str, key: &str| {
+                table
+                    .get(section)
+                    .and_then(|section| section.get(key))
+                    .is_some()
+            };
+            config.fill_from_profile(&DetectionProfile::preset(instrument), given);
+        }
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Applique un profil d'instrument, qui remplace les réglages de détection, de découpage
+    /// et du gate (profil choisi en ligne de commande) ; le résultat est vérifié comme un
+    /// fichier chargé, le profil pouvant dépasser la fréquence d'échantillonnage du fichier
+    pub fn with_profile(mut self, instrument: InstrumentProfile) -> Result<Self, ConfigError> {
+        self.detector.profile = Some(instrument.name().to_string());
+        self.fill_from_profile(&DetectionProfile::preset(instrument), |_, _| false);
+        self.validate()?;
+        Ok(self)
+    }
+
+    /// Reprend du profil chaque clé pour laquelle `given(section, clé)` est faux
+    fn fill_from_profile(
+        &mut self,
+        profile: &DetectionProfile,
+        given: impl Fn(&str, &str) -> bool,
+    ) {
+        if !given("framing", "frame_size") {
+            self.framing.frame_size = profile.frame_size;
+        }
+        if !given("framing", "hop_size") {
+            self.framing.hop_size = profile.hop_size;
+        }
+        if !given("detector", "algorithm") {
+            self.detector.algorithm = profile.detector.name().to_string();
+        }
+        if !given("detector", "filter") {
+            self.detector.filter = profile.filter.name().to_string();
+        }
+        if !given("detector", "min_frequency") {
+            self.detector.min_frequency = profile.min_frequency;
+        }
+        if !given("detector", "max_frequency") {
+            self.detector.max_frequency = profile.max_frequency;
+        }
+        if !given("gate", "open_db") {
+            self.gate.open_db = profile.gate_open_db;
+        }
+        if !given("gate", "close_db") {
+            self.gate.close_db = profile.gate_close_db;
+        }
+    }
+
+    /// Vérifie chaque valeur ; l'erreur désigne la première clé refusée
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.source.name.is_empty() {
+            return Err(invalid("source.name", "nom de source vide"));
+        }
+        if !(8000..=192_000).contains(&self.source.sample_rate) {
+            return Err(invalid(
+                "source.sample_rate",
+                "fréquence attendue entre 8000 et 192000 Hz",
+            ));
+        }
+        if self.source.latency_ms == 0 {
+            return Err(invalid(
+                "source.latency_ms",
+                "la latence doit être positive",
+            ));
+        }
+        self.tee()?;
+        if self.framing.frame_size < 64 {
+            return Err(invalid(
+                "framing.frame_size",
+                "au moins 64 échantillons par trame",
+            ));
+        }
+        if !(1..=self.framing.frame_size).contains(&self.framing.hop_size) {
+            return Err(invalid(
+                "framing.hop_size",
+                format!("pas attendu entre 1 et {}", self.framing.frame_size),
+            ));
+        }
+        self.detector()?;
+        self.window()?;
+        self.filters()?;
+        if self.detector.min_frequency <= 0.0 {
+            return Err(invalid(
+                "detector.min_frequency",
+                "la fréquence doit être positive",
+            ));
+        }
+        let nyquist = self.source.sample_rate as f32 / 2.0;
+        if self.detector.max_frequency <= self.detector.min_frequency
+            || self.detector.max_frequency > nyquist
+        {
+            return Err(invalid(
+                "detector.max_frequency",
+                format!(
+                    "fréquence attendue entre detector.min_frequency et {} Hz",
+                    nyquist
+                ),
+            ));
+        }
+        self.smoothing()?;
+        if self.gate.close_db > self.gate.open_db {
+            return Err(invalid(
+                "gate.close_db",
+                "le seuil de fermeture doit être sous celui d'ouverture",
+            ));
+        }
+        if self.tracker.min_frames == 0 {
+            return Err(invalid("tracker.min_frames", "au moins une trame"));
+        }
+        if !(0.0..=1.0).contains(&self.tracker.min_confidence) {
+            return Err(invalid(
+                "tracker.min_confidence",
+                "confiance attendue entre 0 et 1",
+            ));
+        }
+        if self.midi.channel > 15 {
+            return Err(invalid("midi.channel", "canal attendu entre 0 et 15"));
+        }
+        if self.recorder.path.is_empty() {
+            return Err(invalid("recorder.path", "chemin vide"));
+        }
+        if self.recorder.sample_rate == 0 {
+            return Err(invalid(
+                "recorder.sample_rate",
+                "la fréquence doit être positive",
+            ));
+        }
+        self.quantize()?;
+        self.led_config()?;
+        let output = self.leds.output.as_deref().unwrap_or_default();
+        let protocol = output
+            .split_once(':')
+            .map_or(output, |(protocol, _)| protocol);
+        if let Some(protocol) = DmxProtocol::parse(protocol) {
+            self.dmx_mapping(protocol)?;
+        }
+        Ok(())
+    }
+
+    /// Profil d'instrument, `None` sans profil
+    pub fn profile(&self) -> Result<Option<InstrumentProfile>, ConfigError> {
+        let Some(name) = &self.detector.profile else {
+            return Ok(None);
+        };
+        InstrumentProfile::parse(name)
+            .map(Some)
+            .ok_or_else(|| invalid("detector.profile", format!("profil inconnu « {} »", name)))
+    }
+
+    pub fn detector(&self) -> Result<DetectorKind, ConfigError> {
+        DetectorKind::parse(&self.detector.algorithm).ok_or_else(|| {
+            invalid(
+                "detector.algorithm",
+                format!("algorithme inconnu « {} »", self.detector.algorithm),
+            )
+        })
+    }
+
+    pub fn window(&self) -> Result<Window, ConfigError> {
+        Window::parse(&self.detector.window).ok_or_else(|| {
+            invalid(
+                "detector.window",
+                format!("fenêtre inconnue « {} »", self.detector.window),
+            )
+        })
+    }
+
+    pub fn filters(&self) -> Result<FilterConfig, ConfigError> {
+        let filters = match self.detector.filter.as_str() {
+            "none" => FilterConfig::none(),
+            name => FilterConfig::preset(FilterPreset::parse(name).ok_or_else(|| {
+                invalid(
+                    "detector.filter",
+                    format!("préréglage inconnu « {} »", name),
+                )
+            })?),
+        };
+        match self.detector.mains {
+            None => Ok(filters),
+            Some(0.0) => Ok(filters.with_hum(None)),
+            Some(mains) if mains > 0.0 => Ok(filters.with_hum(Some(mains))),
+            Some(_) => Err(invalid(
+                "detector.mains",
+                "fréquence du secteur attendue : 50, 60 ou 0",
+            )),
+        }
+    }
+
+    pub fn smoothing(&self) -> Result<SmoothingConfig, ConfigError> {
+        if self.smoothing.median > MAX_MEDIAN {
+            return Err(invalid(
+                "smoothing.median",
+                format!("fenêtre attendue entre 0 et {} trames", MAX_MEDIAN),
+            ));
+        }
+        if self.smoothing.octave_frames == 0 {
+            return Err(invalid("smoothing.octave_frames", "au moins une trame"));
+        }
+        if self.smoothing.transition_cents <= 0.0 {
+            return Err(invalid(
+                "smoothing.transition_cents",
+                "l'écart doit être positif",
+            ));
+        }
+        Ok(SmoothingConfig {
+            median: self.smoothing.median,
+            octave_correction: self.smoothing.octave,
+            octave_frames: self.smoothing.octave_frames,
+            viterbi: self.smoothing.viterbi,
+            transition_cents: self.smoothing.transition_cents,
+        })
+    }
+
+    pub fn framer(&self) -> Framer {
+        Framer::new(CHANNELS, self.framing.frame_size, self.framing.hop_size)
+    }
+
+    /// Copie brute de la capture, `None` si `source.tee` est vide
+    pub fn tee(&self) -> Result<Option<TeeConfig>, ConfigError> {
+        let format = TeeFormat::parse(&self.source.tee_format).ok_or_else(|| {
+            invalid(
+                "source.tee_format",
+                format!("format inconnu « {} »", self.source.tee_format),
+            )
+        })?;
+        if self.source.tee_max_bytes == Some(0) {
+            return Err(invalid(
+                "source.tee_max_bytes",
+                "la taille doit être positive",
+            ));
+        }
+        if self.source.tee_max_duration == Some(0) {
+            return Err(invalid(
+                "source.tee_max_duration",
+                "la durée doit être positive",
+            ));
+        }
+        if self.source.tee.is_empty() {
+            return Ok(None);
+        }
+        let mut tee = TeeConfig::new(&self.source.tee, format);
+        if let Some(max_bytes) = self.source.tee_max_bytes {
+            tee = tee.with_max_bytes(max_bytes);
+        }
+        if let Some(seconds) = self.source.tee_max_duration {
+            tee = tee.with_max_duration(Duration::from_secs(seconds));
+        }
+        Ok(Some(tee))
+    }
+
+    pub fn latency(&self) -> LatencyConfig {
+        LatencyConfig::new(Duration::from_millis(self.source.latency_ms))
+    }
+
+    /// Paramètres rechargeables à chaud
+    pub fn live_params(&self) -> LiveParams {
+        LiveParams {
+            gate_open_db: self.gate.open_db,
+            gate_close_db: self.gate.close_db,
+            min_confidence: self.tracker.min_confidence,
+            min_frames: self.tracker.min_frames,
+            min_frequency: self.detector.min_frequency,
+            max_frequency: self.detector.max_frequency,
+        }
+    }
+
+    /// Segmentation en notes ; avec un profil, seules les notes de la bande du détecteur
+    /// sont transcrites (étendue du piano sinon)
+    pub fn tracker(&self, tuning: Tuning) -> TrackerConfig {
+        let mut tracker = TrackerConfig {
+            gate_open_db: self.gate.open_db,
+            gate_close_db: self.gate.close_db,
+            min_frames: self.tracker.min_frames,
+            min_confidence: self.tracker.min_confidence,
+            ..TrackerConfig::default()
+        };
+        if self.detector.profile.is_some() {
+            tracker.note_range = profile::note_range(
+                &tuning,
+                self.detector.min_frequency,
+                self.detector.max_frequency,
+            );
+        }
+        tracker.tuning = tuning;
+        tracker
+    }
+
+    /// Quantification de l'enregistrement, `None` sans grille ; la force et le swing sont
+    /// vérifiés même sans grille, la ligne de commande pouvant en fournir une
+    pub fn quantize(&self) -> Result<Option<QuantizeConfig>, ConfigError> {
+        if !(0.0..=1.0).contains(&self.recorder.strength) {
+            return Err(invalid("recorder.strength", "force attendue entre 0 et 1"));
+        }
+        if !(0.0..=1.0).contains(&self.recorder.swing) {
+            return Err(invalid("recorder.swing", "swing attendu entre 0 et 1"));
+        }
+        let Some(grid) = &self.recorder.quantize else {
+            return Ok(None);
+        };
+        let grid = Grid::parse(grid)
+            .ok_or_else(|| invalid("recorder.quantize", format!("grille inconnue « {} »", grid)))?;
+        let tempo = match self.recorder.bpm {
+            Some(bpm) if bpm > 0.0 => TempoSource::Fixed(bpm),
+            Some(_) => return Err(invalid("recorder.bpm", "le tempo doit être positif")),
+            None => TempoSource::Detected,
+        };
+        Ok(Some(
+            QuantizeConfig::new(grid, tempo)
+                .with_strength(self.recorder.strength)
+                .with_swing(self.recorder.swing),
+        ))
+    }
+
+    pub fn led_config(&self) -> Result<LedConfig, ConfigError> {
+        let mode = LedMode::parse(&self.leds.mode)
+            .ok_or_else(|| invalid("leds.mode", format!("mode inconnu « {} »", self.leds.mode)))?;
+        if self.leds.count == 0 {
+            return Err(invalid("leds.count", "au moins une LED"));
+        }
+        if !(0.0..=1.0).contains(&self.leds.brightness) {
+            return Err(invalid(
+                "leds.brightness",
+                "luminosité attendue entre 0 et 1",
+            ));
+        }
+        let palette = Palette::parse(&self.leds.palette).ok_or_else(|| {
+            invalid(
+                "leds.palette",
+                format!("palette inconnue « {} »", self.leds.palette),
+            )
+        })?;
+        if !(0.0..1.0).contains(&self.leds.smoothing) {
+            return Err(invalid(
+                "leds.smoothing",
+                "lissage attendu entre 0 et 1 (exclu)",
+            ));
+        }
+        if self.leds.min_frequency <= 0.0 {
+            return Err(invalid(
+                "leds.min_frequency",
+                "la fréquence doit être positive",
+            ));
+        }
+        let nyquist = self.source.sample_rate as f32 / 2.0;
+        if self.leds.max_frequency <= self.leds.min_frequency || self.leds.max_frequency > nyquist {
+            return Err(invalid(
+                "leds.max_frequency",
+                format!(
+                    "fréquence attendue entre leds.min_frequency et {} Hz",
+                    nyquist
+                ),
+            ));
+        }
+        Ok(LedConfig {
+            led_count: self.leds.count,
+            brightness: self.leds.brightness,
+            mode,
+            palette,
+            smoothing: self.leds.smoothing,
+            min_frequency: self.leds.min_frequency,
+            max_frequency: self.leds.max_frequency,
+            onset_flash: self.leds.flash,
+            analysis_rate: self.source.sample_rate as f32 / self.framing.hop_size.max(1) as f32,
+            beat_pulse: self.leds.beat,
+            ..LedConfig::default()
+        })
+    }
+
+    /// Répartition de la bande sur les univers des nœuds DMX ; l'univers de départ par défaut
+    /// dépend du protocole
+    pub fn dmx_mapping(&self, protocol: DmxProtocol) -> Result<DmxMapping, ConfigError> {
+        let universes = protocol.universes();
+        let universe = self.leds.universe.unwrap_or(*universes.start());
+        if !universes.contains(&universe) {
+            return Err(invalid(
+                "leds.universe",
+                format!(
+                    "univers attendu entre {} et {}",
+                    universes.start(),
+                    universes.end()
+                ),
+            ));
+        }
+        // Au moins une LED (trois canaux) doit tenir dans le premier univers
+        if !(1..=510).contains(&self.leds.start_channel) {
+            return Err(invalid(
+                "leds.start_channel",
+                "canal attendu entre 1 et 510",
+            ));
+        }
+        let order = ChannelOrder::parse(&self.leds.order).ok_or_else(|| {
+            invalid(
+                "leds.order",
+                format!("ordre inconnu « {} »", self.leds.order),
+            )
+        })?;
+        if !(1..=170).contains(&self.leds.leds_per_universe) {
+            return Err(invalid(
+                "leds.leds_per_universe",
+                "entre 1 et 170 LEDs par univers",
+            ));
+        }
+        let mapping = DmxMapping::new(universe, self.leds.start_channel)
+            .with_leds_per_universe(self.leds.leds_per_universe)
+            .with_order(order);
+        // Toute la bande doit tenir dans les univers du protocole
+        let last = universe as usize + mapping.universe_count(self.leds.count) - 1;
+        if last > *universes.end() as usize {
+            return Err(invalid(
+                "leds.count",
+                format!(
+                    "{} LEDs à partir de l'univers {} dépassent le dernier univers {}",
+                    self.leds.count,
+                    universe,
+                    universes.end()
+                ),
+            ));
+        }
+        Ok(mapping)
+    }
+
+    /// Clés modifiées entre deux configurations qui ne prennent effet qu'au redémarrage
+    pub fn restart_keys(&self, other: &Config) -> Vec<&'static str> {
+        let changes = [
+            ("source", self.source != other.source),
+            ("framing", self.framing != other.framing),
+            (
+                "detector.profile",
+                self.detector.profile != other.detector.profile,
+            ),
+            (
+                "detector.algorithm",
+                self.detector.algorithm != other.detector.algorithm,
+            ),
+            (
+                "detector.window",
+                self.detector.window != other.detector.window,
+            ),
+            (
+                "detector.filter",
+                self.detector.filter != other.detector.filter,
+            ),
+            (
+                "detector.mains",
+                self.detector.mains != other.detector.mains,
+            ),
+            ("smoothing", self.smoothing != other.smoothing),
+            ("midi.port", self.midi.port != other.midi.port),
+            ("recorder", self.recorder != other.recorder),
+            ("leds", self.leds != other.leds),
+        ];
+        changes
+            .into_iter()
+            .filter(|(_, changed)| *changed)
+            .map(|(key, _)| key)
+            .collect()
+    }
+}
+
+/// Erreur de lecture TOML, située par sa ligne et sa colonne
+fn parse_error(text: &str, err: toml::de::Error) -> ConfigError {
+    let (line, column) = err.span().map_or((0, 0), |span| position(text, span.start));
+    ConfigError::Parse {
+        line,
+        column,
+        message: err.message().to_string(),
+    }
+}
+
+/// Ligne et colonne (à partir de 1) d'une position en octets dans le texte
+fn position(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.chars().rev().take_while(|&c| c != '\n').count() + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dmx_universe_defaults_to_the_protocol_first_universe() {
+        let config = Config::parse("[leds]\noutput = \"artnet\"\n").unwrap();
+        assert_eq!(
+            config
+                .dmx_mapping(DmxProtocol::ArtNet)
+                .unwrap()
+                .start_universe,
+            0
+        );
+        assert_eq!(
+            config
+                .dmx_mapping(DmxProtocol::Sacn)
+                .unwrap()
+                .start_universe,
+            1
+        );
+    }
+
+    #[test]
+    fn dmx_mapping_reads_the_leds_section() {
+        let text = "[leds]\noutput = \"sacn:10.0.0.2\"\nuniverse = 5\nstart_channel = 4\n\
+                    order = \"grb\"\nleds_per_universe = 100\n";
+        let mapping = Config::parse(text)
+            .unwrap()
+            .dmx_mapping(DmxProtocol::Sacn)
+            .unwrap();
+        assert_eq!(mapping.start_universe, 5);
+        assert_eq!(mapping.start_channel, 4);
+        assert_eq!(mapping.order, ChannelOrder::Grb);
+        assert_eq!(mapping.leds_per_universe, 100);
+    }
+
+    #[test]
+    fn invalid_dmx_keys_are_rejected() {
+        let rejected = |leds: &str| match Config::parse(&format!("[leds]\n{}", leds)) {
+            Err(ConfigError::Invalid { key, .. }) => key,
+            other => panic!("configuration acceptée : {:?}", other),
+        };
+        assert_eq!(rejected("output = \"sacn\"\nuniverse = 0"), "leds.universe");
+        assert_eq!(
+            rejected("output = \"artnet\"\nuniverse = 40000"),
+            "leds.universe"
+        );
+        assert_eq!(
+            rejected("output = \"artnet\"\nstart_channel = 511"),
+            "leds.start_channel"
+        );
+        assert_eq!(rejected("output = \"sacn\"\norder = \"rbg\""), "leds.order");
+        assert_eq!(
+            rejected("output = \"sacn\"\nleds_per_universe = 171"),
+            "leds.leds_per_universe"
+        );
+        // 340 LEDs à 170 par univers : deux univers, le second hors plage
+        assert_eq!(
+            rejected("output = \"sacn\"\nuniverse = 63999\ncount = 340"),
+            "leds.count"
+        );
+        assert_eq!(
+            rejected("output = \"artnet\"\nuniverse = 32767\ncount = 171"),
+            "leds.count"
+        );
+        assert!(Config::parse("[leds]\noutput = \"sacn\"\nuniverse = 63998\ncount = 340").is_ok());
+        // Sans sortie DMX, les clés DMX ne sont pas vérifiées
+        assert!(Config::parse("[leds]\nuniverse = 0").is_ok());
+    }
+
+    #[test]
+    fn spectrum_keys_reach_the_led_config() {
+        let text = "[leds]\npalette = \"fire\"\nsmoothing = 0.5\nmin_frequency = 60.0\n\
+                    max_frequency = 12000.0\n";
+        let leds = Config::parse(text).unwrap().led_config().unwrap();
+        assert_eq!(leds.palette, Palette::fire());
+        assert_eq!(leds.smoothing, 0.5);
+        assert_eq!((leds.min_frequency, leds.max_frequency), (60.0, 12000.0));
+
+        let defaults = Config::default().led_config().unwrap();
+        assert_eq!(defaults.palette, Palette::rainbow());
+        assert_eq!(defaults.smoothing, LedConfig::default().smoothing);
+    }
+
+    #[test]
+    fn invalid_spectrum_keys_are_rejected() {
+        let rejected = |leds: &str| match Config::parse(&format!("[leds]\n{}", leds)) {
+            Err(ConfigError::Invalid { key, .. }) => key,
+            other => panic!("configuration acceptée : {:?}", other),
+        };
+        assert_eq!(rejected("palette = \"neon\""), "leds.palette");
+        assert_eq!(rejected("smoothing = 1.0"), "leds.smoothing");
+        assert_eq!(rejected("smoothing = -0.1"), "leds.smoothing");
+        assert_eq!(rejected("min_frequency = 0.0"), "leds.min_frequency");
+        assert_eq!(
+            rejected("min_frequency = 500.0\nmax_frequency = 400.0"),
+            "leds.max_frequency"
+        );
+        // Au-delà de la moitié de la fréquence d'échantillonnage (44,1 kHz par défaut)
+        assert_eq!(rejected("max_frequency = 23000.0"), "leds.max_frequency");
+    }
+
+    #[test]
+    fn tee_keys_reach_the_tee_config() {
+        let text = "[source]\ntee = \"copie-{n}.flac\"\ntee_format = \"flac\"\n\
+                    tee_max_bytes = 1000000\ntee_max_duration = 600\n";
+        let tee = Config::parse(text).unwrap().tee().unwrap().unwrap();
+        assert_eq!(tee.path_template, "copie-{n}.flac");
+        assert_eq!(tee.format, TeeFormat::Flac);
+        assert_eq!(tee.max_bytes, Some(1_000_000));
+        assert_eq!(tee.max_duration, Some(Duration::from_secs(600)));
+
+        let default = Config::default().tee().unwrap().unwrap();
+        assert_eq!(default.format, TeeFormat::Wav16);
+        assert_eq!((default.max_bytes, default.max_duration), (None, None));
+        assert!(Config::parse("[source]\ntee = \"\"")
+            .unwrap()
+            .tee()
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn invalid_tee_keys_are_rejected() {
+        let rejected = |source: &str| match Config::parse(&format!("[source]\n{}", source)) {
+            Err(ConfigError::Invalid { key, .. }) => key,
+            other => panic!("configuration acceptée : {:?}", other),
+        };
+        assert_eq!(rejected("tee_format = \"mp3\""), "source.tee_format");
+        assert_eq!(rejected("tee_max_bytes = 0"), "source.tee_max_bytes");
+        assert_eq!(rejected("tee_max_duration = 0"), "source.tee_max_duration");
+    }
+
+    #[test]
+    fn quantize_keys_are_validated() {
+        let text = "[recorder]\nquantize = \"1/16\"\nbpm = 100.0\nstrength = 0.5\nswing = 0.3\n";
+        let quantize = Config::parse(text).unwrap().quantize().unwrap().unwrap();
+        assert_eq!(quantize.strength, 0.5);
+        assert_eq!(quantize.swing, 0.3);
+        assert_eq!(quantize.tempo, TempoSource::Fixed(100.0));
+
+        let rejected = |recorder: &str| {
+            let text = format!("[recorder]\nquantize = \"1/8\"\n{}", recorder);
+            match Config::parse(&text) {
+                Err(ConfigError::Invalid { key, .. }) => key,
+                other => panic!("configuration acceptée : {:?}", other),
+            }
+        };
+        assert_eq!(rejected("strength = 1.5"), "recorder.strength");
+        assert_eq!(rejected("strength = -0.1"), "recorder.strength");
+        assert_eq!(rejected("swing = 2.0"), "recorder.swing");
+        assert_eq!(rejected("bpm = 0.0"), "recorder.bpm");
+        assert!(Config::parse("[recorder]\nstrength = 2.0\n").is_err());
+    }
+
+    #[test]
+    fn command_line_profile_is_validated() {
+        let narrow = Config::parse(
+            "[source]\nsample_rate = 8000\n\
+             [detector]\nmax_frequency = 3000.0\n\
+             [leds]\nmax_frequency = 3000.0\n",
+        )
+        .unwrap();
+        match narrow.with_profile(InstrumentProfile::Whistle) {
+            Err(ConfigError::Invalid { key, .. }) => assert_eq!(key, "detector.max_frequency"),
+            other => panic!("profil accepté : {:?}", other.map(|config| config.detector)),
+        }
+
+        let config = Config::default()
+            .with_profile(InstrumentProfile::Whistle)
+            .unwrap();
+        assert_eq!(config.detector.profile.as_deref(), Some("whistle"));
+        assert_eq!(config.detector.max_frequency, 4500.0);
+    }
+}
@@ -0,0 +1,13 @@
+// src/config/mod.rs
+
+//! Fichier de configuration TOML : source, trames, détecteur, gate, tracker, MIDI,
+//! enregistreur et LEDs, avec rechargement à chaud des paramètres sans risque
+
+pub mod file;
+pub mod reload;
+
+pub use file::{
+    Config, ConfigError, DetectorSection, FramingSection, GateSection, LedSection, MidiSection,
+    RecorderSection, SourceSection, TrackerSection,
+};
+pub use reload::ConfigWatcher;
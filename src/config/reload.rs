@@ -0,0 +1,179 @@
+// src/config/reload.rs
+
+use crate::audio::controls::ProcessorControls;
+use crate::audio::profile::InstrumentProfile;
+use crate::config::file::Config;
+use crate::midi::transcription::MidiStatus;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime};
+
+/// Période de vérification de la date de modification du fichier
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Recharge le fichier de configuration à chaque modification et applique à chaud les
+/// paramètres sans risque (seuils du gate, tracker, bande du détecteur, canal MIDI) ;
+/// les autres changements sont signalés et attendent le redémarrage
+pub struct ConfigWatcher {
+    path: PathBuf,
+    config: Config,
+    profile: Option<InstrumentProfile>,
+    controls: Option<Arc<ProcessorControls>>,
+    midi: Option<Arc<MidiStatus>>,
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ConfigWatcher {
+    /// `config` est la configuration déjà chargée depuis `path`, profil de `with_profile`
+    /// compris
+    pub fn new(path: impl Into<PathBuf>, config: Config) -> Self {
+        ConfigWatcher {
+            path: path.into(),
+            config,
+            profile: None,
+            controls: None,
+            midi: None,
+            running: Arc::new(AtomicBool::new(true)),
+            thread: None,
+        }
+    }
+
+    /// Profil d'instrument choisi en ligne de commande, réappliqué à chaque rechargement
+    /// pour que le fichier ne remplace pas sa bande de fréquences ni son gate
+    pub fn with_profile(mut self, profile: Option<InstrumentProfile>) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Contrôles du traitement qui reçoivent les seuils et la bande de fréquences
+    pub fn with_controls(mut self, controls: Arc<ProcessorControls>) -> Self {
+        self.controls = Some(controls);
+        self
+    }
+
+    /// Sortie MIDI dont le canal suit `midi.channel`
+    pub fn with_midi(mut self, status: Arc<MidiStatus>) -> Self {
+        self.midi = Some(status);
+        self
+    }
+
+    /// Démarre la surveillance du fichier dans un thread dédié
+    pub fn start(mut self) -> io::Result<Self> {
+        let mut reloader = Reloader {
+            modified: modified(&self.path),
+            path: self.path.clone(),
+            config: self.config.clone(),
+            profile: self.profile,
+            controls: self.controls.clone(),
+            midi: self.midi.clone(),
+        };
+        let running = Arc::clone(&self.running);
+        self.thread = Some(
+            thread::Builder::new()
+                .name("configuration".to_string())
+                .spawn(move || {
+                    while running.load(Ordering::Relaxed) {
+                        thread::sleep(POLL_INTERVAL);
+                        reloader.poll();
+                    }
+                })?,
+        );
+        Ok(self)
+    }
+
+    pub fn stop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            if thread.join().is_err() {
+                log::error!("Le thread de rechargement de la configuration a paniqué");
+            }
+        }
+    }
+}
+
+impl Drop for ConfigWatcher {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// État du thread de surveillance
+struct Reloader {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    /// Dernière configuration valide
+    config: Config,
+    profile: Option<InstrumentProfile>,
+    controls: Option<Arc<ProcessorControls>>,
+    midi: Option<Arc<MidiStatus>>,
+}
+
+impl Reloader {
+    fn poll(&mut self) {
+        // Un fichier momentanément absent (enregistrement par renommage) est ignoré
+        let modified = modified(&self.path);
+        if modified.is_none() || modified == self.modified {
+            return;
+        }
+        self.modified = modified;
+
+        let loaded = Config::load(&self.path).and_then(|config| match self.profile {
+            Some(profile) => config.with_profile(profile),
+            None => Ok(config),
+        });
+        let config = match loaded {
+            Ok(config) => config,
+            Err(err) => {
+                log::warn!("{} : {}, modification ignorée", self.path.display(), err);
+                return;
+            }
+        };
+        self.apply(&config);
+        self.config = config;
+    }
+
+    fn apply(&self, config: &Config) {
+        let params = config.live_params();
+        if params != self.config.live_params() {
+            if let Some(controls) = &self.controls {
+                controls.update(|live| *live = params);
+                log::info!(
+                    "Configuration rechargée : gate {:.0}/{:.0} dB, {} trame(s), confiance {:.2}, {:.0}-{:.0} Hz",
+                    params.gate_open_db,
+                    params.gate_close_db,
+                    params.min_frames,
+                    params.min_confidence,
+                    params.min_frequency,
+                    params.max_frequency
+                );
+            }
+        }
+        if config.midi.channel != self.config.midi.channel {
+            if let Some(midi) = &self.midi {
+                match midi.set_channel(config.midi.channel) {
+                    Ok(()) => log::info!("Canal MIDI : {}", config.midi.channel),
+                    Err(err) => log::warn!("{}", err),
+                }
+            }
+        }
+        let restart = self.config.restart_keys(config);
+        if !restart.is_empty() {
+            log::warn!(
+                "Modifié dans {} : {} ; pris en compte au prochain démarrage",
+                self.path.display(),
+                restart.join(", ")
+            );
+        }
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
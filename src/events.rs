@@ -0,0 +1,255 @@
+// src/events.rs
+
+use crate::audio::chord::Chord;
+use crate::audio::detector::Pitch;
+use crate::audio::key::Key;
+use crate::audio::ring::{bounded_keeping, ChannelMetrics, OverflowPolicy, RingSender};
+use crate::latency::Timestamps;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+/// Nombre d'événements en attente par abonné avant de jeter les plus anciens (les notes
+/// ne sont jamais jetées)
+const SUBSCRIBER_CAPACITY: usize = 256;
+
+/// Début ou fin d'une note
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoteState {
+    On,
+    Off,
+}
+
+/// Note détectée, diffusée à tous les abonnés du bus
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoteEvent {
+    pub state: NoteState,
+    pub note: u8,
+    /// Fréquence mesurée (fréquence nominale de la note pour un Note Off)
+    pub frequency: f32,
+    /// Écart en cents entre la fréquence mesurée et la note dans l'accord du pipeline
+    pub cents: f32,
+    pub velocity: u8,
+    /// Confiance de la détection dans [0, 1]
+    pub confidence: f32,
+    pub timestamps: Timestamps,
+}
+
+/// Hauteur brute d'une trame d'analyse, avant segmentation en notes
+#[derive(Debug, Clone, PartialEq)]
+pub struct PitchEvent {
+    /// `None` si le détecteur n'a rien trouvé
+    pub pitch: Option<Pitch>,
+    /// Niveau RMS de la trame en dBFS
+    pub level_db: f32,
+    pub timestamps: Timestamps,
+}
+
+/// Spectre d'amplitude d'une trame d'analyse, publié à chaque trame
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpectrumEvent {
+    /// Amplitudes des bins 0 à N/2 de la FFT (partagées entre les abonnés)
+    pub magnitudes: Arc<[f32]>,
+    /// Écart en Hz entre deux bins
+    pub bin_hz: f32,
+    /// Gain cohérent de la fenêtre d'analyse (`Window::coherent_gain`), à compenser pour
+    /// ramener les amplitudes en dBFS
+    pub window_gain: f32,
+    /// Niveau RMS de la trame en dBFS
+    pub level_db: f32,
+    pub timestamps: Timestamps,
+}
+
+/// Chromagramme d'une trame d'analyse : énergie des 12 classes de hauteur (0 = C),
+/// normalisée pour que la plus forte vaille 1
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChromaEvent {
+    pub chroma: [f32; 12],
+    pub level_db: f32,
+    pub timestamps: Timestamps,
+}
+
+/// Changement de la tonalité estimée
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyEvent {
+    pub key: Key,
+    pub timestamps: Timestamps,
+}
+
+/// Changement d'accord ; `None` quand plus aucun accord n'est reconnu
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChordEvent {
+    pub chord: Option<Chord>,
+    pub timestamps: Timestamps,
+}
+
+/// Battement détecté, avec le tempo courant
+#[derive(Debug, Clone, PartialEq)]
+pub struct BeatEvent {
+    pub bpm: f32,
+    /// Numéro du battement depuis le début du flux
+    pub number: u64,
+    pub timestamps: Timestamps,
+}
+
+/// Événements publiés par le pipeline d'analyse
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    Note(NoteEvent),
+    Pitch(PitchEvent),
+    Spectrum(SpectrumEvent),
+    Chroma(ChromaEvent),
+    Key(KeyEvent),
+    Chord(ChordEvent),
+    Beat(BeatEvent),
+}
+
+/// Consommateur d'événements ; chaque abonné tourne dans son propre thread
+pub trait EventSink: Send + 'static {
+    fn handle(&mut self, event: &Event);
+
+    /// Appelé une fois à la fermeture du bus, après le dernier événement
+    fn finish(&mut self) {}
+}
+
+/// Toute fermeture `FnMut(&Event)` peut servir d'abonné
+impl<F> EventSink for F
+where
+    F: FnMut(&Event) + Send + 'static,
+{
+    fn handle(&mut self, event: &Event) {
+        self(event)
+    }
+}
+
+struct Subscriber {
+    name: String,
+    sender: RingSender<Event>,
+    thread: JoinHandle<()>,
+}
+
+/// Diffuse les événements à un nombre quelconque d'abonnés sans bloquer la détection :
+/// chaque abonné a sa file bornée et son thread, un abonné lent ne perd que ses propres
+/// événements d'analyse ; les événements de note lui parviennent tous, pour qu'aucun
+/// Note Off ne manque au synthétiseur ni à l'enregistrement
+#[derive(Default)]
+pub struct EventBus {
+    subscribers: Vec<Subscriber>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enregistre un abonné et démarre son thread
+    pub fn subscribe<S: EventSink>(&mut self, name: &str, mut sink: S) {
+        let (sender, receiver) = bounded_keeping(
+            SUBSCRIBER_CAPACITY,
+            OverflowPolicy::DropOldest,
+            |event: &Event| matches!(event, Event::Note(_)),
+        );
+        let thread = thread::Builder::new()
+            .name(format!("sink-{}", name))
+            .spawn(move || {
+                while let Ok(event) = receiver.recv() {
+                    sink.handle(&event);
+                }
+                sink.finish();
+            })
+            .expect("Impossible de démarrer le thread de l'abonné");
+        self.subscribers.push(Subscriber {
+            name: name.to_string(),
+            sender,
+            thread,
+        });
+    }
+
+    pub fn publish(&self, event: Event) {
+        for subscriber in &self.subscribers {
+            if subscriber.sender.send(event.clone()).is_err() {
+                log::warn!("L'abonné {} ne reçoit plus d'événements", subscriber.name);
+            }
+        }
+    }
+
+    /// Compteurs de la file de chaque abonné (événements perdus si l'abonné est trop lent)
+    pub fn metrics(&self) -> Vec<(String, Arc<ChannelMetrics>)> {
+        self.subscribers
+            .iter()
+            .map(|s| (s.name.clone(), s.sender.metrics()))
+            .collect()
+    }
+
+    /// Ferme les files et attend que chaque abonné ait traité ses derniers événements
+    pub fn shutdown(self) {
+        for subscriber in self.subscribers {
+            drop(subscriber.sender);
+            if subscriber.thread.join().is_err() {
+                log::error!("L'abonné {} a paniqué", subscriber.name);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use std::time::{Duration, Instant};
+
+    fn note(state: NoteState, note: u8) -> Event {
+        Event::Note(NoteEvent {
+            state,
+            note,
+            frequency: 440.0,
+            cents: 0.0,
+            velocity: 100,
+            confidence: 1.0,
+            timestamps: Timestamps::new(Instant::now()),
+        })
+    }
+
+    fn pitch() -> Event {
+        Event::Pitch(PitchEvent {
+            pitch: None,
+            level_db: -20.0,
+            timestamps: Timestamps::new(Instant::now()),
+        })
+    }
+
+    #[test]
+    fn slow_subscriber_keeps_every_note_event() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let mut bus = EventBus::new();
+        let sink_received = Arc::clone(&received);
+        bus.subscribe("lent", move |event: &Event| {
+            thread::sleep(Duration::from_micros(200));
+            if let Event::Note(note) = event {
+                sink_received.lock().unwrap().push((note.state, note.note));
+            }
+        });
+
+        // Bien plus d'événements d'analyse que la file n'en contient, autour des notes
+        let mut expected = Vec::new();
+        for number in 0..40 {
+            for _ in 0..50 {
+                bus.publish(pitch());
+            }
+            let state = if number % 2 == 0 {
+                NoteState::On
+            } else {
+                NoteState::Off
+            };
+            bus.publish(note(state, 60 + number / 2));
+            expected.push((state, 60 + number / 2));
+        }
+        let metrics = bus.metrics();
+        bus.shutdown();
+
+        assert_eq!(*received.lock().unwrap(), expected);
+        assert!(
+            metrics[0].1.snapshot().dropped > 0,
+            "la file aurait dû déborder"
+        );
+    }
+}
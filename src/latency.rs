@@ -0,0 +1,225 @@
+// src/latency.rs
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Nombre maximal de mesures conservées par étape (fenêtre glissante)
+const MAX_MEASUREMENTS: usize = 4096;
+
+/// Horodatages d'un bloc audio à chaque étape du pipeline
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Timestamps {
+    /// Entrée du son dans le micro (instant de lecture corrigé de la latence du serveur)
+    pub captured: Instant,
+    /// Prise en charge du bloc par le processeur (assemblage de la trame)
+    pub framed: Option<Instant>,
+    /// Fin de la détection de hauteur
+    pub detected: Option<Instant>,
+    /// Envoi du Note On MIDI
+    pub sent: Option<Instant>,
+}
+
+impl Timestamps {
+    pub fn new(captured: Instant) -> Self {
+        Timestamps {
+            captured,
+            framed: None,
+            detected: None,
+            sent: None,
+        }
+    }
+}
+
+/// Étapes mesurées, entre deux horodatages consécutifs
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    /// Capture → trame
+    Queue,
+    /// Trame → détection
+    Detection,
+    /// Détection → Note On
+    Output,
+    /// Capture → Note On
+    EndToEnd,
+}
+
+impl Stage {
+    pub const ALL: [Stage; 4] = [Stage::Queue, Stage::Detection, Stage::Output, Stage::EndToEnd];
+
+    fn name(self) -> &'static str {
+        match self {
+            Stage::Queue => "capture → trame",
+            Stage::Detection => "trame → détection",
+            Stage::Output => "détection → MIDI",
+            Stage::EndToEnd => "capture → MIDI",
+        }
+    }
+
+    fn measure(self, timestamps: &Timestamps) -> Option<Duration> {
+        let (from, to) = match self {
+            Stage::Queue => (Some(timestamps.captured), timestamps.framed),
+            Stage::Detection => (timestamps.framed, timestamps.detected),
+            Stage::Output => (timestamps.detected, timestamps.sent),
+            Stage::EndToEnd => (Some(timestamps.captured), timestamps.sent),
+        };
+        Some(to?.saturating_duration_since(from?))
+    }
+}
+
+/// Statistiques de latence d'une étape
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LatencySummary {
+    pub count: usize,
+    pub min: Duration,
+    pub mean: Duration,
+    pub p99: Duration,
+}
+
+impl LatencySummary {
+    /// Calcule min/moyenne/p99 sur un ensemble de mesures
+    pub fn from_measurements(measurements: &[Duration]) -> Option<Self> {
+        if measurements.is_empty() {
+            return None;
+        }
+        let mut sorted = measurements.to_vec();
+        sorted.sort();
+        let total: Duration = sorted.iter().sum();
+        let p99_index = ((sorted.len() as f64 * 0.99).ceil() as usize).clamp(1, sorted.len()) - 1;
+        Some(LatencySummary {
+            count: sorted.len(),
+            min: sorted[0],
+            mean: total / sorted.len() as u32,
+            p99: sorted[p99_index],
+        })
+    }
+}
+
+impl fmt::Display for LatencySummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {:.2} ms, moyenne {:.2} ms, p99 {:.2} ms ({} mesures)",
+            self.min.as_secs_f64() * 1000.0,
+            self.mean.as_secs_f64() * 1000.0,
+            self.p99.as_secs_f64() * 1000.0,
+            self.count
+        )
+    }
+}
+
+/// Accumule les horodatages des notes émises et produit un rapport par étape
+pub struct LatencyReport {
+    measurements: Mutex<Vec<VecDeque<Duration>>>,
+}
+
+impl Default for LatencyReport {
+    fn default() -> Self {
+        LatencyReport {
+            measurements: Mutex::new(vec![VecDeque::new(); Stage::ALL.len()]),
+        }
+    }
+}
+
+impl LatencyReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enregistre les durées de chaque étape renseignée dans `timestamps`
+    pub fn record(&self, timestamps: &Timestamps) {
+        let mut measurements = self.measurements.lock().unwrap();
+        for (stage, values) in Stage::ALL.iter().zip(measurements.iter_mut()) {
+            if let Some(duration) = stage.measure(timestamps) {
+                if values.len() == MAX_MEASUREMENTS {
+                    values.pop_front();
+                }
+                values.push_back(duration);
+            }
+        }
+    }
+
+    pub fn summary(&self, stage: Stage) -> Option<LatencySummary> {
+        let mut measurements = self.measurements.lock().unwrap();
+        let index = Stage::ALL.iter().position(|&s| s == stage)?;
+        LatencySummary::from_measurements(measurements[index].make_contiguous())
+    }
+
+    /// Journalise (niveau info) le rapport de latence de toutes les étapes mesurées
+    pub fn print(&self) {
+        log::info!("Rapport de latence :");
+        for stage in Stage::ALL {
+            if let Some(summary) = self.summary(stage) {
+                log::info!("  {:<20} {}", stage.name(), summary);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(count: u64) -> Vec<Duration> {
+        (1..=count).map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn p99_of_small_and_large_sets() {
+        assert_eq!(LatencySummary::from_measurements(&[]), None);
+
+        let one = LatencySummary::from_measurements(&millis(1)).unwrap();
+        assert_eq!(one.min, Duration::from_millis(1));
+        assert_eq!(one.p99, Duration::from_millis(1));
+
+        let hundred = LatencySummary::from_measurements(&millis(100)).unwrap();
+        assert_eq!(hundred.p99, Duration::from_millis(99));
+        assert_eq!(hundred.mean, Duration::from_micros(50_500));
+
+        // Mesures dans le désordre : le p99 de 101 mesures est la 100e
+        let mut unsorted = millis(101);
+        unsorted.reverse();
+        let summary = LatencySummary::from_measurements(&unsorted).unwrap();
+        assert_eq!(summary.count, 101);
+        assert_eq!(summary.min, Duration::from_millis(1));
+        assert_eq!(summary.p99, Duration::from_millis(100));
+    }
+
+    #[test]
+    fn stages_need_both_timestamps() {
+        let captured = Instant::now();
+        let mut timestamps = Timestamps::new(captured);
+        for stage in Stage::ALL {
+            assert_eq!(stage.measure(&timestamps), None);
+        }
+
+        timestamps.framed = Some(captured + Duration::from_millis(2));
+        timestamps.sent = Some(captured + Duration::from_millis(9));
+        assert_eq!(
+            Stage::Queue.measure(&timestamps),
+            Some(Duration::from_millis(2))
+        );
+        assert_eq!(Stage::Detection.measure(&timestamps), None);
+        assert_eq!(Stage::Output.measure(&timestamps), None);
+        assert_eq!(
+            Stage::EndToEnd.measure(&timestamps),
+            Some(Duration::from_millis(9))
+        );
+    }
+
+    #[test]
+    fn report_keeps_a_sliding_window() {
+        let report = LatencyReport::new();
+        let captured = Instant::now();
+        for delay in 0..MAX_MEASUREMENTS as u64 + 10 {
+            let mut timestamps = Timestamps::new(captured);
+            timestamps.framed = Some(captured + Duration::from_micros(delay));
+            report.record(&timestamps);
+        }
+        let queue = report.summary(Stage::Queue).unwrap();
+        assert_eq!(queue.count, MAX_MEASUREMENTS);
+        assert_eq!(queue.min, Duration::from_micros(10));
+        assert_eq!(report.summary(Stage::EndToEnd), None);
+    }
+}
@@ -0,0 +1,128 @@
+// src/leds/artnet.rs
+
+use crate::leds::backend::LedBackend;
+use crate::leds::color::Rgb;
+use crate::leds::dmx::{DmxMapping, UNIVERSE_SIZE};
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
+
+/// Port UDP standard d'Art-Net
+pub const ARTNET_PORT: u16 = 6454;
+
+/// Code d'opération ArtDmx
+const OP_DMX: u16 = 0x5000;
+
+/// Version du protocole Art-Net
+const PROTOCOL_VERSION: u16 = 14;
+
+/// Sortie Art-Net : un paquet ArtDmx par univers et par image, vers un nœud ou en broadcast
+pub struct ArtNetBackend {
+    socket: UdpSocket,
+    mapping: DmxMapping,
+    destination: SocketAddr,
+    sequence: u8,
+}
+
+impl ArtNetBackend {
+    /// `destination` à `None` : broadcast sur le réseau 2.255.255.255 d'Art-Net
+    pub fn new(mapping: DmxMapping, destination: Option<IpAddr>) -> io::Result<Self> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+        let ip = match destination {
+            Some(ip) => ip,
+            None => {
+                socket.set_broadcast(true)?;
+                IpAddr::V4(Ipv4Addr::new(2, 255, 255, 255))
+            }
+        };
+        Ok(ArtNetBackend {
+            socket,
+            mapping,
+            destination: SocketAddr::new(ip, ARTNET_PORT),
+            sequence: 0,
+        })
+    }
+
+    /// Port de destination (par exemple un récepteur de test local)
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.destination.set_port(port);
+        self
+    }
+}
+
+impl LedBackend for ArtNetBackend {
+    fn write(&mut self, pixels: &[Rgb]) -> io::Result<()> {
+        // Séquence 0 = désactivée : on boucle de 1 à 255
+        self.sequence = self.sequence.checked_add(1).unwrap_or(1);
+        for (universe, data) in self.mapping.universes(pixels) {
+            let packet = dmx_packet(self.sequence, universe, &data);
+            self.socket.send_to(&packet, self.destination)?;
+        }
+        Ok(())
+    }
+}
+
+/// Paquet ArtDmx ; `universe` est l'adresse de port sur 15 bits (net, sous-réseau, univers)
+fn dmx_packet(sequence: u8, universe: u16, data: &[u8; UNIVERSE_SIZE]) -> Vec<u8> {
+    let mut packet = Vec::with_capacity(18 + UNIVERSE_SIZE);
+    packet.extend_from_slice(b"Art-Net\0");
+    packet.extend_from_slice(&OP_DMX.to_le_bytes());
+    packet.extend_from_slice(&PROTOCOL_VERSION.to_be_bytes());
+    packet.push(sequence);
+    packet.push(0); // port physique
+    packet.push((universe & 0xff) as u8); // SubUni
+    packet.push(((universe >> 8) & 0x7f) as u8); // Net
+    packet.extend_from_slice(&(UNIVERSE_SIZE as u16).to_be_bytes());
+    packet.extend_from_slice(data);
+    packet
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn art_dmx_packets_reach_a_local_listener() {
+        let listener = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        listener
+            .set_read_timeout(Some(Duration::from_secs(2)))
+            .unwrap();
+        let port = listener.local_addr().unwrap().port();
+        // Univers 0x1ff : net 1, sous-réseau et univers 0xff ; la bande déborde sur 0x200
+        let mapping = DmxMapping::new(0x1ff, 4);
+        let mut backend = ArtNetBackend::new(mapping, Some(Ipv4Addr::LOCALHOST.into()))
+            .unwrap()
+            .with_port(port);
+
+        let pixels = vec![Rgb { r: 9, g: 8, b: 7 }; 170];
+        backend.write(&pixels).unwrap();
+        let mut packet = [0u8; 1024];
+        for (sub_uni, net) in [(0xff, 0x01), (0x00, 0x02)] {
+            let (len, _) = listener.recv_from(&mut packet).unwrap();
+            let packet = &packet[..len];
+            assert_eq!(len, 18 + UNIVERSE_SIZE);
+            assert_eq!(&packet[0..8], b"Art-Net\0");
+            assert_eq!(&packet[8..10], &[0x00, 0x50], "OpDmx en petit-boutiste");
+            assert_eq!(&packet[10..12], &[0, 14], "version en gros-boutiste");
+            assert_eq!(packet[12], 1, "séquence de la première image");
+            assert_eq!(packet[13], 0);
+            assert_eq!((packet[14], packet[15]), (sub_uni, net));
+            assert_eq!(&packet[16..18], &[0x02, 0x00]);
+        }
+        // Premier univers décalé de 3 canaux : 169 LEDs, la dernière dans le suivant
+        let first = dmx_packet(1, 0x1ff, &backend.mapping.universes(&pixels)[0].1);
+        assert!(first[18..21].iter().all(|&c| c == 0));
+        assert_eq!(&first[21..24], &[9, 8, 7]);
+    }
+
+    #[test]
+    fn sequence_skips_zero() {
+        let mut backend =
+            ArtNetBackend::new(DmxMapping::default(), Some(Ipv4Addr::LOCALHOST.into()))
+                .unwrap()
+                .with_port(9);
+        backend.sequence = 255;
+        backend.write(&[]).unwrap();
+        assert_eq!(backend.sequence, 1);
+    }
+}
@@ -0,0 +1,16 @@
+// src/leds/backend.rs
+
+use crate::leds::color::Rgb;
+use std::io;
+
+/// Matériel (ou simulation) qui affiche une bande de LEDs
+pub trait LedBackend: Send {
+    /// Envoie l'état complet de la bande, une couleur par LED
+    fn write(&mut self, pixels: &[Rgb]) -> io::Result<()>;
+}
+
+impl LedBackend for Box<dyn LedBackend> {
+    fn write(&mut self, pixels: &[Rgb]) -> io::Result<()> {
+        (**self).write(pixels)
+    }
+}
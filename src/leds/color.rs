@@ -0,0 +1,60 @@
+// src/leds/color.rs
+
+/// Couleur d'une LED, 8 bits par composante
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb { r: 0, g: 0, b: 0 };
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Rgb { r, g, b }
+    }
+
+    /// Couleur à partir de la teinte (degrés), de la saturation et de la luminosité dans [0, 1]
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32) -> Self {
+        let hue = hue.rem_euclid(360.0) / 60.0;
+        let chroma = value * saturation;
+        let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+        let (r, g, b) = match hue as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = value - chroma;
+        let to_u8 = |c: f32| ((c + m).clamp(0.0, 1.0) * 255.0).round() as u8;
+        Rgb::new(to_u8(r), to_u8(g), to_u8(b))
+    }
+
+    /// Atténue la couleur d'un facteur dans [0, 1]
+    pub fn scale(self, factor: f32) -> Self {
+        let factor = factor.clamp(0.0, 1.0);
+        let scale = |c: u8| (c as f32 * factor).round() as u8;
+        Rgb::new(scale(self.r), scale(self.g), scale(self.b))
+    }
+
+    /// Mélange avec `other` : 0 donne `self`, 1 donne `other`
+    pub fn lerp(self, other: Rgb, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+        Rgb::new(
+            mix(self.r, other.r),
+            mix(self.g, other.g),
+            mix(self.b, other.b),
+        )
+    }
+}
+
+/// Couleur d'une note : les 12 classes de hauteur font le tour du cercle chromatique
+/// (Do rouge, Mi vert-jaune, Sol# bleu...), l'octave ne change pas la teinte
+pub fn note_color(note: u8) -> Rgb {
+    let pitch_class = note % 12;
+    Rgb::from_hsv(pitch_class as f32 * 30.0, 1.0, 1.0)
+}
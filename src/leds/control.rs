@@ -1,4 +1,393 @@
-pub fn update_leds() {
-    println!("Mise à jour des LEDs en fonction des notes...");
-    // TODO: Intégrer la logique de contrôle des LEDs
+// src/leds/control.rs
+
+use crate::audio::onset::OnsetDetector;
+use crate::events::{ChromaEvent, Event, EventSink, NoteState, SpectrumEvent};
+use crate::leds::backend::LedBackend;
+use crate::leds::color::{note_color, Rgb};
+use crate::leds::palette::Palette;
+use crate::leds::spectrum::SpectrumBands;
+use std::io;
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Intensité en dessous de laquelle une LED est considérée éteinte
+const OFF_THRESHOLD: f32 = 1.0 / 255.0;
+
+/// Part maximale de blanc mélangée à la bande lors d'un flash d'attaque
+const FLASH_MIX: f32 = 0.6;
+
+/// Luminosité entre deux battements quand la bande pulse sur le tempo
+const PULSE_FLOOR: f32 = 0.4;
+
+/// Ce que la bande affiche
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LedMode {
+    /// Une LED par note détectée, couleur selon la classe de hauteur
+    Notes,
+    /// Analyseur de spectre : une bande de fréquences (espacement logarithmique) par LED
+    Spectrum,
+    /// 12 segments, un par classe de hauteur, allumés selon le chromagramme
+    Chroma,
+}
+
+impl LedMode {
+    /// Nom utilisé en ligne de commande : `notes`, `spectrum` ou `chroma`
+    pub fn parse(name: &str) -> Option<LedMode> {
+        match name {
+            "notes" => Some(LedMode::Notes),
+            "spectrum" => Some(LedMode::Spectrum),
+            "chroma" => Some(LedMode::Chroma),
+            _ => None,
+        }
+    }
+}
+
+/// Réglages de la bande de LEDs
+#[derive(Debug, Clone)]
+pub struct LedConfig {
+    pub led_count: usize,
+    /// Notes réparties sur la longueur de la bande (piano par défaut)
+    pub note_range: RangeInclusive<u8>,
+    /// Constante de temps de l'extinction après le Note Off
+    pub decay: Duration,
+    /// Images par seconde envoyées au matériel
+    pub frame_rate: u32,
+    /// Luminosité globale dans [0, 1]
+    pub brightness: f32,
+    pub mode: LedMode,
+    /// Couleurs du mode spectre, des graves aux aigus
+    pub palette: Palette,
+    /// Lissage de la retombée des modes spectre et chroma : 0 sans lissage, proche de 1 très lent
+    pub smoothing: f32,
+    /// Fréquences couvertes par le mode spectre
+    pub min_frequency: f32,
+    pub max_frequency: f32,
+    /// Flash blanc sur chaque attaque détectée, quel que soit le mode
+    pub onset_flash: bool,
+    /// Trames d'analyse par seconde (fréquence d'échantillonnage / pas du framer), qui
+    /// règle la moyenne récente du détecteur d'attaques
+    pub analysis_rate: f32,
+    /// Luminosité qui pulse sur chaque battement détecté
+    pub beat_pulse: bool,
+}
+
+impl Default for LedConfig {
+    fn default() -> Self {
+        LedConfig {
+            led_count: 60,
+            note_range: 21..=108,
+            decay: Duration::from_millis(300),
+            frame_rate: 60,
+            brightness: 0.5,
+            mode: LedMode::Notes,
+            palette: Palette::default(),
+            smoothing: 0.8,
+            min_frequency: 40.0,
+            max_frequency: 8000.0,
+            onset_flash: false,
+            analysis_rate: 44100.0 / 1024.0,
+            beat_pulse: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Led {
+    color: Rgb,
+    intensity: f32,
+    /// Notes tenues sur cette LED (des notes voisines peuvent la partager) : pas
+    /// d'extinction tant que chacune n'a pas reçu son Note Off
+    held: u32,
+}
+
+/// État de la bande partagé entre l'abonné et le thread d'animation
+struct Strip {
+    config: LedConfig,
+    leds: Vec<Led>,
+    /// Niveaux lissés des modes spectre et chroma, un par LED
+    levels: Vec<f32>,
+    bands: SpectrumBands,
+    onsets: OnsetDetector,
+    flash: f32,
+    /// Impulsion du dernier battement, 1 au battement puis décroissante
+    pulse: f32,
+}
+
+impl Strip {
+    fn new(config: LedConfig) -> Self {
+        Strip {
+            leds: vec![Led::default(); config.led_count],
+            levels: vec![0.0; config.led_count],
+            bands: SpectrumBands::new(config.min_frequency, config.max_frequency),
+            onsets: OnsetDetector::new().with_frame_rate(config.analysis_rate),
+            flash: 0.0,
+            pulse: 0.0,
+            config,
+        }
+    }
+
+    /// LED associée à une note, ou `None` hors de la plage configurée
+    fn index(&self, note: u8) -> Option<usize> {
+        let (low, high) = (
+            *self.config.note_range.start(),
+            *self.config.note_range.end(),
+        );
+        if !self.config.note_range.contains(&note) {
+            return None;
+        }
+        let span = (high - low) as usize + 1;
+        Some((note - low) as usize * self.leds.len() / span)
+    }
+
+    fn note_on(&mut self, note: u8, velocity: u8) {
+        if let Some(index) = self.index(note) {
+            let led = &mut self.leds[index];
+            led.color = note_color(note);
+            led.intensity = velocity as f32 / 127.0;
+            led.held += 1;
+        }
+    }
+
+    fn note_off(&mut self, note: u8) {
+        if let Some(index) = self.index(note) {
+            let led = &mut self.leds[index];
+            led.held = led.held.saturating_sub(1);
+        }
+    }
+
+    fn spectrum(&mut self, spectrum: &SpectrumEvent) {
+        if self.config.onset_flash && self.onsets.process(&spectrum.magnitudes) {
+            self.flash = 1.0;
+        }
+        if self.config.mode == LedMode::Spectrum {
+            let count = self.levels.len();
+            let targets = self.bands.levels(
+                &spectrum.magnitudes,
+                spectrum.bin_hz,
+                spectrum.window_gain,
+                count,
+            );
+            self.smooth(targets);
+        }
+    }
+
+    fn chroma(&mut self, chroma: &ChromaEvent) {
+        if self.config.mode == LedMode::Chroma {
+            // Le chromagramme est normalisé : le niveau de la trame l'éteint dans le silence
+            let loudness = ((chroma.level_db + 60.0) / 60.0).clamp(0.0, 1.0);
+            let count = self.levels.len();
+            let targets = (0..count)
+                .map(|i| chroma.chroma[i * 12 / count] * loudness)
+                .collect();
+            self.smooth(targets);
+        }
+    }
+
+    /// Montée immédiate, retombée lissée
+    fn smooth(&mut self, targets: Vec<f32>) {
+        let smoothing = self.config.smoothing.clamp(0.0, 0.99);
+        for (level, target) in self.levels.iter_mut().zip(targets) {
+            *level = if target > *level {
+                target
+            } else {
+                target + (*level - target) * smoothing
+            };
+        }
+    }
+
+    fn beat(&mut self) {
+        self.pulse = 1.0;
+    }
+
+    /// Décroissance exponentielle des LEDs relâchées, du flash et de l'impulsion
+    fn advance(&mut self, elapsed: Duration) {
+        let factor = (-elapsed.as_secs_f32() / self.config.decay.as_secs_f32().max(1e-3)).exp();
+        for led in self.leds.iter_mut().filter(|led| led.held == 0) {
+            led.intensity *= factor;
+            if led.intensity < OFF_THRESHOLD {
+                led.intensity = 0.0;
+            }
+        }
+        self.flash *= factor;
+        self.pulse *= factor;
+    }
+
+    fn pixels(&self) -> Vec<Rgb> {
+        let count = self.leds.len();
+        let brightness = if self.config.beat_pulse {
+            self.config.brightness * (PULSE_FLOOR + (1.0 - PULSE_FLOOR) * self.pulse)
+        } else {
+            self.config.brightness
+        };
+        (0..count)
+            .map(|i| {
+                let pixel = match self.config.mode {
+                    LedMode::Notes => self.leds[i].color.scale(self.leds[i].intensity),
+                    LedMode::Spectrum => {
+                        let position = i as f32 / (count.max(2) - 1) as f32;
+                        self.config.palette.sample(position).scale(self.levels[i])
+                    }
+                    LedMode::Chroma => note_color((i * 12 / count) as u8).scale(self.levels[i]),
+                };
+                let white = Rgb::new(255, 255, 255);
+                pixel.lerp(white, self.flash * FLASH_MIX).scale(brightness)
+            })
+            .collect()
+    }
+}
+
+/// Abonné au bus qui anime la bande selon le mode : une LED par note (couleur selon la
+/// classe de hauteur, intensité selon la vélocité, extinction progressive au Note Off),
+/// analyseur de spectre ou chromagramme, avec flash optionnel sur les attaques et
+/// pulsation optionnelle sur les battements
+pub struct LedController {
+    strip: Arc<Mutex<Strip>>,
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl LedController {
+    /// Démarre le thread d'animation qui rafraîchit `backend` à `config.frame_rate`
+    pub fn new<B: LedBackend + 'static>(config: LedConfig, mut backend: B) -> io::Result<Self> {
+        let period = Duration::from_secs_f32(1.0 / config.frame_rate.max(1) as f32);
+        let strip = Arc::new(Mutex::new(Strip::new(config)));
+        let running = Arc::new(AtomicBool::new(true));
+
+        let thread_strip = Arc::clone(&strip);
+        let thread_running = Arc::clone(&running);
+        let thread = thread::Builder::new()
+            .name("leds".to_string())
+            .spawn(move || {
+                let mut last = Instant::now();
+                while thread_running.load(Ordering::Relaxed) {
+                    thread::sleep(period);
+                    let now = Instant::now();
+                    let pixels = {
+                        let mut strip = thread_strip.lock().unwrap();
+                        strip.advance(now - last);
+                        strip.pixels()
+                    };
+                    last = now;
+                    if let Err(err) = backend.write(&pixels) {
+                        log::error!("Erreur d'écriture sur les LEDs : {}", err);
+                        return;
+                    }
+                }
+                // Éteindre la bande en partant
+                let count = thread_strip.lock().unwrap().leds.len();
+                let _ = backend.write(&vec![Rgb::BLACK; count]);
+            })?;
+
+        Ok(LedController {
+            strip,
+            running,
+            thread: Some(thread),
+        })
+    }
+
+    /// Arrête l'animation et éteint la bande
+    pub fn stop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            if thread.join().is_err() {
+                log::error!("Le thread des LEDs a paniqué");
+            }
+        }
+    }
+}
+
+impl EventSink for LedController {
+    fn handle(&mut self, event: &Event) {
+        let mut strip = self.strip.lock().unwrap();
+        match event {
+            Event::Note(note) => match note.state {
+                NoteState::On => strip.note_on(note.note, note.velocity),
+                NoteState::Off => strip.note_off(note.note),
+            },
+            Event::Spectrum(spectrum) => strip.spectrum(spectrum),
+            Event::Chroma(chroma) => strip.chroma(chroma),
+            Event::Beat(_) => strip.beat(),
+            Event::Pitch(_) | Event::Key(_) | Event::Chord(_) => {}
+        }
+    }
+
+    fn finish(&mut self) {
+        self.stop();
+    }
+}
+
+impl Drop for LedController {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strip(led_count: usize) -> Strip {
+        Strip::new(LedConfig {
+            led_count,
+            ..LedConfig::default()
+        })
+    }
+
+    #[test]
+    fn notes_spread_over_the_strip() {
+        let strip = strip(60);
+        assert_eq!(strip.index(20), None);
+        assert_eq!(strip.index(21), Some(0));
+        assert_eq!(strip.index(60), Some(26));
+        assert_eq!(strip.index(108), Some(59));
+        assert_eq!(strip.index(109), None);
+
+        // Une LED par touche de piano
+        let keys = self::strip(88);
+        assert_eq!(keys.index(21), Some(0));
+        assert_eq!(keys.index(22), Some(1));
+        assert_eq!(keys.index(108), Some(87));
+    }
+
+    #[test]
+    fn released_note_decays_with_the_configured_time_constant() {
+        let mut strip = strip(88);
+        strip.note_on(60, 127);
+        let index = strip.index(60).unwrap();
+        strip.advance(Duration::from_secs(1));
+        assert_eq!(strip.leds[index].intensity, 1.0);
+
+        strip.note_off(60);
+        strip.advance(strip.config.decay);
+        let intensity = strip.leds[index].intensity;
+        assert!((intensity - (-1f32).exp()).abs() < 1e-4, "{}", intensity);
+        strip.advance(Duration::from_secs(10));
+        assert_eq!(strip.leds[index].intensity, 0.0);
+    }
+
+    #[test]
+    fn shared_led_stays_lit_until_every_note_is_released() {
+        // 44 LEDs pour 88 notes : La0 et La#0 partagent la première
+        let mut strip = strip(44);
+        assert_eq!(strip.index(21), strip.index(22));
+        strip.note_on(21, 127);
+        strip.note_on(22, 127);
+        strip.note_off(21);
+        strip.advance(Duration::from_secs(1));
+        assert_eq!(strip.leds[0].intensity, 1.0);
+        assert_eq!(strip.leds[0].color, note_color(22));
+
+        strip.note_off(22);
+        strip.advance(Duration::from_secs(1));
+        assert!(strip.leds[0].intensity < 1.0);
+        // Un Note Off en trop ne bloque pas la LED suivante
+        strip.note_off(22);
+        strip.note_on(21, 127);
+        strip.note_off(21);
+        strip.advance(Duration::from_secs(1));
+        assert!(strip.leds[0].intensity < 1.0);
+    }
 }
@@ -0,0 +1,217 @@
+// src/leds/dmx.rs
+
+use crate::leds::color::Rgb;
+use std::ops::RangeInclusive;
+
+/// Nombre de canaux d'un univers DMX512
+pub const UNIVERSE_SIZE: usize = 512;
+
+/// Ordre des composantes d'une LED dans ses canaux DMX
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelOrder {
+    Rgb,
+    Grb,
+    Bgr,
+}
+
+impl ChannelOrder {
+    /// Nom utilisé dans la configuration : `rgb`, `grb` ou `bgr`
+    pub fn parse(name: &str) -> Option<ChannelOrder> {
+        match name {
+            "rgb" => Some(ChannelOrder::Rgb),
+            "grb" => Some(ChannelOrder::Grb),
+            "bgr" => Some(ChannelOrder::Bgr),
+            _ => None,
+        }
+    }
+}
+
+/// Protocole réseau des nœuds DMX
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DmxProtocol {
+    /// E1.31 (sACN)
+    Sacn,
+    ArtNet,
+}
+
+impl DmxProtocol {
+    /// Nom utilisé dans `--leds` et `leds.output` : `sacn` ou `artnet`
+    pub fn parse(name: &str) -> Option<DmxProtocol> {
+        match name {
+            "sacn" => Some(DmxProtocol::Sacn),
+            "artnet" => Some(DmxProtocol::ArtNet),
+            _ => None,
+        }
+    }
+
+    /// Univers valides : 1 à 63999 en E1.31, 0 à 32767 (net, sous-réseau, univers) en Art-Net ;
+    /// le premier sert d'univers de départ par défaut
+    pub fn universes(self) -> RangeInclusive<u16> {
+        match self {
+            DmxProtocol::Sacn => 1..=63999,
+            DmxProtocol::ArtNet => 0..=0x7fff,
+        }
+    }
+}
+
+/// Répartition des LEDs sur les univers et canaux DMX d'un nœud réseau
+#[derive(Debug, Clone)]
+pub struct DmxMapping {
+    /// Univers de la première LED
+    pub start_universe: u16,
+    /// Canal (à partir de 1) de la première LED dans le premier univers
+    pub start_channel: u16,
+    /// LEDs par univers ; une LED n'est jamais coupée entre deux univers
+    pub leds_per_universe: usize,
+    pub order: ChannelOrder,
+}
+
+impl Default for DmxMapping {
+    fn default() -> Self {
+        DmxMapping {
+            start_universe: 1,
+            start_channel: 1,
+            leds_per_universe: 170,
+            order: ChannelOrder::Rgb,
+        }
+    }
+}
+
+impl DmxMapping {
+    pub fn new(start_universe: u16, start_channel: u16) -> Self {
+        DmxMapping {
+            start_universe,
+            start_channel,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_leds_per_universe(mut self, leds_per_universe: usize) -> Self {
+        self.leds_per_universe = leds_per_universe;
+        self
+    }
+
+    pub fn with_order(mut self, order: ChannelOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Canal de départ (à partir de 0) dans le premier univers, LEDs du premier univers
+    /// et LEDs des suivants
+    fn capacities(&self) -> (usize, usize, usize) {
+        let offset = self.start_channel.clamp(1, UNIVERSE_SIZE as u16) as usize - 1;
+        let first_capacity = ((UNIVERSE_SIZE - offset) / 3).min(self.leds_per_universe);
+        let capacity = (UNIVERSE_SIZE / 3).min(self.leds_per_universe).max(1);
+        (offset, first_capacity, capacity)
+    }
+
+    /// Nombre d'univers occupés par une bande de `leds` LEDs (au moins un)
+    pub fn universe_count(&self, leds: usize) -> usize {
+        let (_, first_capacity, capacity) = self.capacities();
+        1 + leds.saturating_sub(first_capacity).div_ceil(capacity)
+    }
+
+    /// Découpe la bande en univers : `(numéro d'univers, 512 canaux)`.
+    /// Seul le premier univers est décalé de `start_channel`, les suivants commencent au canal 1 ;
+    /// le dernier univers doit rester dans la plage du protocole (voir `universe_count`)
+    pub fn universes(&self, pixels: &[Rgb]) -> Vec<(u16, [u8; UNIVERSE_SIZE])> {
+        let (offset, first_capacity, capacity) = self.capacities();
+
+        let mut universes = Vec::new();
+        let mut remaining = pixels;
+        let mut universe = self.start_universe;
+        let mut start = offset;
+        let mut count = first_capacity;
+        while !remaining.is_empty() || universes.is_empty() {
+            let (chunk, rest) = remaining.split_at(count.min(remaining.len()));
+            let mut data = [0u8; UNIVERSE_SIZE];
+            for (i, pixel) in chunk.iter().enumerate() {
+                let channels = match self.order {
+                    ChannelOrder::Rgb => [pixel.r, pixel.g, pixel.b],
+                    ChannelOrder::Grb => [pixel.g, pixel.r, pixel.b],
+                    ChannelOrder::Bgr => [pixel.b, pixel.g, pixel.r],
+                };
+                data[start + i * 3..start + i * 3 + 3].copy_from_slice(&channels);
+            }
+            universes.push((universe, data));
+            remaining = rest;
+            universe = universe.wrapping_add(1);
+            start = 0;
+            count = capacity;
+        }
+        universes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strip(count: usize) -> Vec<Rgb> {
+        (0..count)
+            .map(|i| Rgb {
+                r: i as u8,
+                g: 100,
+                b: 200,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn offset_start_channel_splits_across_universes() {
+        let mapping = DmxMapping::new(3, 10);
+        let universes = mapping.universes(&strip(200));
+
+        // (512 - 9) / 3 = 167 LEDs dans le premier univers, à partir du canal 10
+        assert_eq!(universes.len(), 2);
+        let (first, data) = &universes[0];
+        assert_eq!(*first, 3);
+        assert!(data[..9].iter().all(|&c| c == 0));
+        assert_eq!(&data[9..12], &[0, 100, 200]);
+        assert_eq!(&data[9 + 166 * 3..9 + 167 * 3], &[166, 100, 200]);
+        assert!(data[9 + 167 * 3..].iter().all(|&c| c == 0));
+
+        // Les 33 suivantes au canal 1 de l'univers suivant
+        let (second, data) = &universes[1];
+        assert_eq!(*second, 4);
+        assert_eq!(&data[..3], &[167, 100, 200]);
+        assert_eq!(&data[32 * 3..33 * 3], &[199, 100, 200]);
+        assert!(data[33 * 3..].iter().all(|&c| c == 0));
+    }
+
+    #[test]
+    fn leds_per_universe_and_channel_order() {
+        let mapping = DmxMapping::new(0, 1)
+            .with_leds_per_universe(2)
+            .with_order(ChannelOrder::Grb);
+        let universes = mapping.universes(&strip(5));
+        let numbers: Vec<u16> = universes.iter().map(|(u, _)| *u).collect();
+        assert_eq!(numbers, vec![0, 1, 2]);
+        assert_eq!(&universes[0].1[..6], &[100, 0, 200, 100, 1, 200]);
+        assert_eq!(&universes[2].1[..6], &[100, 4, 200, 0, 0, 0]);
+    }
+
+    #[test]
+    fn universe_count_matches_the_split() {
+        let offset = DmxMapping::new(3, 10);
+        for leds in [0, 1, 167, 168, 200, 337, 338, 1000] {
+            assert_eq!(
+                offset.universe_count(leds),
+                offset.universes(&strip(leds)).len(),
+                "{} LEDs",
+                leds
+            );
+        }
+        assert_eq!(offset.universe_count(167), 1);
+        assert_eq!(offset.universe_count(168), 2);
+        let small = DmxMapping::default().with_leds_per_universe(2);
+        assert_eq!(small.universe_count(5), 3);
+    }
+
+    #[test]
+    fn empty_strip_still_sends_one_universe() {
+        let universes = DmxMapping::default().universes(&[]);
+        assert_eq!(universes.len(), 1);
+        assert_eq!(universes[0].0, 1);
+    }
+}
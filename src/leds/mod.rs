@@ -1,7 +1,25 @@
+// src/leds/mod.rs
+
+//! Sortie lumineuse : une bande de LEDs pilotée par les notes ou le spectre, en direct
+//! (WS2812 sur SPI) ou via des nœuds DMX sur le réseau (sACN, Art-Net)
+
+pub mod artnet;
+pub mod backend;
+pub mod color;
 pub mod control;
+pub mod dmx;
+pub mod palette;
+pub mod sacn;
+pub mod spectrum;
+pub mod terminal;
+pub mod ws2812;
 
-pub fn control_leds() {
-    println!("Contrôle des LEDs en fonction des notes...");
-    // Appel des fonctions du module `control`
-    control::update_leds();
-}
+pub use artnet::ArtNetBackend;
+pub use backend::LedBackend;
+pub use color::Rgb;
+pub use control::{LedConfig, LedController, LedMode};
+pub use dmx::{ChannelOrder, DmxMapping, DmxProtocol};
+pub use palette::Palette;
+pub use sacn::SacnBackend;
+pub use terminal::TerminalBackend;
+pub use ws2812::Ws2812Backend;
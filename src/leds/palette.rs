@@ -0,0 +1,71 @@
+// src/leds/palette.rs
+
+use crate::leds::color::Rgb;
+
+/// Dégradé de couleurs échantillonné sur [0, 1], interpolé linéairement entre ses étapes
+#[derive(Debug, Clone, PartialEq)]
+pub struct Palette {
+    stops: Vec<Rgb>,
+}
+
+impl Palette {
+    /// Palette à partir de ses couleurs, de 0 à 1 à intervalles réguliers
+    pub fn new(stops: Vec<Rgb>) -> Self {
+        assert!(!stops.is_empty(), "palette : au moins une couleur");
+        Palette { stops }
+    }
+
+    /// Arc-en-ciel du rouge (graves) au violet (aigus)
+    pub fn rainbow() -> Self {
+        Palette::new(
+            (0..6)
+                .map(|i| Rgb::from_hsv(i as f32 * 54.0, 1.0, 1.0))
+                .collect(),
+        )
+    }
+
+    /// Rouge sombre, orange, jaune puis blanc
+    pub fn fire() -> Self {
+        Palette::new(vec![
+            Rgb::new(64, 0, 0),
+            Rgb::new(255, 32, 0),
+            Rgb::new(255, 160, 0),
+            Rgb::new(255, 255, 160),
+        ])
+    }
+
+    /// Bleu profond, cyan puis blanc
+    pub fn ocean() -> Self {
+        Palette::new(vec![
+            Rgb::new(0, 0, 96),
+            Rgb::new(0, 96, 255),
+            Rgb::new(0, 255, 200),
+            Rgb::new(200, 255, 255),
+        ])
+    }
+
+    /// Palette prédéfinie d'après son nom : `rainbow`, `fire` ou `ocean`
+    pub fn parse(name: &str) -> Option<Palette> {
+        match name {
+            "rainbow" => Some(Palette::rainbow()),
+            "fire" => Some(Palette::fire()),
+            "ocean" => Some(Palette::ocean()),
+            _ => None,
+        }
+    }
+
+    /// Couleur à la position `t` dans [0, 1]
+    pub fn sample(&self, t: f32) -> Rgb {
+        let last = self.stops.len() - 1;
+        let position = t.clamp(0.0, 1.0) * last as f32;
+        let index = (position.floor() as usize).min(last);
+        let next = (index + 1).min(last);
+        self.stops[index].lerp(self.stops[next], position - index as f32)
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette::rainbow()
+    }
+}
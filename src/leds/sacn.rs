@@ -0,0 +1,220 @@
+// src/leds/sacn.rs
+
+use crate::leds::backend::LedBackend;
+use crate::leds::color::Rgb;
+use crate::leds::dmx::{DmxMapping, UNIVERSE_SIZE};
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
+
+/// Port UDP standard d'E1.31
+pub const SACN_PORT: u16 = 5568;
+
+/// Taille d'un paquet de données E1.31 avec 512 canaux
+const PACKET_SIZE: usize = 638;
+
+/// Identifiant ACN en tête de chaque paquet
+const ACN_PACKET_IDENTIFIER: &[u8; 12] = b"ASC-E1.17\0\0\0";
+
+/// Priorité par défaut d'une source E1.31
+const DEFAULT_PRIORITY: u8 = 100;
+
+/// Sortie streaming ACN (E1.31) : un paquet par univers et par image, en multicast
+/// (239.255.<univers>) ou vers l'adresse d'un nœud
+pub struct SacnBackend {
+    socket: UdpSocket,
+    mapping: DmxMapping,
+    destination: Option<IpAddr>,
+    port: u16,
+    cid: [u8; 16],
+    source_name: String,
+    priority: u8,
+    sequences: HashMap<u16, u8>,
+}
+
+impl SacnBackend {
+    /// `destination` à `None` : multicast sur le groupe de chaque univers
+    pub fn new(mapping: DmxMapping, destination: Option<IpAddr>) -> io::Result<Self> {
+        Ok(SacnBackend {
+            socket: UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?,
+            mapping,
+            destination,
+            port: SACN_PORT,
+            cid: random_cid(),
+            source_name: "real_time_audio_signal_processing".to_string(),
+            priority: DEFAULT_PRIORITY,
+            sequences: HashMap::new(),
+        })
+    }
+
+    /// Port de destination (par exemple un récepteur de test local)
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    pub fn with_source_name(mut self, name: &str) -> Self {
+        self.source_name = name.to_string();
+        self
+    }
+
+    /// Priorité de la source (0 à 200), le récepteur suit la source la plus prioritaire
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority.min(200);
+        self
+    }
+
+    fn target(&self, universe: u16) -> SocketAddr {
+        let ip = self.destination.unwrap_or_else(|| {
+            let [high, low] = universe.to_be_bytes();
+            IpAddr::V4(Ipv4Addr::new(239, 255, high, low))
+        });
+        SocketAddr::new(ip, self.port)
+    }
+}
+
+impl LedBackend for SacnBackend {
+    fn write(&mut self, pixels: &[Rgb]) -> io::Result<()> {
+        for (universe, data) in self.mapping.universes(pixels) {
+            let sequence = self.sequences.entry(universe).or_insert(0);
+            *sequence = sequence.wrapping_add(1);
+            let packet = data_packet(
+                &self.cid,
+                &self.source_name,
+                self.priority,
+                *sequence,
+                universe,
+                &data,
+            );
+            self.socket.send_to(&packet, self.target(universe))?;
+        }
+        Ok(())
+    }
+}
+
+/// Paquet de données E1.31 : couches racine, framing et DMP
+fn data_packet(
+    cid: &[u8; 16],
+    source_name: &str,
+    priority: u8,
+    sequence: u8,
+    universe: u16,
+    data: &[u8; UNIVERSE_SIZE],
+) -> Vec<u8> {
+    let mut packet = Vec::with_capacity(PACKET_SIZE);
+
+    // Couche racine
+    packet.extend_from_slice(&0x0010u16.to_be_bytes()); // taille du préambule
+    packet.extend_from_slice(&0x0000u16.to_be_bytes()); // taille du postambule
+    packet.extend_from_slice(ACN_PACKET_IDENTIFIER);
+    packet.extend_from_slice(&flags_and_length(PACKET_SIZE - 16));
+    packet.extend_from_slice(&0x0000_0004u32.to_be_bytes()); // VECTOR_ROOT_E131_DATA
+    packet.extend_from_slice(cid);
+
+    // Couche framing
+    packet.extend_from_slice(&flags_and_length(PACKET_SIZE - 38));
+    packet.extend_from_slice(&0x0000_0002u32.to_be_bytes()); // VECTOR_E131_DATA_PACKET
+    let mut name = [0u8; 64];
+    let bytes = source_name.as_bytes();
+    let len = bytes.len().min(63);
+    name[..len].copy_from_slice(&bytes[..len]);
+    packet.extend_from_slice(&name);
+    packet.push(priority);
+    packet.extend_from_slice(&0u16.to_be_bytes()); // adresse de synchronisation
+    packet.push(sequence);
+    packet.push(0); // options
+    packet.extend_from_slice(&universe.to_be_bytes());
+
+    // Couche DMP
+    packet.extend_from_slice(&flags_and_length(PACKET_SIZE - 115));
+    packet.push(0x02); // VECTOR_DMP_SET_PROPERTY
+    packet.push(0xa1); // type d'adresse et de données
+    packet.extend_from_slice(&0u16.to_be_bytes()); // première adresse
+    packet.extend_from_slice(&1u16.to_be_bytes()); // incrément d'adresse
+    packet.extend_from_slice(&(UNIVERSE_SIZE as u16 + 1).to_be_bytes());
+    packet.push(0); // code de départ DMX
+    packet.extend_from_slice(data);
+    packet
+}
+
+/// Champ « flags et longueur » d'une couche ACN : 0x7 sur 4 bits puis la longueur sur 12 bits
+fn flags_and_length(length: usize) -> [u8; 2] {
+    (0x7000 | (length as u16 & 0x0fff)).to_be_bytes()
+}
+
+/// Identifiant de composant (CID) aléatoire, propre à cette instance
+fn random_cid() -> [u8; 16] {
+    let mut cid = [0u8; 16];
+    for half in cid.chunks_mut(8) {
+        let value = RandomState::new().build_hasher().finish();
+        half.copy_from_slice(&value.to_be_bytes());
+    }
+    // Format UUID version 4
+    cid[6] = (cid[6] & 0x0f) | 0x40;
+    cid[8] = (cid[8] & 0x3f) | 0x80;
+    cid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn packets_reach_a_local_listener() {
+        let listener = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        listener
+            .set_read_timeout(Some(Duration::from_secs(2)))
+            .unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let mut backend = SacnBackend::new(DmxMapping::new(7, 1), Some(Ipv4Addr::LOCALHOST.into()))
+            .unwrap()
+            .with_port(port)
+            .with_source_name("essai")
+            .with_priority(150);
+
+        let pixels = vec![Rgb { r: 1, g: 2, b: 3 }; 171];
+        backend.write(&pixels).unwrap();
+        let mut packet = [0u8; 1024];
+        for (universe, pixels_in_universe) in [(7u16, 170), (8, 1)] {
+            let (len, _) = listener.recv_from(&mut packet).unwrap();
+            let packet = &packet[..len];
+            assert_eq!(len, PACKET_SIZE);
+
+            // Couche racine
+            assert_eq!(&packet[0..4], &[0x00, 0x10, 0x00, 0x00]);
+            assert_eq!(&packet[4..16], ACN_PACKET_IDENTIFIER);
+            assert_eq!(&packet[16..18], &flags_and_length(622));
+            assert_eq!(&packet[18..22], &[0, 0, 0, 4]);
+            assert_eq!(&packet[22..38], &backend.cid);
+
+            // Couche framing
+            assert_eq!(&packet[38..40], &flags_and_length(600));
+            assert_eq!(&packet[40..44], &[0, 0, 0, 2]);
+            assert_eq!(&packet[44..50], b"essai\0");
+            assert_eq!(packet[108], 150);
+            assert_eq!(packet[111], 1, "première image de l'univers");
+            assert_eq!(&packet[113..115], &universe.to_be_bytes());
+
+            // Couche DMP
+            assert_eq!(&packet[115..117], &flags_and_length(523));
+            assert_eq!(&packet[117..119], &[0x02, 0xa1]);
+            assert_eq!(&packet[119..125], &[0, 0, 0, 1, 0x02, 0x01]);
+            assert_eq!(packet[125], 0, "code de départ DMX");
+            let data = &packet[126..];
+            assert_eq!(&data[..3], &[1, 2, 3]);
+            assert_eq!(
+                &data[(pixels_in_universe - 1) * 3..pixels_in_universe * 3],
+                &[1, 2, 3]
+            );
+            assert!(data[pixels_in_universe * 3..].iter().all(|&c| c == 0));
+        }
+    }
+
+    #[test]
+    fn flags_and_length_keeps_twelve_bits() {
+        assert_eq!(flags_and_length(622), [0x72, 0x6e]);
+    }
+}
@@ -0,0 +1,100 @@
+// src/leds/spectrum.rs
+
+/// Niveau (dBFS) correspondant à une LED éteinte
+const FLOOR_DB: f32 = -60.0;
+
+/// Regroupe le spectre d'amplitude en bandes espacées logarithmiquement,
+/// une par LED, avec un niveau normalisé dans [0, 1]
+#[derive(Debug, Clone)]
+pub struct SpectrumBands {
+    min_frequency: f32,
+    max_frequency: f32,
+}
+
+impl SpectrumBands {
+    pub fn new(min_frequency: f32, max_frequency: f32) -> Self {
+        assert!(
+            0.0 < min_frequency && min_frequency < max_frequency,
+            "bandes : plage de fréquences invalide"
+        );
+        SpectrumBands {
+            min_frequency,
+            max_frequency,
+        }
+    }
+
+    /// Niveau de chacune des `count` bandes : amplitude du bin le plus fort de la bande,
+    /// corrigée du gain cohérent de la fenêtre, convertie en dBFS puis ramenée
+    /// de [-60 dB, 0 dB] à [0, 1]
+    pub fn levels(
+        &self,
+        magnitudes: &[f32],
+        bin_hz: f32,
+        window_gain: f32,
+        count: usize,
+    ) -> Vec<f32> {
+        if magnitudes.len() < 2 || count == 0 {
+            return vec![0.0; count];
+        }
+        let fft_len = (magnitudes.len() - 1) * 2;
+        let scale = 2.0 / (fft_len as f32 * window_gain);
+        let ratio = self.max_frequency / self.min_frequency;
+        let edge = |i: usize| self.min_frequency * ratio.powf(i as f32 / count as f32);
+
+        (0..count)
+            .map(|band| {
+                let low = ((edge(band) / bin_hz).floor() as usize).clamp(1, magnitudes.len() - 1);
+                let high =
+                    ((edge(band + 1) / bin_hz).ceil() as usize).clamp(low + 1, magnitudes.len());
+                let peak = magnitudes[low..high].iter().cloned().fold(0.0, f32::max) * scale;
+                let db = 20.0 * peak.max(1e-10).log10();
+                ((db - FLOOR_DB) / -FLOOR_DB).clamp(0.0, 1.0)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::analysis::SpectrumAnalyzer;
+    use crate::audio::window::Window;
+    use std::f32::consts::PI;
+
+    /// Niveau de la bande la plus forte pour une sinusoïde pleine échelle
+    fn full_scale_level(window: Window, window_gain: f32) -> f32 {
+        let (len, sample_rate) = (2048, 48000.0);
+        let bin_hz = sample_rate / len as f32;
+        // Fréquence au centre d'un bin : le pic n'est pas partagé entre deux bins
+        let frequency = 40.0 * bin_hz;
+        let frame: Vec<f32> = (0..len)
+            .map(|n| (2.0 * PI * frequency * n as f32 / sample_rate).sin())
+            .collect();
+        let mut analyzer = SpectrumAnalyzer::new().with_window(window);
+        let magnitudes = analyzer.analyze(&frame);
+        SpectrumBands::new(40.0, 8000.0)
+            .levels(magnitudes, bin_hz, window_gain, 16)
+            .into_iter()
+            .fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn full_scale_sine_reaches_the_top_with_any_window() {
+        for window in [
+            Window::Hamming,
+            Window::Hann,
+            Window::BlackmanHarris,
+            Window::FlatTop,
+        ] {
+            let level = full_scale_level(window, window.coherent_gain());
+            assert!(level > 0.99, "{:?} : {}", window, level);
+        }
+    }
+
+    #[test]
+    fn hamming_gain_underestimates_a_flat_top_spectrum() {
+        // L'ancien gain fixe de Hamming faisait perdre 8 dB avec la fenêtre à sommet plat
+        let level = full_scale_level(Window::FlatTop, Window::Hamming.coherent_gain());
+        assert!(level < 0.9, "{}", level);
+    }
+}
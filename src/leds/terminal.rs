@@ -0,0 +1,37 @@
+// src/leds/terminal.rs
+
+use crate::leds::backend::LedBackend;
+use crate::leds::color::Rgb;
+use std::io::{self, Write};
+
+/// Bande simulée : une ligne de blocs en couleurs ANSI 24 bits, redessinée sur place
+pub struct TerminalBackend<W: Write + Send> {
+    output: W,
+}
+
+impl TerminalBackend<io::Stdout> {
+    pub fn stdout() -> Self {
+        TerminalBackend {
+            output: io::stdout(),
+        }
+    }
+}
+
+impl<W: Write + Send> TerminalBackend<W> {
+    pub fn new(output: W) -> Self {
+        TerminalBackend { output }
+    }
+}
+
+impl<W: Write + Send> LedBackend for TerminalBackend<W> {
+    fn write(&mut self, pixels: &[Rgb]) -> io::Result<()> {
+        let mut line = String::with_capacity(pixels.len() * 24 + 8);
+        line.push('\r');
+        for pixel in pixels {
+            line.push_str(&format!("\x1b[38;2;{};{};{}m█", pixel.r, pixel.g, pixel.b));
+        }
+        line.push_str("\x1b[0m");
+        self.output.write_all(line.as_bytes())?;
+        self.output.flush()
+    }
+}
@@ -0,0 +1,87 @@
+// src/leds/ws2812.rs
+
+use crate::leds::backend::LedBackend;
+use crate::leds::color::Rgb;
+use spidev::{SpiModeFlags, Spidev, SpidevOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Horloge SPI : 3 bits SPI par bit WS2812, soit 1,25 µs par bit à 2,4 MHz
+const SPI_SPEED_HZ: u32 = 2_400_000;
+
+/// Motifs SPI d'un bit WS2812 : `110` pour un 1, `100` pour un 0
+const BIT_ONE: u32 = 0b110;
+const BIT_ZERO: u32 = 0b100;
+
+/// Octets à zéro en fin de trame pour le reset (> 280 µs de niveau bas)
+const RESET_BYTES: usize = 100;
+
+/// Bande WS2812 (NeoPixel) pilotée par la sortie MOSI d'un bus SPI (`/dev/spidevX.Y`)
+pub struct Ws2812Backend {
+    spi: Spidev,
+    buffer: Vec<u8>,
+}
+
+impl Ws2812Backend {
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut spi = Spidev::open(path)?;
+        let options = SpidevOptions::new()
+            .bits_per_word(8)
+            .max_speed_hz(SPI_SPEED_HZ)
+            .mode(SpiModeFlags::SPI_MODE_0)
+            .build();
+        spi.configure(&options)?;
+        Ok(Ws2812Backend {
+            spi,
+            buffer: Vec::new(),
+        })
+    }
+}
+
+impl LedBackend for Ws2812Backend {
+    fn write(&mut self, pixels: &[Rgb]) -> io::Result<()> {
+        self.buffer.clear();
+        for pixel in pixels {
+            // Les WS2812 attendent l'ordre vert, rouge, bleu
+            for component in [pixel.g, pixel.r, pixel.b] {
+                encode_byte(component, &mut self.buffer);
+            }
+        }
+        self.buffer.resize(self.buffer.len() + RESET_BYTES, 0);
+        // Le pilote spidev limite un transfert à 4096 octets par défaut (~140 LEDs),
+        // au-delà il faut augmenter `spidev.bufsiz`
+        self.spi.write_all(&self.buffer)
+    }
+}
+
+/// Encode un octet de couleur en 3 octets SPI, bit de poids fort en premier
+fn encode_byte(value: u8, buffer: &mut Vec<u8>) {
+    let mut bits = 0u32;
+    for i in (0..8).rev() {
+        let pattern = if value & (1 << i) != 0 {
+            BIT_ONE
+        } else {
+            BIT_ZERO
+        };
+        bits = (bits << 3) | pattern;
+    }
+    buffer.extend_from_slice(&bits.to_be_bytes()[1..]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_bit_becomes_three_spi_bits() {
+        let mut buffer = Vec::new();
+        encode_byte(0x00, &mut buffer);
+        encode_byte(0xff, &mut buffer);
+        // 0xa5 = 10100101 : 110 100 110 100 100 110 100 110
+        encode_byte(0xa5, &mut buffer);
+        assert_eq!(
+            buffer,
+            [0x92, 0x49, 0x24, 0xdb, 0x6d, 0xb6, 0xd3, 0x49, 0xa6]
+        );
+    }
+}
@@ -0,0 +1,58 @@
+// src/lib.rs
+
+//! Transcription audio temps réel : capture PulseAudio, découpage en trames,
+//! détection de hauteur, segmentation en notes, analyses harmonique (tonalité, accords)
+//! et rythmique (tempo, battements), diffusion des événements vers des abonnés
+//! (MIDI, enregistrement, journal, LEDs, tableau de bord).
+//!
+//! Les types principaux du pipeline sont réexportés à la racine du crate.
+
+pub mod audio;
+pub mod config;
+pub mod events;
+pub mod latency;
+pub mod leds;
+pub mod logging;
+pub mod midi;
+pub mod recorder;
+pub mod sinks;
+pub mod tui;
+pub mod tuner;
+pub mod tuning;
+pub mod utils;
+
+pub use audio::analysis::SpectrumAnalyzer;
+pub use audio::capture::{
+    AudioBuffer, AudioCapturer, CaptureError, CaptureEvent, CaptureHandle, CaptureStatus,
+    CaptureStop, LatencyConfig, ReconnectPolicy,
+};
+pub use audio::chord::{Chord, ChordQuality};
+pub use audio::controls::{LiveParams, ProcessorControls};
+pub use audio::detector::{
+    DetectorKind, FftPitchDetector, Pitch, PitchDetector, YinPitchDetector,
+};
+pub use audio::filter::{Biquad, FilterChain, FilterConfig, FilterKind, FilterPreset};
+pub use audio::framer::{Frame, Framer};
+pub use audio::harmony::{HarmonyAnalyzer, HarmonyChange, HarmonyConfig};
+pub use audio::key::{Key, KeyEstimator, Mode};
+pub use audio::process::AudioProcessor;
+pub use audio::profile::{DetectionProfile, InstrumentProfile};
+pub use audio::quantize::{Grid, NoteSpan, QuantizeConfig, Quantizer, TempoSource};
+pub use audio::realtime::{CountingAllocator, RealtimeError, Scheduling, ThreadConfig};
+pub use audio::ring::{
+    bounded, bounded_keeping, ChannelMetrics, OverflowPolicy, RingReceiver, RingSender,
+};
+pub use audio::smoothing::{PitchSmoother, SmoothingConfig};
+pub use audio::tempo::{Beat, BeatTracker, TempoConfig, TempoEstimator};
+pub use audio::tracker::{NoteChange, NoteTracker, TrackerConfig};
+pub use audio::window::{Window, WindowTables};
+pub use config::{Config, ConfigError, ConfigWatcher};
+pub use midi::transcription::{MidiStatus, MidiTranscriber};
+pub use recorder::Recorder;
+pub use events::{
+    BeatEvent, ChordEvent, ChromaEvent, Event, EventBus, EventSink, KeyEvent, NoteEvent,
+    NoteState, PitchEvent, SpectrumEvent,
+};
+pub use sinks::{LogSink, QuantizedRecorder};
+pub use tuner::{Instrument, Tuner, TunerConfig, TunerDisplay, TunerReading};
+pub use tuning::{KeyboardMapping, Scale, ScalaError, Temperament, Tuning};
@@ -0,0 +1,456 @@
+// src/logging.rs
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, LineWriter, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Nom du crate, omis des cibles dans les filtres et à l'affichage
+const CRATE_NAME: &str = "real_time_audio_signal_processing";
+const CRATE_PREFIX: &str = "real_time_audio_signal_processing::";
+
+/// Fenêtre sur laquelle est compté le nombre de messages d'un même site d'appel
+const RATE_WINDOW: Duration = Duration::from_secs(1);
+
+/// Directive de filtre invalide
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterError {
+    pub directive: String,
+}
+
+impl fmt::Display for FilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "directive de journalisation invalide « {} » (attendu : niveau ou module=niveau)",
+            self.directive
+        )
+    }
+}
+
+impl Error for FilterError {}
+
+/// Niveau par défaut et niveaux par module, par exemple `warn,audio::capture=debug`
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogFilter {
+    default: LevelFilter,
+    /// Modules (chemins sans le nom du crate) et leur niveau
+    modules: Vec<(String, LevelFilter)>,
+}
+
+impl LogFilter {
+    /// Filtre sans exception par module
+    pub fn new(default: LevelFilter) -> Self {
+        LogFilter {
+            default,
+            modules: Vec::new(),
+        }
+    }
+
+    /// Directives séparées par des virgules : `niveau` pour le niveau par défaut,
+    /// `module=niveau` pour un module et ses sous-modules
+    /// (niveaux : off, error, warn, info, debug, trace)
+    pub fn parse(spec: &str) -> Result<Self, FilterError> {
+        let mut filter = LogFilter::new(LevelFilter::Info);
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            let invalid = || FilterError {
+                directive: directive.to_string(),
+            };
+            match directive.split_once('=') {
+                Some((module, level)) => {
+                    let level = level.trim().parse().map_err(|_| invalid())?;
+                    let module = short_target(module.trim());
+                    if module.is_empty() {
+                        return Err(invalid());
+                    }
+                    filter.modules.push((module.to_string(), level));
+                }
+                None => filter.default = directive.parse().map_err(|_| invalid())?,
+            }
+        }
+        Ok(filter)
+    }
+
+    /// Niveau applicable à une cible : celui du module le plus précis qui la contient
+    pub fn level(&self, target: &str) -> LevelFilter {
+        let target = short_target(target);
+        self.modules
+            .iter()
+            .filter(|(module, _)| {
+                target == module
+                    || target
+                        .strip_prefix(module.as_str())
+                        .is_some_and(|rest| rest.starts_with("::"))
+            })
+            .max_by_key(|(module, _)| module.len())
+            .map_or(self.default, |(_, level)| *level)
+    }
+
+    /// Niveau le plus verbeux du filtre, pour que les macros désactivées ne coûtent rien
+    pub fn max_level(&self) -> LevelFilter {
+        self.modules
+            .iter()
+            .map(|(_, level)| *level)
+            .fold(self.default, Ord::max)
+    }
+}
+
+impl Default for LogFilter {
+    fn default() -> Self {
+        LogFilter::new(LevelFilter::Info)
+    }
+}
+
+/// Présentation des messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Une ligne lisible : heure, niveau, module, message
+    Text,
+    /// Un objet JSON par ligne (`time`, `level`, `target`, `message`)
+    Json,
+}
+
+/// Réglages de la journalisation
+#[derive(Debug, Clone)]
+pub struct LogConfig {
+    pub filter: LogFilter,
+    pub format: LogFormat,
+    /// Messages au plus par seconde et par site d'appel ; 0 pour ne rien limiter
+    pub rate_limit: u32,
+    /// Fichier de sortie, sortie d'erreur par défaut
+    pub file: Option<PathBuf>,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        LogConfig {
+            filter: LogFilter::default(),
+            format: LogFormat::Text,
+            rate_limit: 20,
+            file: None,
+        }
+    }
+}
+
+/// Compteur de messages d'un site d'appel sur la fenêtre en cours
+struct Window {
+    start: Instant,
+    count: u32,
+    suppressed: u64,
+    /// Niveau et cible du site, repris pour le bilan des messages supprimés
+    level: Level,
+    target: String,
+}
+
+struct State {
+    output: Box<dyn Write + Send>,
+    windows: HashMap<(&'static str, u32), Window>,
+}
+
+/// Implémentation de la façade `log` : filtre par module, texte ou JSON, limitation
+/// du débit par site d'appel pour les messages émis dans les boucles de traitement
+pub struct Logger {
+    filter: LogFilter,
+    format: LogFormat,
+    rate_limit: u32,
+    state: Mutex<State>,
+}
+
+impl Logger {
+    pub fn new(config: LogConfig) -> io::Result<Self> {
+        let output: Box<dyn Write + Send> = match &config.file {
+            Some(path) => Box::new(LineWriter::new(File::create(path)?)),
+            None => Box::new(io::stderr()),
+        };
+        Ok(Logger {
+            filter: config.filter,
+            format: config.format,
+            rate_limit: config.rate_limit,
+            state: Mutex::new(State {
+                output,
+                windows: HashMap::new(),
+            }),
+        })
+    }
+
+    /// Écrit les messages dans `output` plutôt que dans le fichier ou la sortie d'erreur
+    pub fn with_output(self, output: impl Write + Send + 'static) -> Self {
+        self.state.lock().unwrap().output = Box::new(output);
+        self
+    }
+
+    /// Installe ce journal comme destination des macros `log` du programme
+    pub fn install(self) -> Result<(), Box<dyn Error>> {
+        log::set_max_level(self.filter.max_level());
+        log::set_boxed_logger(Box::new(self))?;
+        Ok(())
+    }
+
+    fn write(&self, output: &mut dyn Write, level: Level, target: &str, message: &str) {
+        let now = chrono::Local::now();
+        let target = short_target(target);
+        let _ = match self.format {
+            LogFormat::Text => writeln!(
+                output,
+                "{} {:<5} {} : {}",
+                now.format("%H:%M:%S%.3f"),
+                level,
+                target,
+                message
+            ),
+            LogFormat::Json => writeln!(
+                output,
+                "{{\"time\":\"{}\",\"level\":\"{}\",\"target\":{},\"message\":{}}}",
+                now.format("%Y-%m-%dT%H:%M:%S%.3f%:z"),
+                level,
+                json_string(target),
+                json_string(message)
+            ),
+        };
+    }
+
+    /// Signale les messages supprimés d'un site et remet leur compte à zéro
+    fn report(&self, output: &mut dyn Write, site: (&'static str, u32), window: &mut Window) {
+        if window.suppressed == 0 {
+            return;
+        }
+        let note = format!(
+            "{} messages similaires supprimés ({}:{})",
+            window.suppressed, site.0, site.1
+        );
+        self.write(output, window.level, &window.target, &note);
+        window.suppressed = 0;
+    }
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.filter.level(metadata.target())
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        let State { output, windows } = &mut *state;
+
+        if self.rate_limit > 0 {
+            let site = (record.file_static().unwrap_or(""), record.line().unwrap_or(0));
+            let now = Instant::now();
+            // Bilan des fenêtres écoulées de tous les sites, pas seulement de celui-ci : un
+            // site qui se tait ne garderait jamais ses suppressions pour lui
+            for (&site, window) in windows.iter_mut() {
+                if now.duration_since(window.start) >= RATE_WINDOW {
+                    self.report(output.as_mut(), site, window);
+                }
+            }
+            let window = windows.entry(site).or_insert_with(|| Window {
+                start: now,
+                count: 0,
+                suppressed: 0,
+                level: record.level(),
+                target: record.target().to_string(),
+            });
+            if now.duration_since(window.start) >= RATE_WINDOW {
+                window.start = now;
+                window.count = 0;
+            }
+            if window.count >= self.rate_limit {
+                window.suppressed += 1;
+                return;
+            }
+            window.count += 1;
+        }
+
+        let message = record.args().to_string();
+        self.write(output.as_mut(), record.level(), record.target(), &message);
+    }
+
+    /// Signale aussi les messages supprimés des fenêtres en cours
+    fn flush(&self) {
+        let mut state = self.state.lock().unwrap();
+        let State { output, windows } = &mut *state;
+        for (&site, window) in windows.iter_mut() {
+            self.report(output.as_mut(), site, window);
+        }
+        let _ = output.flush();
+    }
+}
+
+/// Cible sans le nom du crate (`audio::capture`), `main` pour le programme lui-même
+fn short_target(target: &str) -> &str {
+    if target == CRATE_NAME {
+        return "main";
+    }
+    target.strip_prefix(CRATE_PREFIX).unwrap_or(target)
+}
+
+/// Chaîne JSON entre guillemets, caractères spéciaux échappés
+fn json_string(text: &str) -> String {
+    let mut json = String::with_capacity(text.len() + 2);
+    json.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    /// Tampon partagé entre le journal et le test
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(bytes)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Buffer {
+        fn lines(&self) -> Vec<String> {
+            let bytes = self.0.lock().unwrap();
+            String::from_utf8_lossy(&bytes)
+                .lines()
+                .map(str::to_string)
+                .collect()
+        }
+    }
+
+    fn logger(rate_limit: u32) -> (Logger, Buffer) {
+        let buffer = Buffer::default();
+        let config = LogConfig {
+            rate_limit,
+            ..LogConfig::default()
+        };
+        let logger = Logger::new(config).unwrap().with_output(buffer.clone());
+        (logger, buffer)
+    }
+
+    fn log_at(logger: &Logger, line: u32, message: &str) {
+        logger.log(
+            &Record::builder()
+                .args(format_args!("{}", message))
+                .level(Level::Warn)
+                .target("real_time_audio_signal_processing::audio::process")
+                .file_static(Some("src/audio/process.rs"))
+                .line(Some(line))
+                .build(),
+        );
+    }
+
+    #[test]
+    fn most_precise_module_wins() {
+        let filter = LogFilter::parse("warn, audio=debug,audio::capture=trace").unwrap();
+        assert_eq!(filter.level("audio::capture"), LevelFilter::Trace);
+        assert_eq!(filter.level("audio::capture::pulse"), LevelFilter::Trace);
+        assert_eq!(
+            filter.level("real_time_audio_signal_processing::audio::capture"),
+            LevelFilter::Trace
+        );
+        assert_eq!(filter.level("audio::process"), LevelFilter::Debug);
+        assert_eq!(filter.level("audio"), LevelFilter::Debug);
+        assert_eq!(filter.level("audiox"), LevelFilter::Warn);
+        assert_eq!(
+            filter.level("real_time_audio_signal_processing"),
+            LevelFilter::Warn
+        );
+        assert_eq!(filter.max_level(), LevelFilter::Trace);
+
+        // L'ordre des directives ne compte pas
+        let reversed = LogFilter::parse("audio::capture=trace,audio=debug").unwrap();
+        assert_eq!(reversed.level("audio::capture"), LevelFilter::Trace);
+        assert_eq!(LogFilter::parse("").unwrap(), LogFilter::default());
+    }
+
+    #[test]
+    fn invalid_directives_are_rejected() {
+        for spec in ["verbose", "audio=loud", "=debug", "info,audio="] {
+            let err = LogFilter::parse(spec).unwrap_err();
+            assert!(
+                spec.contains(&err.directive),
+                "{} : {}",
+                spec,
+                err.directive
+            );
+        }
+    }
+
+    #[test]
+    fn json_strings_are_escaped() {
+        assert_eq!(json_string("note"), "\"note\"");
+        assert_eq!(
+            json_string("a\"b\\c\nd\te\r\u{1}é"),
+            "\"a\\\"b\\\\c\\nd\\te\\r\\u0001é\""
+        );
+    }
+
+    #[test]
+    fn repeated_messages_are_limited_then_reported_on_flush() {
+        let (logger, buffer) = logger(3);
+        for _ in 0..10 {
+            log_at(&logger, 10, "trop d'allocations");
+        }
+        log_at(&logger, 20, "autre site");
+        assert_eq!(buffer.lines().len(), 4);
+        assert!(buffer.lines()[3].ends_with("autre site"));
+
+        logger.flush();
+        let lines = buffer.lines();
+        assert_eq!(lines.len(), 5);
+        assert!(
+            lines[4].ends_with("7 messages similaires supprimés (src/audio/process.rs:10)"),
+            "{}",
+            lines[4]
+        );
+        // Rien à signaler au flush suivant
+        logger.flush();
+        assert_eq!(buffer.lines().len(), 5);
+    }
+
+    #[test]
+    fn suppressed_count_is_reported_by_another_site() {
+        let (logger, buffer) = logger(1);
+        log_at(&logger, 10, "premier");
+        log_at(&logger, 10, "supprimé");
+        std::thread::sleep(RATE_WINDOW + Duration::from_millis(50));
+        log_at(&logger, 20, "autre site");
+        let lines = buffer.lines();
+        assert_eq!(lines.len(), 3, "{:?}", lines);
+        assert!(lines[1].contains("1 messages similaires supprimés"));
+        assert!(lines[1].contains("audio::process"));
+        assert!(lines[2].ends_with("autre site"));
+    }
+
+    #[test]
+    fn no_limit_when_rate_is_zero() {
+        let (logger, buffer) = logger(0);
+        for _ in 0..50 {
+            log_at(&logger, 10, "message");
+        }
+        logger.flush();
+        assert_eq!(buffer.lines().len(), 50);
+    }
+}
@@ -1,49 +1,449 @@
 // src/main.rs
 
-mod audio;
-mod midi;
-mod utils;
-mod recorder;
-
-use audio::capture::AudioCapturer;
-use audio::process::AudioProcessor;
-use midi::transcription::MidiTranscriber;
-use recorder::Recorder;
+use real_time_audio_signal_processing::audio::calibration::LoopbackCalibration;
+use real_time_audio_signal_processing::audio::realtime::{lock_memory, parse_cpus};
+use real_time_audio_signal_processing::latency::LatencyReport;
+use real_time_audio_signal_processing::logging::{LogConfig, LogFilter, LogFormat, Logger};
+use real_time_audio_signal_processing::tui::Dashboard;
+use real_time_audio_signal_processing::leds::{
+    ArtNetBackend, DmxProtocol, LedBackend, LedController, LedMode, Palette, SacnBackend,
+    TerminalBackend, Ws2812Backend,
+};
+use real_time_audio_signal_processing::{
+    bounded, AudioCapturer, AudioProcessor, CaptureError, CaptureEvent, Config, ConfigWatcher,
+    CountingAllocator, DetectorKind, Event, EventBus, FilterConfig, FilterPreset,
+    Framer, Grid, Instrument, InstrumentProfile, LogSink, MidiTranscriber, NoteState,
+    OverflowPolicy, ProcessorControls, QuantizeConfig, QuantizedRecorder, Quantizer,
+    ReconnectPolicy, Recorder, Scheduling, SmoothingConfig, TempoSource, Temperament, ThreadConfig,
+    TrackerConfig, Tuner, TunerConfig, TunerDisplay, Tuning, Window,
+};
 use std::error::Error;
+use std::net::IpAddr;
+use std::path::PathBuf;
 use std::sync::mpsc::channel;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// Nombre de tentatives de connexion au serveur PulseAudio
+const CAPTURE_ATTEMPTS: u32 = 3;
+
+/// Nombre de blocs audio en attente entre la capture et le traitement
+const CHANNEL_CAPACITY: usize = 32;
+
+/// Nombre de notes entre deux rapports de latence
+const LATENCY_REPORT_INTERVAL: u64 = 50;
+
+/// Trames du mode accordeur : ~190 ms, pour la précision sur les cordes graves
+const TUNER_FRAME_SIZE: usize = 8192;
+const TUNER_HOP_SIZE: usize = 1024;
+
+/// Compte les allocations du chemin critique du traitement, signalées dans le journal
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
 
 fn main() -> Result<(), Box<dyn Error>> {
+    // Journalisation : `--log=warn,audio::capture=debug` (niveau par défaut et par module),
+    // `--log-json`, `--log-file=journal.log`, `--log-rate=20` messages par seconde et par site
+    // d'appel (0 sans limite) ; avec le tableau de bord, seules les erreurs vont sur la console
+    let default_filter = match flag("--tui") && option("--log-file").is_none() {
+        true => "error",
+        false => "info",
+    };
+    let format = match flag("--log-json") {
+        true => LogFormat::Json,
+        false => LogFormat::Text,
+    };
+    Logger::new(LogConfig {
+        filter: LogFilter::parse(&option("--log").unwrap_or(default_filter.to_string()))?,
+        format,
+        rate_limit: option("--log-rate").map(|rate| rate.parse()).transpose()?.unwrap_or(20),
+        file: option("--log-file").map(PathBuf::from),
+    })?
+    .install()?;
+
+    // Configuration : `--config=reglages.toml`, valeurs par défaut sans fichier ; les options
+    // de la ligne de commande l'emportent sur le fichier
+    let config_path = option("--config").map(PathBuf::from);
+    let file_config = match &config_path {
+        Some(path) => Config::load(path).map_err(|err| format!("{} : {}", path.display(), err))?,
+        None => Config::default(),
+    };
+
+    // Profil d'instrument : `--profile=guitar|bass|violin|flute|whistle|voice|soprano|
+    // mezzo-soprano|alto|tenor|baritone|bass-voice|general`, qui fixe la bande de fréquences,
+    // l'algorithme, les trames, le gate et le pré-filtrage à la place du fichier
+    let profile = match option("--profile") {
+        Some(name) => {
+            Some(InstrumentProfile::parse(&name).ok_or(format!("Profil inconnu : {}", name))?)
+        }
+        None => None,
+    };
+    let config = match profile {
+        Some(profile) => file_config.with_profile(profile)?,
+        None => file_config,
+    };
+
+    // Temps réel : `--rt=fifo:70` ou `--rt=rr` pour les threads de capture et de traitement,
+    // cœurs dédiés `--capture-cpus=2` et `--process-cpus=3`, mémoire verrouillée avec `--mlock`
+    let scheduling = match option("--rt") {
+        Some(name) => {
+            Scheduling::parse(&name).ok_or(format!("Ordonnancement inconnu : {}", name))?
+        }
+        None => Scheduling::Normal,
+    };
+    let cpus = |name| match option(name) {
+        Some(spec) => parse_cpus(&spec).ok_or(format!("Liste de cœurs invalide : {}", spec)),
+        None => Ok(Vec::new()),
+    };
+    let capture_thread = ThreadConfig {
+        scheduling,
+        cpus: cpus("--capture-cpus")?,
+    };
+    let process_thread = ThreadConfig {
+        scheduling,
+        cpus: cpus("--process-cpus")?,
+    };
+    if flag("--mlock") {
+        match lock_memory() {
+            Ok(()) => log::info!("Mémoire du processus verrouillée"),
+            Err(err) => log::warn!("{}", err),
+        }
+    }
+
     // Nom de la source PulseAudio
-    let source_name = "bluez_source.DC_E5_5B_25_03_64.a2dp_source";
+    let source_name = &config.source.name;
 
-    // Créer un canal pour transmettre les échantillons audio
-    let (sender, receiver) = channel();
+    // Créer un canal borné pour transmettre les échantillons audio : si le traitement prend
+    // du retard, les blocs les plus anciens sont jetés plutôt que d'accumuler de la latence
+    let (sender, receiver) = bounded(CHANNEL_CAPACITY, OverflowPolicy::DropOldest);
+    let channel_metrics = sender.metrics();
 
-    // Initialiser le module de capture audio
-    let audio_capturer = AudioCapturer::new(source_name);
-    audio_capturer.start(sender); // Assurez-vous que `start` retourne un Result
+    // Suivre les changements d'état de la capture (pertes de la source Bluetooth, reconnexions)
+    let (capture_events, capture_events_receiver) = channel();
+    thread::spawn(move || {
+        for event in capture_events_receiver {
+            match event {
+                CaptureEvent::Connected { source, fallback: true } => log::warn!(
+                    "Capture sur la source par défaut ({}) en attendant la source Bluetooth",
+                    source
+                ),
+                CaptureEvent::GaveUp(err) => log::error!("Abandon de la capture : {}", err),
+                _ => {}
+            }
+        }
+    });
+
+    // Initialiser le module de capture audio, avec une copie brute de la capture en WAV 16 bits,
+    // une reconnexion automatique si la source disparaît et un budget de latence (20 ms par défaut)
+    let mut audio_capturer = AudioCapturer::new(source_name)
+        .with_sample_rate(config.source.sample_rate)
+        .with_reconnect(ReconnectPolicy::default())
+        .with_latency(config.latency())
+        .with_events(capture_events)
+        .with_thread(capture_thread);
+    if let Some(tee) = config.tee()? {
+        audio_capturer = audio_capturer.with_tee(tee);
+    }
+    let sample_rate = config.source.sample_rate as f32;
+    let mut attempt = 1;
+    let capture = loop {
+        match audio_capturer.start(sender.clone()) {
+            Ok(handle) => break handle,
+            Err(CaptureError::ServerUnavailable(reason)) if attempt < CAPTURE_ATTEMPTS => {
+                log::warn!(
+                    "Serveur PulseAudio indisponible ({}), nouvelle tentative {}/{}...",
+                    reason,
+                    attempt + 1,
+                    CAPTURE_ATTEMPTS
+                );
+                attempt += 1;
+                thread::sleep(Duration::from_secs(1));
+            }
+            Err(err) => {
+                log::error!("Impossible de démarrer la capture : {}", err);
+                return Err(err.into());
+            }
+        }
+    };
+    // Seul le thread de capture doit garder l'émetteur, pour que le canal se ferme avec lui
+    drop(sender);
 
-    // Initialiser le transcripteur MIDI avec le canal 0 (canal 1 MIDI)
-    let mut midi_transcriber = MidiTranscriber::new(0)?; // Canal 0
+    // Accord : `--a4=432` pour la référence, `--tuning=just|pythagorean|meantone|werckmeister3|
+    // vallotti|kirnberger3` ou un fichier Scala `--tuning=gamme.scl` (placement `--kbm=clavier.kbm`)
+    let reference = option("--a4").map(|a4| a4.parse()).transpose()?.unwrap_or(440.0);
+    let tuning = match option("--tuning") {
+        None => Tuning::equal(reference),
+        Some(name) if name == "just" => Tuning::just(0, reference),
+        Some(name) => match Temperament::parse(&name) {
+            Some(temperament) => Tuning::temperament(temperament, reference),
+            None => Tuning::from_scala(name, option("--kbm"))?,
+        },
+    };
 
-    // Initialiser l'enregistreur de notes avec un taux d'échantillonnage de 44100 Hz et un fichier de sortie unique
-    let recorder = Recorder::new(44100, "recorded_notes_all.wav")?;
-    recorder.start_recording();
+    // Pré-filtrage avant l'analyse : `--filter=general|voice|guitar|bass|violin|flute|whistle|none`
+    // (general par défaut), réjecteur du secteur `--mains=50|60|off`
+    let filters = match option("--filter").as_deref() {
+        None => config.filters()?,
+        Some("none") => FilterConfig::none(),
+        Some(name) => FilterConfig::preset(
+            FilterPreset::parse(name).ok_or(format!("Préréglage de filtre inconnu : {}", name))?,
+        ),
+    };
+    let filters = match option("--mains").as_deref() {
+        None => filters,
+        Some("off") => filters.with_hum(None),
+        Some(mains) => filters.with_hum(Some(mains.parse()?)),
+    };
 
-    // Initialiser le module de traitement audio
-    let audio_processor = AudioProcessor::new(receiver, move |note_number| {
-        // Convertir le numéro de note MIDI en fréquence
-        if let Some(freq) = utils::frequency_to_midi_note_number_to_freq(note_number) {
-            // Ajouter la note au Recorder
-            recorder.add_note(freq, 100, 500); // Vélocité 100, durée 500 ms
+    // Fenêtre d'analyse : `--window=hann|hamming|blackman-harris|kaiser[:beta]|flat-top|rectangular`
+    let window = match option("--window") {
+        Some(name) => Window::parse(&name).ok_or(format!("Fenêtre inconnue : {}", name))?,
+        None => config.window()?,
+    };
+
+    // Algorithme de détection : `--detector=fft|yin`
+    let detector = match option("--detector") {
+        Some(name) => {
+            DetectorKind::parse(&name).ok_or(format!("Algorithme inconnu : {}", name))?
         }
+        None => config.detector()?,
+    };
 
-        // Jouer la note via FluidSynth
-        if let Err(err) = midi_transcriber.play_note(note_number, 100, 500) {
-            eprintln!("Erreur lors de la lecture de la note MIDI : {}", err);
+    // Lissage de la hauteur avant la segmentation : `--smoothing=median:5,octave,viterbi`
+    // (filtre médian sur 5 trames, correction des sauts d'octave, chemin de Viterbi) ou `none`
+    let smoothing = match option("--smoothing") {
+        Some(spec) => {
+            SmoothingConfig::parse(&spec).ok_or(format!("Lissage invalide : {}", spec))?
         }
-    });
+        None => config.smoothing()?,
+    };
+
+    // Mode accordeur : `--tuner` (chromatique) ou `--tuner=guitar|bass|violin`, sans MIDI ni
+    // enregistrement ; des trames plus longues affinent la mesure dans le grave
+    let instrument = match option("--tuner") {
+        Some(name) => {
+            Some(Instrument::parse(&name).ok_or(format!("Instrument inconnu : {}", name))?)
+        }
+        None => flag("--tuner").then_some(Instrument::Chromatic),
+    };
+    if let Some(instrument) = instrument {
+        let tuner = Tuner::new(TunerConfig {
+            instrument,
+            tuning: tuning.clone(),
+            ..TunerConfig::default()
+        });
+        // Même algorithme et même bande de recherche que la transcription
+        let mut tuner_detector = detector.build(window);
+        tuner_detector
+            .set_frequency_range(config.detector.min_frequency, config.detector.max_frequency);
+        let mut bus = EventBus::new();
+        bus.subscribe("accordeur", TunerDisplay::stdout(tuner));
+        AudioProcessor::new(receiver, bus)
+            .with_sample_rate(sample_rate)
+            .with_framer(Framer::new(2, TUNER_FRAME_SIZE, TUNER_HOP_SIZE))
+            .with_detector(tuner_detector)
+            .with_filters(filters)
+            .with_thread(process_thread)
+            .with_tracker(TrackerConfig {
+                tuning,
+                ..TrackerConfig::default()
+            })
+            .start();
+        capture.join()?;
+        return Ok(());
+    }
+
+    // Mesure de la latence de bout en bout (capture → Note ON)
+    let latency_report = Arc::new(LatencyReport::new());
+
+    // Initialiser le transcripteur MIDI, sur le canal 0 (canal 1 MIDI) par défaut ; le port est
+    // demandé au lancement s'il n'est pas dans la configuration
+    let midi_transcriber = MidiTranscriber::open(config.midi.channel, config.midi.port.as_deref())?
+        .with_latency_report(Arc::clone(&latency_report));
+    let midi_status = midi_transcriber.status();
+
+    // Tableau de bord plein écran `--tui` à la place du journal et des rapports sur la console
+    let dashboard = flag("--tui");
+
+    // Initialiser l'enregistreur de notes (44100 Hz et un fichier de sortie unique par défaut)
+    let recorder = Recorder::new(config.recorder.sample_rate, &config.recorder.path)?
+        .with_tuning(tuning.clone());
+
+    // Abonnés aux événements de note, chacun dans son thread
+    let mut bus = EventBus::new();
+    if !dashboard {
+        bus.subscribe("journal", LogSink);
+    }
+    bus.subscribe("midi", midi_transcriber);
+
+    // Enregistrement quantifié optionnel : `--quantize=1/8|1/16|1/8t|1/16t`, au tempo détecté
+    // ou fixé par `--bpm=120`, avec `--swing=0.5` et `--strength=0.8` ; les notes sont rendues à
+    // la fin de la capture
+    let quantize = match option("--quantize") {
+        Some(grid) => {
+            let grid = Grid::parse(&grid).ok_or(format!("Grille inconnue : {}", grid))?;
+            let tempo = match option("--bpm") {
+                Some(bpm) => TempoSource::Fixed(bpm.parse()?),
+                None => TempoSource::Detected,
+            };
+            let strength = fraction_option("--strength")?.unwrap_or(config.recorder.strength);
+            let swing = fraction_option("--swing")?.unwrap_or(config.recorder.swing);
+            Some(
+                QuantizeConfig::new(grid, tempo)
+                    .with_strength(strength)
+                    .with_swing(swing),
+            )
+        }
+        None => config.quantize()?,
+    };
+    match quantize {
+        Some(config) => {
+            bus.subscribe(
+                "enregistreur",
+                QuantizedRecorder::new(recorder, Quantizer::new(config)),
+            );
+        }
+        None => {
+            recorder.start_recording();
+            bus.subscribe("enregistreur", recorder);
+        }
+    }
+
+    // Calibration optionnelle : un La 4 joué via rodio dans l'accord choisi doit revenir
+    // par le micro
+    if flag("--calibrate") {
+        let calibration = LoopbackCalibration::new(69, 10, tuning.clone());
+        calibration.start()?;
+        bus.subscribe("calibration", calibration);
+    }
+
+    // Bande de LEDs optionnelle : `--leds=terminal` (simulation), `--leds=/dev/spidev0.0` (WS2812),
+    // `--leds=sacn[:ip]` ou `--leds=artnet[:ip]` (nœuds DMX, multicast/broadcast sans adresse)
+    if let Some(target) = option("--leds").or(config.leds.output.clone()) {
+        let (protocol, address) = match target.split_once(':') {
+            Some((protocol, address)) => (protocol, Some(address.parse::<IpAddr>()?)),
+            None => (target.as_str(), None),
+        };
+        let backend: Box<dyn LedBackend> = match protocol {
+            "terminal" => Box::new(TerminalBackend::stdout()),
+            "sacn" => {
+                let mapping = config.dmx_mapping(DmxProtocol::Sacn)?;
+                Box::new(SacnBackend::new(mapping, address)?)
+            }
+            "artnet" => {
+                let mapping = config.dmx_mapping(DmxProtocol::ArtNet)?;
+                Box::new(ArtNetBackend::new(mapping, address)?)
+            }
+            _ => Box::new(Ws2812Backend::open(&target)?),
+        };
+        // Mode d'affichage : `--led-mode=notes|spectrum|chroma`, dégradé du mode spectre avec
+        // `--led-palette=rainbow|fire|ocean`, flash sur les attaques avec `--led-flash`,
+        // pulsation sur les battements avec `--led-beat`
+        let mut leds = config.led_config()?;
+        if let Some(mode) = option("--led-mode") {
+            leds.mode = LedMode::parse(&mode).ok_or(format!("Mode de LEDs inconnu : {}", mode))?;
+        }
+        if let Some(name) = option("--led-palette") {
+            leds.palette =
+                Palette::parse(&name).ok_or(format!("Palette de LEDs inconnue : {}", name))?;
+        }
+        leds.onset_flash |= flag("--led-flash");
+        leds.beat_pulse |= flag("--led-beat");
+        bus.subscribe("leds", LedController::new(leds, backend)?);
+    }
+
+    // Réglages du détecteur et du gate modifiables depuis le tableau de bord, et rechargés
+    // à chaud quand le fichier de configuration change, comme le canal MIDI
+    let tracker_config = config.tracker(tuning.clone());
+    let controls = Arc::new(ProcessorControls::new(config.live_params()));
+    let _watcher = match config_path {
+        Some(path) => Some(
+            ConfigWatcher::new(path, config.clone())
+                .with_profile(profile)
+                .with_controls(Arc::clone(&controls))
+                .with_midi(Arc::clone(&midi_status))
+                .start()?,
+        ),
+        None => None,
+    };
+
+    if dashboard {
+        // Quitter arrête la capture : le canal se ferme et chaque abonné termine son travail
+        let stop = capture.stopper();
+        let mut monitor = Dashboard::new(Arc::clone(&controls))
+            .with_tuning(tuning)
+            .with_quit(move || stop.stop())
+            .with_midi(Arc::clone(&midi_status))
+            .with_channel("capture", Arc::clone(&channel_metrics));
+        for (name, metrics) in bus.metrics() {
+            monitor = monitor.with_channel(&name, metrics);
+        }
+        bus.subscribe("tableau de bord", monitor.start()?);
+    } else {
+        // Rapport périodique de latence et de l'état du canal
+        let report = Arc::clone(&latency_report);
+        let processor_metrics = Arc::clone(&channel_metrics);
+        let mut notes_played = 0;
+        bus.subscribe("rapports", move |event: &Event| {
+            let Event::Note(note) = event else {
+                return;
+            };
+            if note.state == NoteState::On {
+                notes_played += 1;
+                if notes_played % LATENCY_REPORT_INTERVAL == 0 {
+                    report.print();
+                    log::info!("Canal capture → traitement : {}", processor_metrics.snapshot());
+                }
+            }
+        });
+    }
+
+    // Initialiser le module de traitement audio
+    let audio_processor = AudioProcessor::new(receiver, bus)
+        .with_sample_rate(sample_rate)
+        .with_framer(config.framer())
+        .with_detector(detector.build(window))
+        .with_smoothing(smoothing)
+        .with_tracker(tracker_config)
+        .with_filters(filters)
+        .with_thread(process_thread)
+        .with_controls(controls);
     audio_processor.start();
+    if !dashboard {
+        latency_report.print();
+        log::info!("Canal capture → traitement : {}", channel_metrics.snapshot());
+    }
 
+    // Le canal est fermé : remonter l'éventuelle erreur de capture, après avoir signalé les
+    // messages du journal encore supprimés
+    let result = capture.join();
+    log::logger().flush();
+    result?;
     Ok(())
 }
+
+/// Valeur d'une option `--nom=valeur` de la ligne de commande
+fn option(name: &str) -> Option<String> {
+    let prefix = format!("{}=", name);
+    std::env::args().find_map(|arg| arg.strip_prefix(&prefix).map(String::from))
+}
+
+/// Valeur d'une option `--nom=valeur` attendue entre 0 et 1
+fn fraction_option(name: &str) -> Result<Option<f32>, Box<dyn Error>> {
+    let Some(text) = option(name) else {
+        return Ok(None);
+    };
+    let value: f32 = text
+        .parse()
+        .map_err(|_| format!("Nombre attendu pour {} : {}", name, text))?;
+    if !(0.0..=1.0).contains(&value) {
+        return Err(format!("{} attendu entre 0 et 1 : {}", name, value).into());
+    }
+    Ok(Some(value))
+}
+
+/// Présence d'une option sans valeur (`--nom`)
+fn flag(name: &str) -> bool {
+    std::env::args().any(|arg| arg == name)
+}
@@ -1,15 +1,64 @@
 use midir::{MidiOutput, MidiOutputConnection};
 use std::error::Error;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::Arc;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use crate::latency::LatencyReport;
+
+/// État de la sortie MIDI, lisible depuis n'importe quel thread ; le canal peut y être
+/// changé pendant la transcription
+#[derive(Debug, Default)]
+pub struct MidiStatus {
+    /// Nom du port de sortie connecté
+    pub port: String,
+    channel: AtomicU8,
+    sent: AtomicU64,
+    errors: AtomicU64,
+}
+
+impl MidiStatus {
+    /// Canal (0-15) des prochains Note On
+    pub fn channel(&self) -> u8 {
+        self.channel.load(Ordering::Relaxed)
+    }
+
+    /// Change le canal des notes suivantes ; les notes tenues s'éteignent sur leur canal
+    pub fn set_channel(&self, channel: u8) -> Result<(), Box<dyn Error>> {
+        if channel > 15 {
+            return Err("Le canal MIDI doit être compris entre 0 et 15.".into());
+        }
+        self.channel.store(channel, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Messages envoyés avec succès
+    pub fn sent(&self) -> u64 {
+        self.sent.load(Ordering::Relaxed)
+    }
+
+    /// Envois refusés par le port
+    pub fn errors(&self) -> u64 {
+        self.errors.load(Ordering::Relaxed)
+    }
+}
 
 pub struct MidiTranscriber {
     conn_out: MidiOutputConnection,
-    channel: u8, // Canal MIDI (0-15)
+    /// Canal sur lequel chaque note tenue a été allumée
+    note_channels: [u8; 128],
+    latency_report: Option<Arc<LatencyReport>>,
+    status: Arc<MidiStatus>,
 }
 
 impl MidiTranscriber {
     pub fn new(channel: u8) -> Result<Self, Box<dyn Error>> {
+        Self::open(channel, None)
+    }
+
+    /// Se connecte au port `port` (numéro, ou partie du nom comme « FluidSynth ») ;
+    /// sans port, la liste est affichée et le numéro demandé sur l'entrée standard
+    pub fn open(channel: u8, port: Option<&str>) -> Result<Self, Box<dyn Error>> {
         if channel > 15 {
             return Err("Le canal MIDI doit être compris entre 0 et 15.".into());
         }
@@ -22,37 +71,97 @@ impl MidiTranscriber {
             return Err("Aucun port de sortie MIDI disponible".into());
         }
 
-        // Afficher les ports disponibles pour choisir le bon
-        for (i, port) in out_ports.iter().enumerate() {
-            println!("Port {}: {}", i, midi_out.port_name(port)?);
-        }
+        let port_index = match port {
+            Some(wanted) => match wanted.parse::<usize>() {
+                Ok(index) => index,
+                Err(_) => out_ports
+                    .iter()
+                    .position(|port| {
+                        midi_out.port_name(port).is_ok_and(|name| name.contains(wanted))
+                    })
+                    .ok_or(format!("Port MIDI introuvable : {}", wanted))?,
+            },
+            None => {
+                // Afficher les ports disponibles pour choisir le bon
+                for (i, port) in out_ports.iter().enumerate() {
+                    println!("Port {}: {}", i, midi_out.port_name(port)?);
+                }
 
-        // Demander à l'utilisateur de choisir le port
-        println!("Entrez le numéro du port MIDI à utiliser:");
-        let mut input = String::new();
-        std::io::stdin().read_line(&mut input)?;
-        let port_index: usize = input.trim().parse()?;
+                // Demander à l'utilisateur de choisir le port
+                println!("Entrez le numéro du port MIDI à utiliser:");
+                let mut input = String::new();
+                std::io::stdin().read_line(&mut input)?;
+                input.trim().parse()?
+            }
+        };
         if port_index >= out_ports.len() {
             return Err("Index de port invalide".into());
         }
 
         let port = &out_ports[port_index];
+        let status = Arc::new(MidiStatus {
+            port: midi_out.port_name(port)?,
+            channel: AtomicU8::new(channel),
+            ..MidiStatus::default()
+        });
         let conn_out = midi_out.connect(port, "midir-test")?;
 
-        Ok(MidiTranscriber { conn_out, channel })
+        Ok(MidiTranscriber {
+            conn_out,
+            note_channels: [channel; 128],
+            latency_report: None,
+            status,
+        })
     }
 
-    pub fn play_note(&mut self, note: u8, velocity: u8, duration_ms: u64) -> Result<(), Box<dyn Error>> {
-        // Construire les messages Note ON et Note OFF en spécifiant le canal
-        let note_on = 0x90 + self.channel; // 0x90 est le code pour Note ON sur le canal 1
-        let note_off = 0x80 + self.channel; // 0x80 est le code pour Note OFF sur le canal 1
+    /// Enregistre les horodatages de chaque note émise dans ce rapport de latence
+    pub fn with_latency_report(mut self, report: Arc<LatencyReport>) -> Self {
+        self.latency_report = Some(report);
+        self
+    }
+
+    pub fn latency_report(&self) -> Option<&LatencyReport> {
+        self.latency_report.as_deref()
+    }
+
+    /// Port connecté et compteurs d'envoi, partagés avec le transcripteur
+    pub fn status(&self) -> Arc<MidiStatus> {
+        Arc::clone(&self.status)
+    }
 
-        // Note ON
-        self.conn_out.send(&[note_on, note, velocity])?;
+    /// Envoie un message en tenant les compteurs à jour
+    fn send(&mut self, message: &[u8]) -> Result<(), Box<dyn Error>> {
+        match self.conn_out.send(message) {
+            Ok(()) => {
+                self.status.sent.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+            Err(err) => {
+                self.status.errors.fetch_add(1, Ordering::Relaxed);
+                Err(err.into())
+            }
+        }
+    }
+
+    pub fn play_note(&mut self, note: u8, velocity: u8, duration_ms: u64) -> Result<(), Box<dyn Error>> {
+        self.note_on(note, velocity)?;
         // Attendre la durée spécifiée
         thread::sleep(Duration::from_millis(duration_ms));
-        // Note OFF
-        self.conn_out.send(&[note_off, note, 0])?;
+        self.note_off(note)
+    }
+
+    /// Envoie un Note ON et retourne l'instant d'envoi (mesure de latence)
+    pub fn note_on(&mut self, note: u8, velocity: u8) -> Result<Instant, Box<dyn Error>> {
+        let channel = self.status.channel();
+        self.note_channels[note as usize & 0x7f] = channel;
+        let note_on = 0x90 + channel; // 0x90 est le code pour Note ON sur le canal 1
+        self.send(&[note_on, note, velocity])?;
+        Ok(Instant::now())
+    }
+
+    pub fn note_off(&mut self, note: u8) -> Result<(), Box<dyn Error>> {
+        let note_off = 0x80 + self.note_channels[note as usize & 0x7f]; // 0x80 est le code pour Note OFF sur le canal 1
+        self.send(&[note_off, note, 0])?;
         Ok(())
     }
 }
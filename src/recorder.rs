@@ -1,5 +1,7 @@
 // src/recorder.rs
 
+use crate::audio::quantize::NoteSpan;
+use crate::tuning::Tuning;
 use hound::{WavWriter, WavSpec, SampleFormat};
 use std::path::Path;
 use std::error::Error;
@@ -7,6 +9,7 @@ use std::f32::consts::PI;
 use std::io::BufWriter;
 use std::fs::File;
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 /// Structure représentant une note active
 pub struct ActiveNote {
@@ -20,13 +23,14 @@ pub struct Recorder {
     writer: Arc<Mutex<Option<WavWriter<BufWriter<File>>>>>,
     active_notes: Arc<Mutex<Vec<ActiveNote>>>,
     sample_rate: u32,
+    tuning: Tuning,
 }
 
 impl Recorder {
     pub fn new(sample_rate: u32, output_path: &str) -> Result<Self, Box<dyn Error>> {
         let spec = WavSpec {
             channels: 1,
-            sample_rate: sample_rate,
+            sample_rate,
             bits_per_sample: 16,
             sample_format: SampleFormat::Int,
         };
@@ -38,9 +42,20 @@ impl Recorder {
             writer: Arc::new(Mutex::new(Some(writer))),
             active_notes: Arc::new(Mutex::new(Vec::new())),
             sample_rate,
+            tuning: Tuning::default(),
         })
     }
 
+    /// Accord utilisé pour synthétiser les notes reçues du bus
+    pub fn with_tuning(mut self, tuning: Tuning) -> Self {
+        self.tuning = tuning;
+        self
+    }
+
+    pub fn tuning(&self) -> &Tuning {
+        &self.tuning
+    }
+
     pub fn add_note(&self, frequency: f32, velocity: u8, duration_ms: u64) {
         let duration_samples = ((duration_ms as f32 / 1000.0) * self.sample_rate as f32) as usize;
         let mut notes = self.active_notes.lock().unwrap();
@@ -50,7 +65,28 @@ impl Recorder {
             phase: 0.0,
             remaining_samples: duration_samples,
         });
-        println!("Note ajoutée : {} Hz, vélocité {}, durée {} ms", frequency, velocity, duration_ms);
+        log::debug!("Note ajoutée : {} Hz, vélocité {}, durée {} ms", frequency, velocity, duration_ms);
+    }
+
+    /// Démarre une note tenue jusqu'à l'appel de `stop_note` avec la même fréquence
+    pub fn start_note(&self, frequency: f32, velocity: u8) {
+        let mut notes = self.active_notes.lock().unwrap();
+        notes.push(ActiveNote {
+            frequency,
+            velocity,
+            phase: 0.0,
+            remaining_samples: usize::MAX,
+        });
+    }
+
+    /// Arrête les notes tenues de cette fréquence
+    pub fn stop_note(&self, frequency: f32) {
+        let mut notes = self.active_notes.lock().unwrap();
+        for note in notes.iter_mut() {
+            if note.remaining_samples == usize::MAX && (note.frequency - frequency).abs() < 1e-3 {
+                note.remaining_samples = 0;
+            }
+        }
     }
 
     pub fn generate_sample(active_notes: &Mutex<Vec<ActiveNote>>, sample_rate: u32) -> i16 {
@@ -80,7 +116,7 @@ impl Recorder {
             // Supprimer les notes terminées
             for &i in notes_to_remove.iter().rev() {
                 notes.remove(i);
-                println!("Note terminée et supprimée du Recorder.");
+                log::trace!("Note terminée et supprimée du Recorder.");
             }
         }
 
@@ -91,6 +127,34 @@ impl Recorder {
         (mixed_sample * i16::MAX as f32) as i16
     }
 
+    /// Rend d'un coup une liste de notes dans le fichier WAV, chacune placée selon ses instants
+    /// de début et de fin par rapport à `origin`, puis finalise le fichier.
+    /// À utiliser à la place de `start_recording`, par exemple après quantification
+    pub fn render(&self, notes: &[NoteSpan], origin: Instant) -> Result<(), Box<dyn Error>> {
+        let sample_rate = self.sample_rate as f32;
+        let position = |at: Instant| {
+            (at.saturating_duration_since(origin).as_secs_f32() * sample_rate) as usize
+        };
+        let length = notes.iter().map(|note| position(note.end)).max().unwrap_or(0);
+
+        let mut mix = vec![0.0f32; length];
+        for note in notes {
+            let amplitude = note.velocity as f32 / 127.0;
+            let start = position(note.start);
+            for (n, sample) in mix[start..position(note.end)].iter_mut().enumerate() {
+                *sample += amplitude * (2.0 * PI * note.frequency * n as f32 / sample_rate).sin();
+            }
+        }
+
+        let mut writer_guard = self.writer.lock().unwrap();
+        let mut wav_writer = writer_guard.take().ok_or("Fichier WAV déjà finalisé")?;
+        for sample in mix {
+            wav_writer.write_sample((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)?;
+        }
+        wav_writer.finalize()?;
+        Ok(())
+    }
+
     pub fn start_recording(&self) {
         let writer = Arc::clone(&self.writer);
         let active_notes = Arc::clone(&self.active_notes);
@@ -98,8 +162,8 @@ impl Recorder {
 
         std::thread::spawn(move || {
             // Définir une durée d'enregistrement (par exemple, 1 minute pour le test)
-            let total_samples = sample_rate * 60 * 1; // 1 minute
-            println!("Début de l'enregistrement pour {} échantillons.", total_samples);
+            let total_samples = sample_rate * 60; // 1 minute
+            log::info!("Début de l'enregistrement pour {} échantillons.", total_samples);
             for i in 0..total_samples {
                 let sample = Recorder::generate_sample(&active_notes, sample_rate);
 
@@ -108,7 +172,7 @@ impl Recorder {
                     let mut writer_guard = writer.lock().unwrap();
                     if let Some(ref mut wav_writer) = *writer_guard {
                         if let Err(e) = wav_writer.write_sample(sample) {
-                            eprintln!("Erreur lors de l'écriture du fichier WAV: {}", e);
+                            log::error!("Erreur lors de l'écriture du fichier WAV: {}", e);
                             break;
                         }
                     }
@@ -116,7 +180,7 @@ impl Recorder {
 
                 // Log pour chaque seconde d'échantillons écrits
                 if i % sample_rate == 0 && i != 0 {
-                    println!("Échantillons écrits : {}", i);
+                    log::debug!("Échantillons écrits : {}", i);
                 }
             }
 
@@ -125,14 +189,14 @@ impl Recorder {
                 let mut writer_guard = writer.lock().unwrap();
                 if let Some(wav_writer) = writer_guard.take() { // Suppression de `mut`
                     if let Err(e) = wav_writer.finalize() {
-                        eprintln!("Erreur lors de la finalisation du fichier WAV: {}", e);
+                        log::error!("Erreur lors de la finalisation du fichier WAV: {}", e);
                     } else {
-                        println!("Fichier WAV finalisé avec succès.");
+                        log::info!("Fichier WAV finalisé avec succès.");
                     }
                 }
             }
 
-            println!("Enregistrement terminé.");
+            log::info!("Enregistrement terminé.");
         });
     }
 }
@@ -0,0 +1,155 @@
+// src/sinks.rs
+
+use crate::audio::quantize::{NoteSpan, Quantizer};
+use crate::events::{Event, EventSink, NoteEvent, NoteState};
+use crate::midi::transcription::MidiTranscriber;
+use crate::recorder::Recorder;
+use crate::utils;
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// Sortie MIDI : Note On / Note Off sur le canal du transcripteur
+impl EventSink for MidiTranscriber {
+    fn handle(&mut self, event: &Event) {
+        let Event::Note(note) = event else {
+            return;
+        };
+        match note.state {
+            NoteState::On => match self.note_on(note.note, note.velocity) {
+                Ok(sent) => {
+                    if let Some(report) = self.latency_report() {
+                        let mut timestamps = note.timestamps;
+                        timestamps.sent = Some(sent);
+                        report.record(&timestamps);
+                    }
+                }
+                Err(err) => log::error!("Erreur lors de la lecture de la note MIDI : {}", err),
+            },
+            NoteState::Off => {
+                if let Err(err) = self.note_off(note.note) {
+                    log::error!("Erreur lors de la lecture de la note MIDI : {}", err);
+                }
+            }
+        }
+    }
+}
+
+/// Enregistreur : synthèse des notes tenues dans le fichier WAV
+impl EventSink for Recorder {
+    fn handle(&mut self, event: &Event) {
+        let Event::Note(note) = event else {
+            return;
+        };
+        if let Some(frequency) = self.tuning().frequency(note.note) {
+            match note.state {
+                NoteState::On => self.start_note(frequency, note.velocity),
+                NoteState::Off => self.stop_note(frequency),
+            }
+        }
+    }
+}
+
+/// Enregistreur quantifié : collecte les notes complètes et les battements, puis à la fermeture
+/// du bus aligne les notes sur la grille du `Quantizer` et les rend dans le fichier WAV
+pub struct QuantizedRecorder {
+    recorder: Recorder,
+    quantizer: Quantizer,
+    /// Notes commencées, en attente de leur Note Off
+    open: HashMap<u8, NoteEvent>,
+    notes: Vec<NoteSpan>,
+}
+
+impl QuantizedRecorder {
+    /// `recorder` ne doit pas avoir été démarré avec `start_recording`
+    pub fn new(recorder: Recorder, quantizer: Quantizer) -> Self {
+        QuantizedRecorder {
+            recorder,
+            quantizer,
+            open: HashMap::new(),
+            notes: Vec::new(),
+        }
+    }
+
+    fn close(&mut self, started: NoteEvent, end: Instant) {
+        self.notes.push(NoteSpan {
+            note: started.note,
+            frequency: self
+                .recorder
+                .tuning()
+                .frequency(started.note)
+                .unwrap_or(started.frequency),
+            velocity: started.velocity,
+            start: started.timestamps.captured,
+            end,
+        });
+    }
+}
+
+impl EventSink for QuantizedRecorder {
+    fn handle(&mut self, event: &Event) {
+        match event {
+            Event::Beat(beat) => self.quantizer.add_beat(beat.timestamps.captured, beat.bpm),
+            Event::Note(note) => match note.state {
+                NoteState::On => {
+                    if let Some(previous) = self.open.insert(note.note, note.clone()) {
+                        self.close(previous, note.timestamps.captured);
+                    }
+                }
+                NoteState::Off => {
+                    if let Some(started) = self.open.remove(&note.note) {
+                        self.close(started, note.timestamps.captured);
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+
+    fn finish(&mut self) {
+        let now = Instant::now();
+        let open: Vec<NoteEvent> = self.open.drain().map(|(_, started)| started).collect();
+        for started in open {
+            self.close(started, now);
+        }
+
+        // Dans l'ordre de début : à tempo fixe, la grille est calée sur la première note
+        self.notes.sort_by_key(|note| note.start);
+        for note in &mut self.notes {
+            self.quantizer.quantize_note(note);
+        }
+        let Some(origin) = self.notes.iter().map(|note| note.start).min() else {
+            return;
+        };
+        if let Err(err) = self.recorder.render(&self.notes, origin) {
+            log::error!("Erreur lors du rendu des notes quantifiées : {}", err);
+        }
+    }
+}
+
+/// Journal des notes, tonalités et accords détectés sur la sortie standard
+#[derive(Debug, Default)]
+pub struct LogSink;
+
+impl EventSink for LogSink {
+    fn handle(&mut self, event: &Event) {
+        match event {
+            Event::Note(note) if note.state == NoteState::On => log::info!(
+                "Note détectée : {} (MIDI {}, {:.2} Hz, {:+.0} cents, vélocité {})",
+                utils::midi_note_number_to_name(note.note),
+                note.note,
+                note.frequency,
+                note.cents,
+                note.velocity
+            ),
+            Event::Key(key) => log::info!(
+                "Tonalité : {} (corrélation {:.2})",
+                key.key, key.key.correlation
+            ),
+            Event::Chord(chord) => match chord.chord {
+                Some(chord) => log::info!("Accord : {}", chord),
+                None => log::info!("Accord : aucun"),
+            },
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,756 @@
+// src/tui/dashboard.rs
+
+use crate::audio::chord::Chord;
+use crate::audio::controls::{LiveParams, ProcessorControls};
+use crate::audio::detector::Pitch;
+use crate::audio::ring::ChannelMetrics;
+use crate::events::{Event, EventSink, NoteState};
+use crate::leds::color::note_color;
+use crate::leds::spectrum::SpectrumBands;
+use crate::midi::transcription::MidiStatus;
+use crate::tui::terminal::{Key, RawTerminal};
+use crate::tuning::Tuning;
+use crate::utils;
+use std::collections::VecDeque;
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Images par seconde du tableau de bord
+const REFRESH_RATE: u32 = 20;
+
+/// Bas de l'échelle des niveaux
+const FLOOR_DB: f32 = -60.0;
+
+/// Retombée de l'indicateur de crête, en dB par trame d'analyse
+const PEAK_FALL_DB: f32 = 0.3;
+
+/// Durée visible dans le piano roll
+const ROLL_DURATION: Duration = Duration::from_secs(8);
+
+/// Hauteur du spectre, en lignes
+const SPECTRUM_HEIGHT: usize = 8;
+
+/// Fréquences couvertes par le spectre
+const SPECTRUM_RANGE: (f32, f32) = (40.0, 8000.0);
+
+/// Huitièmes de case pour les barres du spectre
+const BARS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Paramètre réglable au clavier
+#[derive(Debug, Clone, Copy)]
+enum Param {
+    GateOpen,
+    GateClose,
+    MinConfidence,
+    MinFrames,
+    MinFrequency,
+    MaxFrequency,
+}
+
+const PARAMS: [Param; 6] = [
+    Param::GateOpen,
+    Param::GateClose,
+    Param::MinConfidence,
+    Param::MinFrames,
+    Param::MinFrequency,
+    Param::MaxFrequency,
+];
+
+impl Param {
+    fn label(self) -> &'static str {
+        match self {
+            Param::GateOpen => "Ouverture du gate",
+            Param::GateClose => "Fermeture du gate",
+            Param::MinConfidence => "Confiance minimale",
+            Param::MinFrames => "Trames avant Note On",
+            Param::MinFrequency => "Fréquence minimale",
+            Param::MaxFrequency => "Fréquence maximale",
+        }
+    }
+
+    fn value(self, params: &LiveParams) -> String {
+        match self {
+            Param::GateOpen => format!("{:.0} dBFS", params.gate_open_db),
+            Param::GateClose => format!("{:.0} dBFS", params.gate_close_db),
+            Param::MinConfidence => format!("{:.2}", params.min_confidence),
+            Param::MinFrames => params.min_frames.to_string(),
+            Param::MinFrequency => format!("{:.1} Hz", params.min_frequency),
+            Param::MaxFrequency => format!("{:.1} Hz", params.max_frequency),
+        }
+    }
+
+    /// Un cran vers le haut (`step` = 1) ou vers le bas (`step` = -1) ; les fréquences
+    /// avancent d'un demi-ton
+    fn adjust(self, params: &mut LiveParams, step: f32) {
+        match self {
+            Param::GateOpen => params.gate_open_db += step,
+            Param::GateClose => params.gate_close_db += step,
+            Param::MinConfidence => params.min_confidence += step * 0.01,
+            Param::MinFrames => {
+                params.min_frames = (params.min_frames as f32 + step).max(1.0) as usize
+            }
+            Param::MinFrequency => params.min_frequency *= 2f32.powf(step / 12.0),
+            Param::MaxFrequency => params.max_frequency *= 2f32.powf(step / 12.0),
+        }
+    }
+}
+
+/// Note affichée dans le piano roll
+struct RollNote {
+    note: u8,
+    start: Instant,
+    /// `None` tant que la note est tenue
+    end: Option<Instant>,
+}
+
+/// Dernières valeurs reçues du bus
+struct Monitor {
+    level_db: f32,
+    peak_db: f32,
+    pitch: Option<Pitch>,
+    /// Amplitudes, écart entre bins et gain cohérent de la fenêtre
+    spectrum: Option<(Arc<[f32]>, f32, f32)>,
+    roll: VecDeque<RollNote>,
+    key: Option<String>,
+    chord: Option<Chord>,
+    bpm: Option<f32>,
+    frames: u64,
+}
+
+impl Monitor {
+    fn new() -> Self {
+        Monitor {
+            level_db: FLOOR_DB,
+            peak_db: FLOOR_DB,
+            pitch: None,
+            spectrum: None,
+            roll: VecDeque::new(),
+            key: None,
+            chord: None,
+            bpm: None,
+            frames: 0,
+        }
+    }
+
+    fn update(&mut self, event: &Event) {
+        match event {
+            Event::Pitch(frame) => {
+                self.level_db = frame.level_db.max(FLOOR_DB);
+                self.peak_db = self.level_db.max(self.peak_db - PEAK_FALL_DB);
+                self.pitch = frame.pitch;
+                self.frames += 1;
+            }
+            Event::Spectrum(spectrum) => {
+                self.spectrum = Some((
+                    Arc::clone(&spectrum.magnitudes),
+                    spectrum.bin_hz,
+                    spectrum.window_gain,
+                ));
+            }
+            Event::Note(note) => {
+                let at = note.timestamps.captured;
+                if let Some(open) = self
+                    .roll
+                    .iter_mut()
+                    .rev()
+                    .find(|n| n.note == note.note && n.end.is_none())
+                {
+                    open.end = Some(at);
+                }
+                if note.state == NoteState::On {
+                    self.roll.push_back(RollNote {
+                        note: note.note,
+                        start: at,
+                        end: None,
+                    });
+                }
+                // Oublier les notes sorties de la fenêtre
+                let horizon = Instant::now().checked_sub(ROLL_DURATION);
+                self.roll
+                    .retain(|n| n.end.is_none() || horizon.is_none_or(|h| n.end > Some(h)));
+            }
+            Event::Key(key) => self.key = Some(key.key.to_string()),
+            Event::Chord(chord) => self.chord = chord.chord,
+            Event::Beat(beat) => self.bpm = Some(beat.bpm),
+            Event::Chroma(_) => {}
+        }
+    }
+}
+
+/// Contenu de l'écran, reconstruit à chaque image
+struct View {
+    controls: Arc<ProcessorControls>,
+    midi: Option<Arc<MidiStatus>>,
+    channels: Vec<(String, Arc<ChannelMetrics>)>,
+    monitor: Arc<Mutex<Monitor>>,
+    bands: SpectrumBands,
+    selected: usize,
+    tuning: Tuning,
+}
+
+impl View {
+    fn key(&mut self, key: Key) {
+        let step = match key {
+            Key::Up => {
+                self.selected = (self.selected + PARAMS.len() - 1) % PARAMS.len();
+                return;
+            }
+            Key::Down => {
+                self.selected = (self.selected + 1) % PARAMS.len();
+                return;
+            }
+            Key::Right | Key::Char('+') => 1.0,
+            Key::Left | Key::Char('-') => -1.0,
+            _ => return,
+        };
+        let param = PARAMS[self.selected];
+        self.controls.update(|params| param.adjust(params, step));
+    }
+
+    fn render(&self, width: usize, height: usize) -> Vec<String> {
+        let monitor = self.monitor.lock().unwrap();
+        let params = self.controls.get();
+        let inner = width.saturating_sub(2);
+
+        let mut header = vec![
+            fit(
+                " \x1b[1mTranscription temps réel\x1b[0m   ↑↓ paramètre   ←→ réglage   q quitter",
+                width,
+            ),
+            String::new(),
+            fit(
+                &format!(
+                    " Niveau   {} {:6.1} dBFS  crête {:6.1}",
+                    meter(
+                        monitor.level_db,
+                        monitor.peak_db,
+                        params.gate_open_db,
+                        inner.saturating_sub(36)
+                    ),
+                    monitor.level_db,
+                    monitor.peak_db
+                ),
+                width,
+            ),
+            fit(&pitch_line(monitor.pitch, &self.tuning), width),
+            fit(&harmony_line(&monitor), width),
+            String::new(),
+            fit(
+                &format!(
+                    " Spectre {:.0} Hz – {:.0} kHz",
+                    SPECTRUM_RANGE.0,
+                    SPECTRUM_RANGE.1 / 1000.0
+                ),
+                width,
+            ),
+        ];
+        header.extend(self.spectrum(&monitor, inner));
+
+        let mut footer = vec![String::new(), fit(&self.midi_line(), width)];
+        footer.push(fit(
+            &format!(" Trames   {} analysées", monitor.frames),
+            width,
+        ));
+        for (name, metrics) in &self.channels {
+            let snapshot = metrics.snapshot();
+            footer.push(fit(
+                &format!(
+                    "   {:<14} {} perdus, {} débordements, remplissage max {}",
+                    name, snapshot.dropped, snapshot.overruns, snapshot.max_depth
+                ),
+                width,
+            ));
+        }
+        footer.push(String::new());
+        for (i, param) in PARAMS.iter().enumerate() {
+            let line = format!(" {:<24} {}", param.label(), param.value(&params));
+            footer.push(if i == self.selected {
+                format!("\x1b[7m▶{}\x1b[0m", fit(&line, width.saturating_sub(1)))
+            } else {
+                format!(" {}", fit(&line, width.saturating_sub(1)))
+            });
+        }
+
+        // Le piano roll prend la place restante
+        let roll_height = height.saturating_sub(header.len() + footer.len() + 2);
+        let mut lines = header;
+        if roll_height > 0 {
+            lines.push(String::new());
+            lines.push(fit(
+                &format!(" Piano roll ({} s)", ROLL_DURATION.as_secs()),
+                width,
+            ));
+            lines.extend(piano_roll(&monitor.roll, inner, roll_height));
+        }
+        lines.extend(footer);
+        lines.truncate(height);
+        lines
+    }
+
+    /// Barres du spectre, une colonne par bande logarithmique
+    fn spectrum(&self, monitor: &Monitor, width: usize) -> Vec<String> {
+        let levels = match &monitor.spectrum {
+            Some((magnitudes, bin_hz, gain)) => {
+                self.bands.levels(magnitudes, *bin_hz, *gain, width)
+            }
+            None => vec![0.0; width],
+        };
+        (0..SPECTRUM_HEIGHT)
+            .rev()
+            .map(|row| {
+                let bars: String = levels
+                    .iter()
+                    .map(|level| {
+                        let fill = (level * SPECTRUM_HEIGHT as f32 - row as f32).clamp(0.0, 1.0);
+                        BARS[(fill * 8.0).round() as usize]
+                    })
+                    .collect();
+                format!(" \x1b[36m{}\x1b[0m", bars)
+            })
+            .collect()
+    }
+
+    fn midi_line(&self) -> String {
+        match &self.midi {
+            Some(status) => format!(
+                " MIDI     port « {} », {} messages, {} erreurs",
+                status.port,
+                status.sent(),
+                status.errors()
+            ),
+            None => " MIDI     aucune sortie".to_string(),
+        }
+    }
+}
+
+/// Barre de niveau colorée (vert, jaune au-dessus de -18 dB, rouge au-dessus de -6 dB),
+/// avec la crête `▌` et le seuil d'ouverture du gate `│`
+fn meter(level_db: f32, peak_db: f32, gate_db: f32, width: usize) -> String {
+    let position =
+        |db: f32| (((db - FLOOR_DB) / -FLOOR_DB).clamp(0.0, 1.0) * width as f32).round() as usize;
+    let (level, peak, gate) = (position(level_db), position(peak_db), position(gate_db));
+    let mut bar = String::from("[");
+    for i in 0..width {
+        let db = FLOOR_DB + (i as f32 + 0.5) / width as f32 * -FLOOR_DB;
+        let color = if db > -6.0 {
+            "\x1b[31m"
+        } else if db > -18.0 {
+            "\x1b[33m"
+        } else {
+            "\x1b[32m"
+        };
+        let cell = if i < level {
+            '█'
+        } else if i + 1 == peak.max(1) && peak > level {
+            '▌'
+        } else if i == gate.min(width - 1) {
+            '│'
+        } else {
+            ' '
+        };
+        bar.push_str(color);
+        bar.push(cell);
+    }
+    bar.push_str("\x1b[0m]");
+    bar
+}
+
+/// Note la plus proche et écart en cents dans l'accord du pipeline
+fn pitch_line(pitch: Option<Pitch>, tuning: &Tuning) -> String {
+    match pitch {
+        Some(pitch) => match tuning.note(pitch.frequency) {
+            Some(note) => format!(
+                " Hauteur  {:<4} {:8.2} Hz {:+6.1} cents   confiance {:.2}",
+                utils::midi_note_number_to_name(note),
+                pitch.frequency,
+                tuning.cents(pitch.frequency, note),
+                pitch.confidence
+            ),
+            None => format!(" Hauteur  {:8.2} Hz", pitch.frequency),
+        },
+        None => " Hauteur  —".to_string(),
+    }
+}
+
+fn harmony_line(monitor: &Monitor) -> String {
+    let chord = monitor
+        .chord
+        .map(|chord| chord.to_string())
+        .unwrap_or_else(|| "—".to_string());
+    let tempo = monitor
+        .bpm
+        .map(|bpm| format!("{:.0} BPM", bpm))
+        .unwrap_or_else(|| "—".to_string());
+    format!(
+        " Tonalité {}   accord {}   tempo {}",
+        monitor.key.as_deref().unwrap_or("—"),
+        chord,
+        tempo
+    )
+}
+
+/// Notes des `ROLL_DURATION` dernières secondes, le présent à droite, les aigus en haut
+fn piano_roll(notes: &VecDeque<RollNote>, width: usize, height: usize) -> Vec<String> {
+    const LABEL: usize = 5;
+    let columns = width.saturating_sub(LABEL);
+    let now = Instant::now();
+    let column = |at: Instant| {
+        let age = now.saturating_duration_since(at).as_secs_f32();
+        let position = 1.0 - age / ROLL_DURATION.as_secs_f32();
+        (position.max(0.0) * columns as f32) as usize
+    };
+
+    // Plage affichée : les notes visibles, centrées, ou autour du C4 à défaut
+    let (low, high) = notes
+        .iter()
+        .fold(None, |range: Option<(u8, u8)>, n| match range {
+            Some((low, high)) => Some((low.min(n.note), high.max(n.note))),
+            None => Some((n.note, n.note)),
+        })
+        .unwrap_or((60, 60));
+    let center = (low as usize + high as usize) / 2;
+    let top = (center + height / 2).max(height.saturating_sub(1)).min(127);
+
+    (0..height)
+        .map(|row| {
+            let Some(note) = top.checked_sub(row).map(|n| n as u8) else {
+                return String::new();
+            };
+            let mut cells: Vec<Option<u8>> = vec![None; columns];
+            for roll_note in notes.iter().filter(|n| n.note == note) {
+                let start = column(roll_note.start);
+                let end = roll_note
+                    .end
+                    .map_or(columns, |end| column(end).max(start + 1));
+                for cell in cells.iter_mut().take(end.min(columns)).skip(start) {
+                    *cell = Some(note);
+                }
+            }
+            let mut line = format!(" {:>3} ", utils::midi_note_number_to_name(note));
+            for cell in cells {
+                match cell {
+                    Some(note) => {
+                        let color = note_color(note);
+                        line.push_str(&format!("\x1b[38;2;{};{};{}m█", color.r, color.g, color.b));
+                    }
+                    None if note % 12 == 0 => line.push_str("\x1b[2m·"),
+                    None => line.push_str("\x1b[0m "),
+                }
+            }
+            line.push_str("\x1b[0m");
+            line
+        })
+        .collect()
+}
+
+/// Tronque une ligne à `width` caractères visibles ; les séquences d'échappement ANSI
+/// (`ESC [ … lettre`) ne comptent pas et sont toutes gardées, y compris les remises à zéro
+/// des attributs situées après la coupure
+fn fit(line: &str, width: usize) -> String {
+    let mut fitted = String::with_capacity(line.len());
+    let mut visible = 0;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            fitted.push(c);
+            if let Some(open) = chars.next() {
+                fitted.push(open);
+                if open == '[' {
+                    for c in chars.by_ref() {
+                        fitted.push(c);
+                        if ('@'..='~').contains(&c) {
+                            break;
+                        }
+                    }
+                }
+            }
+        } else if visible < width {
+            fitted.push(c);
+            visible += 1;
+        }
+    }
+    fitted
+}
+
+/// Tableau de bord plein écran : niveau d'entrée, spectre, hauteur et note courantes,
+/// piano roll des notes détectées, état de la sortie MIDI et pertes des files, avec
+/// réglage au clavier des paramètres du détecteur et du gate
+pub struct Dashboard {
+    controls: Arc<ProcessorControls>,
+    midi: Option<Arc<MidiStatus>>,
+    channels: Vec<(String, Arc<ChannelMetrics>)>,
+    tuning: Tuning,
+    quit: Option<Box<dyn FnOnce() + Send>>,
+    monitor: Arc<Mutex<Monitor>>,
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Dashboard {
+    /// Les réglages clavier sont appliqués via `controls`, à partager avec
+    /// `AudioProcessor::with_controls`
+    pub fn new(controls: Arc<ProcessorControls>) -> Self {
+        Dashboard {
+            controls,
+            midi: None,
+            channels: Vec::new(),
+            tuning: Tuning::default(),
+            quit: None,
+            monitor: Arc::new(Mutex::new(Monitor::new())),
+            running: Arc::new(AtomicBool::new(true)),
+            thread: None,
+        }
+    }
+
+    /// Affiche le port et les compteurs de la sortie MIDI
+    pub fn with_midi(mut self, status: Arc<MidiStatus>) -> Self {
+        self.midi = Some(status);
+        self
+    }
+
+    /// Accord dans lequel la hauteur courante est nommée, celui du pipeline
+    pub fn with_tuning(mut self, tuning: Tuning) -> Self {
+        self.tuning = tuning;
+        self
+    }
+
+    /// Appelé quand l'utilisateur quitte le tableau de bord, pour arrêter le programme
+    /// proprement (en général `CaptureStop::stop`)
+    pub fn with_quit(mut self, quit: impl FnOnce() + Send + 'static) -> Self {
+        self.quit = Some(Box::new(quit));
+        self
+    }
+
+    /// Affiche les blocs perdus d'une file (canal de capture, abonnés du bus)
+    pub fn with_channel(mut self, name: &str, metrics: Arc<ChannelMetrics>) -> Self {
+        self.channels.push((name.to_string(), metrics));
+        self
+    }
+
+    /// Passe le terminal en plein écran et démarre le thread d'affichage. `q`, `Échap` ou
+    /// Ctrl+C restaurent le terminal puis appellent la fermeture de `with_quit`
+    pub fn start(mut self) -> io::Result<Self> {
+        let terminal = RawTerminal::enter()?;
+        let view = View {
+            controls: Arc::clone(&self.controls),
+            midi: self.midi.take(),
+            channels: std::mem::take(&mut self.channels),
+            monitor: Arc::clone(&self.monitor),
+            bands: SpectrumBands::new(SPECTRUM_RANGE.0, SPECTRUM_RANGE.1),
+            selected: 0,
+            tuning: self.tuning.clone(),
+        };
+        let running = Arc::clone(&self.running);
+        let quit = self.quit.take();
+        self.thread = Some(
+            thread::Builder::new()
+                .name("tableau-de-bord".to_string())
+                .spawn(move || {
+                    if let Err(err) = run(view, terminal, &running, quit) {
+                        log::error!("Erreur du tableau de bord : {}", err);
+                    }
+                })?,
+        );
+        Ok(self)
+    }
+
+    /// Arrête l'affichage et restaure le terminal
+    pub fn stop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            if thread.join().is_err() {
+                log::error!("Le thread du tableau de bord a paniqué");
+            }
+        }
+    }
+}
+
+/// Boucle d'affichage et de lecture du clavier ; le terminal est restauré en sortant
+fn run(
+    mut view: View,
+    terminal: RawTerminal,
+    running: &AtomicBool,
+    quit: Option<Box<dyn FnOnce() + Send>>,
+) -> io::Result<()> {
+    let period = Duration::from_secs(1) / REFRESH_RATE;
+    let mut next_draw = Instant::now();
+    while running.load(Ordering::Relaxed) {
+        if Instant::now() >= next_draw {
+            let (width, height) = terminal.size();
+            terminal.draw(&view.render(width, height))?;
+            next_draw += period;
+        }
+        for key in terminal.read_keys(next_draw.saturating_duration_since(Instant::now()))? {
+            if key == Key::Quit {
+                drop(terminal);
+                if let Some(quit) = quit {
+                    quit();
+                }
+                return Ok(());
+            }
+            view.key(key);
+        }
+    }
+    Ok(())
+}
+
+impl EventSink for Dashboard {
+    fn handle(&mut self, event: &Event) {
+        self.monitor.lock().unwrap().update(event);
+    }
+
+    fn finish(&mut self) {
+        self.stop();
+    }
+}
+
+impl Drop for Dashboard {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Texte affiché, sans les séquences d'échappement
+    fn visible(line: &str) -> String {
+        let mut text = String::new();
+        let mut escape = false;
+        for c in line.chars() {
+            match c {
+                '\x1b' => escape = true,
+                c if escape => escape = c == '[' || !('@'..='~').contains(&c),
+                c => text.push(c),
+            }
+        }
+        text
+    }
+
+    #[test]
+    fn fit_counts_visible_characters() {
+        let line = " \x1b[1mTitre\x1b[0m suite";
+        assert_eq!(fit(line, 100), line);
+        let cut = fit(line, 4);
+        assert_eq!(visible(&cut), " Tit");
+        // La remise à zéro après la coupure est conservée
+        assert!(cut.ends_with("\x1b[0m"), "{:?}", cut);
+        assert_eq!(visible(&fit(line, 8)), " Titre s");
+        assert_eq!(fit("aé€", 2), "aé");
+    }
+
+    #[test]
+    fn meter_marks_level_peak_and_gate() {
+        let bar = meter(-50.0, -12.0, -45.0, 60);
+        let cells: Vec<char> = visible(&bar).chars().collect();
+        assert_eq!(cells.len(), 62);
+        assert_eq!((cells[0], cells[61]), ('[', ']'));
+        let cells = &cells[1..61];
+        assert!(cells[..10].iter().all(|&c| c == '█'));
+        assert_eq!(cells[10], ' ');
+        assert_eq!(cells[15], '│');
+        assert_eq!(cells[47], '▌');
+        assert_eq!(cells.iter().filter(|&&c| c != ' ').count(), 12);
+        // Rouge au-dessus de -6 dB
+        assert!(bar.ends_with("\x1b[31m \x1b[0m]"), "{:?}", bar);
+
+        let full = visible(&meter(0.0, 0.0, -45.0, 20));
+        assert_eq!(full, format!("[{}]", "█".repeat(20)));
+    }
+
+    #[test]
+    fn piano_roll_places_notes_in_time() {
+        let now = Instant::now();
+        let notes = VecDeque::from([
+            RollNote {
+                note: 64,
+                start: now - Duration::from_millis(6100),
+                end: Some(now - Duration::from_millis(5100)),
+            },
+            RollNote {
+                note: 60,
+                start: now - Duration::from_millis(1900),
+                end: None,
+            },
+        ]);
+        let rows: Vec<String> = piano_roll(&notes, 45, 5)
+            .iter()
+            .map(|row| visible(row))
+            .collect();
+        assert_eq!(rows.len(), 5);
+        assert!(
+            rows.iter().all(|row| row.chars().count() == 45),
+            "{:?}",
+            rows
+        );
+
+        // Aigus en haut, centrés sur les notes visibles : E4 à C4
+        let c4 = &rows[4];
+        assert!(c4.starts_with("  C4 "), "{:?}", c4);
+        let cells: Vec<char> = c4.chars().skip(5).collect();
+        assert!(cells[..30].iter().all(|&c| c == '·'));
+        assert!(cells[30..].iter().all(|&c| c == '█'));
+
+        let e4: Vec<char> = rows[0].chars().skip(5).collect();
+        assert!(rows[0].starts_with("  E4 "), "{:?}", rows[0]);
+        let held: Vec<usize> = (0..40).filter(|&i| e4[i] == '█').collect();
+        assert_eq!(held, (9..14).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn params_adjust_by_one_step() {
+        let mut params = LiveParams {
+            gate_open_db: -45.0,
+            gate_close_db: -50.0,
+            min_confidence: 0.5,
+            min_frames: 1,
+            min_frequency: 110.0,
+            max_frequency: 880.0,
+        };
+        Param::GateOpen.adjust(&mut params, 1.0);
+        Param::GateClose.adjust(&mut params, -1.0);
+        Param::MinConfidence.adjust(&mut params, -1.0);
+        Param::MinFrames.adjust(&mut params, -1.0);
+        assert_eq!(params.gate_open_db, -44.0);
+        assert_eq!(params.gate_close_db, -51.0);
+        assert!((params.min_confidence - 0.49).abs() < 1e-6);
+        assert_eq!(params.min_frames, 1);
+        Param::MinFrames.adjust(&mut params, 1.0);
+        assert_eq!(params.min_frames, 2);
+
+        // Un demi-ton par cran : douze crans font une octave
+        for _ in 0..12 {
+            Param::MinFrequency.adjust(&mut params, 1.0);
+            Param::MaxFrequency.adjust(&mut params, -1.0);
+        }
+        assert!((params.min_frequency - 220.0).abs() < 0.01);
+        assert!((params.max_frequency - 440.0).abs() < 0.01);
+        assert_eq!(Param::MaxFrequency.value(&params), "440.0 Hz");
+    }
+
+    #[test]
+    fn pitch_line_follows_the_tuning() {
+        let pitch = Some(Pitch {
+            frequency: 415.0,
+            confidence: 0.9,
+        });
+        let baroque = pitch_line(pitch, &Tuning::equal(415.0));
+        assert!(
+            baroque.contains("A4") && baroque.contains("+0.0 cents"),
+            "{}",
+            baroque
+        );
+        // Le même La baroque est un Sol# 4 un peu bas en La 440
+        let modern = pitch_line(pitch, &Tuning::default());
+        assert!(
+            modern.contains(&utils::midi_note_number_to_name(68)),
+            "{}",
+            modern
+        );
+        assert!(modern.contains("-1.3 cents"), "{}", modern);
+    }
+}
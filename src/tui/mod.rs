@@ -0,0 +1,10 @@
+// src/tui/mod.rs
+
+//! Tableau de bord plein écran dans le terminal : suivi en direct du pipeline et réglage
+//! au clavier des paramètres de détection
+
+pub mod dashboard;
+pub mod terminal;
+
+pub use dashboard::Dashboard;
+pub use terminal::{Key, RawTerminal};
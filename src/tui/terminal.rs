@@ -0,0 +1,151 @@
+// src/tui/terminal.rs
+
+use std::io::{self, Write};
+use std::mem::MaybeUninit;
+use std::time::Duration;
+
+/// Touche lue au clavier
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    Char(char),
+    /// `q`, `Échap` ou Ctrl+C
+    Quit,
+}
+
+/// Terminal en mode brut sur l'écran alternatif : pas d'écho, touches lues une à une,
+/// curseur masqué ; l'état d'origine est restauré à la destruction
+pub struct RawTerminal {
+    original: libc::termios,
+}
+
+impl RawTerminal {
+    pub fn enter() -> io::Result<Self> {
+        let original = unsafe {
+            let mut termios = MaybeUninit::<libc::termios>::uninit();
+            if libc::tcgetattr(libc::STDIN_FILENO, termios.as_mut_ptr()) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            termios.assume_init()
+        };
+
+        // Ctrl+C arrive comme une touche, pour toujours restaurer le terminal avant de quitter
+        let mut raw = original;
+        raw.c_lflag &= !(libc::ICANON | libc::ECHO | libc::ISIG);
+        raw.c_cc[libc::VMIN] = 0;
+        raw.c_cc[libc::VTIME] = 0;
+        if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let mut stdout = io::stdout();
+        stdout.write_all(b"\x1b[?1049h\x1b[?25l\x1b[2J")?;
+        stdout.flush()?;
+        Ok(RawTerminal { original })
+    }
+
+    /// Colonnes et lignes du terminal (80×24 si la taille est inconnue)
+    pub fn size(&self) -> (usize, usize) {
+        let mut size = MaybeUninit::<libc::winsize>::zeroed();
+        let result =
+            unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, size.as_mut_ptr()) };
+        let size = unsafe { size.assume_init() };
+        if result != 0 || size.ws_col == 0 || size.ws_row == 0 {
+            (80, 24)
+        } else {
+            (size.ws_col as usize, size.ws_row as usize)
+        }
+    }
+
+    /// Attend au plus `timeout` et retourne les touches tapées (souvent une seule)
+    pub fn read_keys(&self, timeout: Duration) -> io::Result<Vec<Key>> {
+        let mut poll = libc::pollfd {
+            fd: libc::STDIN_FILENO,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        let millis = timeout.as_millis().min(i32::MAX as u128) as i32;
+        match unsafe { libc::poll(&mut poll, 1, millis) } {
+            -1 => {
+                let err = io::Error::last_os_error();
+                return match err.kind() {
+                    io::ErrorKind::Interrupted => Ok(Vec::new()),
+                    _ => Err(err),
+                };
+            }
+            0 => return Ok(Vec::new()),
+            _ => {}
+        }
+
+        let mut buffer = [0u8; 64];
+        let count = unsafe {
+            libc::read(
+                libc::STDIN_FILENO,
+                buffer.as_mut_ptr() as *mut libc::c_void,
+                buffer.len(),
+            )
+        };
+        if count < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let mut bytes = &buffer[..count as usize];
+        let mut keys = Vec::new();
+        while !bytes.is_empty() {
+            let (key, length) = parse_key(bytes);
+            keys.extend(key);
+            bytes = &bytes[length..];
+        }
+        Ok(keys)
+    }
+
+    /// Remplace tout l'écran par `lines`, chacune effacée jusqu'au bout de la ligne
+    pub fn draw(&self, lines: &[String]) -> io::Result<()> {
+        let mut screen = String::from("\x1b[H");
+        for (i, line) in lines.iter().enumerate() {
+            if i > 0 {
+                screen.push_str("\r\n");
+            }
+            screen.push_str(line);
+            screen.push_str("\x1b[0m\x1b[K");
+        }
+        screen.push_str("\x1b[J");
+        let mut stdout = io::stdout();
+        stdout.write_all(screen.as_bytes())?;
+        stdout.flush()
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let mut stdout = io::stdout();
+        let _ = stdout.write_all(b"\x1b[0m\x1b[?25h\x1b[?1049l");
+        let _ = stdout.flush();
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.original);
+        }
+    }
+}
+
+/// Décode la première touche de `bytes`, y compris les séquences des flèches (`Échap [ A`...),
+/// et retourne le nombre d'octets consommés
+fn parse_key(bytes: &[u8]) -> (Option<Key>, usize) {
+    match bytes {
+        [0x1b, b'[', b'A', ..] => (Some(Key::Up), 3),
+        [0x1b, b'[', b'B', ..] => (Some(Key::Down), 3),
+        [0x1b, b'[', b'C', ..] => (Some(Key::Right), 3),
+        [0x1b, b'[', b'D', ..] => (Some(Key::Left), 3),
+        // Autre séquence d'échappement : ignorée jusqu'à sa lettre finale
+        [0x1b, b'[', rest @ ..] => {
+            let length = rest
+                .iter()
+                .position(|b| b.is_ascii_alphabetic() || *b == b'~');
+            (None, length.map_or(bytes.len(), |i| i + 3))
+        }
+        [0x1b, ..] | [0x03, ..] | [b'q', ..] => (Some(Key::Quit), 1),
+        [byte, ..] if byte.is_ascii_graphic() => (Some(Key::Char(*byte as char)), 1),
+        _ => (None, 1),
+    }
+}
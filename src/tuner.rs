@@ -0,0 +1,392 @@
+// src/tuner.rs
+
+use crate::audio::detector::Pitch;
+use crate::events::{Event, EventSink};
+use crate::tuning::Tuning;
+use crate::utils;
+use std::io::{self, Write};
+use std::time::Instant;
+
+/// Vitesse de défilement du stroboscope, en cases par seconde et par cent d'écart
+const STROBE_SPEED: f32 = 0.5;
+
+/// Largeur de l'aiguille, de -50 à +50 cents
+const NEEDLE_WIDTH: usize = 41;
+
+/// Instrument accordé : chromatique (toutes les notes) ou cordes à vide d'un instrument
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instrument {
+    Chromatic,
+    /// Guitare 6 cordes : E2 A2 D3 G3 B3 E4
+    Guitar,
+    /// Basse 4 cordes : E1 A1 D2 G2
+    Bass,
+    /// Violon : G3 D4 A4 E5
+    Violin,
+}
+
+impl Instrument {
+    /// Notes MIDI des cordes à vide, vide pour le mode chromatique
+    pub fn strings(self) -> &'static [u8] {
+        match self {
+            Instrument::Chromatic => &[],
+            Instrument::Guitar => &[40, 45, 50, 55, 59, 64],
+            Instrument::Bass => &[28, 33, 38, 43],
+            Instrument::Violin => &[55, 62, 69, 76],
+        }
+    }
+
+    /// Nom utilisé en ligne de commande (`chromatic`, `guitar`, `bass`, `violin`)
+    pub fn parse(name: &str) -> Option<Instrument> {
+        match name {
+            "chromatic" => Some(Instrument::Chromatic),
+            "guitar" => Some(Instrument::Guitar),
+            "bass" => Some(Instrument::Bass),
+            "violin" => Some(Instrument::Violin),
+            _ => None,
+        }
+    }
+}
+
+/// Réglages de l'accordeur
+#[derive(Debug, Clone)]
+pub struct TunerConfig {
+    pub instrument: Instrument,
+    pub tuning: Tuning,
+    /// Lissage de l'écart en cents : 0 sans lissage, proche de 1 très lent
+    pub smoothing: f32,
+    /// Niveau (dBFS RMS) en dessous duquel l'accordeur n'affiche rien
+    pub gate_db: f32,
+    /// Écart maximal (cents) pour considérer la note juste
+    pub in_tune_cents: f32,
+    /// Trames consécutives dans la tolérance avant d'annoncer la note stable
+    pub stable_frames: usize,
+}
+
+impl Default for TunerConfig {
+    fn default() -> Self {
+        TunerConfig {
+            instrument: Instrument::Chromatic,
+            tuning: Tuning::default(),
+            smoothing: 0.7,
+            gate_db: -50.0,
+            in_tune_cents: 3.0,
+            stable_frames: 8,
+        }
+    }
+}
+
+/// Mesure de l'accordeur pour une trame
+#[derive(Debug, Clone, PartialEq)]
+pub struct TunerReading {
+    /// Note visée : la plus proche, ou la corde la plus proche pour un instrument
+    pub note: u8,
+    /// Numéro de corde (0 = la plus grave) pour un instrument
+    pub string: Option<usize>,
+    pub frequency: f32,
+    /// Écart lissé en cents par rapport à la note visée
+    pub cents: f32,
+    /// Note juste depuis assez de trames
+    pub stable: bool,
+    /// Phase du stroboscope, immobile quand la note est juste
+    pub strobe_phase: f32,
+}
+
+impl TunerReading {
+    /// Nom de la note et octave (ex. A4)
+    pub fn name(&self) -> String {
+        utils::midi_note_number_to_name(self.note)
+    }
+}
+
+/// Accordeur chromatique : note visée, écart en cents lissé, stabilité et stroboscope
+pub struct Tuner {
+    config: TunerConfig,
+    target: Option<u8>,
+    cents: f32,
+    in_tune_frames: usize,
+    strobe_phase: f32,
+    last_update: Option<Instant>,
+}
+
+impl Tuner {
+    pub fn new(config: TunerConfig) -> Self {
+        Tuner {
+            config,
+            target: None,
+            cents: 0.0,
+            in_tune_frames: 0,
+            strobe_phase: 0.0,
+            last_update: None,
+        }
+    }
+
+    pub fn config(&self) -> &TunerConfig {
+        &self.config
+    }
+
+    /// Note visée pour une fréquence : note la plus proche, ou corde à vide la plus proche
+    fn target(&self, frequency: f32) -> Option<(u8, Option<usize>)> {
+        let strings = self.config.instrument.strings();
+        if strings.is_empty() {
+            return self.config.tuning.note(frequency).map(|note| (note, None));
+        }
+        strings
+            .iter()
+            .enumerate()
+            .filter_map(|(index, &note)| {
+                let cents = self.config.tuning.cents(frequency, note);
+                self.config
+                    .tuning
+                    .frequency(note)
+                    .map(|_| (index, note, cents.abs()))
+            })
+            .min_by(|a, b| a.2.total_cmp(&b.2))
+            .map(|(index, note, _)| (note, Some(index)))
+    }
+
+    /// Met à jour l'accordeur avec la hauteur et le niveau d'une trame captée à `at`
+    pub fn update(
+        &mut self,
+        pitch: Option<Pitch>,
+        level_db: f32,
+        at: Instant,
+    ) -> Option<TunerReading> {
+        let elapsed = self
+            .last_update
+            .map_or(0.0, |last| at.saturating_duration_since(last).as_secs_f32());
+        self.last_update = Some(at);
+
+        let pitch = pitch.filter(|_| level_db >= self.config.gate_db);
+        let Some((pitch, (note, string))) =
+            pitch.and_then(|p| self.target(p.frequency).map(|target| (p, target)))
+        else {
+            self.target = None;
+            self.in_tune_frames = 0;
+            return None;
+        };
+
+        let cents = self.config.tuning.cents(pitch.frequency, note);
+        if self.target == Some(note) {
+            let smoothing = self.config.smoothing.clamp(0.0, 0.99);
+            self.cents = cents + (self.cents - cents) * smoothing;
+        } else {
+            // Nouvelle note : repartir de la mesure brute
+            self.target = Some(note);
+            self.cents = cents;
+            self.in_tune_frames = 0;
+        }
+
+        if self.cents.abs() <= self.config.in_tune_cents {
+            self.in_tune_frames += 1;
+        } else {
+            self.in_tune_frames = 0;
+        }
+        self.strobe_phase += self.cents * STROBE_SPEED * elapsed;
+
+        Some(TunerReading {
+            note,
+            string,
+            frequency: pitch.frequency,
+            cents: self.cents,
+            stable: self.in_tune_frames >= self.config.stable_frames,
+            strobe_phase: self.strobe_phase,
+        })
+    }
+}
+
+/// Affichage de l'accordeur sur une ligne du terminal, redessinée à chaque trame
+pub struct TunerDisplay<W: Write + Send> {
+    tuner: Tuner,
+    output: W,
+}
+
+impl TunerDisplay<io::Stdout> {
+    pub fn stdout(tuner: Tuner) -> Self {
+        TunerDisplay {
+            tuner,
+            output: io::stdout(),
+        }
+    }
+}
+
+impl<W: Write + Send> TunerDisplay<W> {
+    pub fn new(tuner: Tuner, output: W) -> Self {
+        TunerDisplay { tuner, output }
+    }
+
+    fn render(&mut self, reading: Option<&TunerReading>) -> io::Result<()> {
+        let line = match reading {
+            None => "  --".to_string(),
+            Some(reading) => {
+                let mut needle = vec!['─'; NEEDLE_WIDTH];
+                needle[NEEDLE_WIDTH / 2] = '┼';
+                let position = ((reading.cents.clamp(-50.0, 50.0) + 50.0) / 100.0
+                    * (NEEDLE_WIDTH - 1) as f32)
+                    .round() as usize;
+                needle[position] = '●';
+
+                // Motif qui défile vers la droite si la note est trop haute, vers la gauche
+                // si elle est trop basse, et s'immobilise quand elle est juste
+                let offset = reading.strobe_phase.floor().rem_euclid(4.0) as usize;
+                let strobe: String = (0..16)
+                    .map(|i| {
+                        if (i + 4 - offset) % 4 < 2 {
+                            '▮'
+                        } else {
+                            '▯'
+                        }
+                    })
+                    .collect();
+
+                let string = reading
+                    .string
+                    .map(|index| format!(" corde {}", index + 1))
+                    .unwrap_or_default();
+                let status = if reading.stable { "  juste" } else { "" };
+                format!(
+                    "  {:<4}{} {:8.2} Hz {:+6.1} cents  {}  {}{}",
+                    reading.name(),
+                    string,
+                    reading.frequency,
+                    reading.cents,
+                    needle.into_iter().collect::<String>(),
+                    strobe,
+                    status
+                )
+            }
+        };
+        write!(self.output, "\r\x1b[2K{}", line)?;
+        self.output.flush()
+    }
+}
+
+impl<W: Write + Send + 'static> EventSink for TunerDisplay<W> {
+    fn handle(&mut self, event: &Event) {
+        let Event::Pitch(frame) = event else {
+            return;
+        };
+        let reading = self
+            .tuner
+            .update(frame.pitch, frame.level_db, frame.timestamps.captured);
+        if let Err(err) = self.render(reading.as_ref()) {
+            log::error!("Erreur d'affichage de l'accordeur : {}", err);
+        }
+    }
+
+    fn finish(&mut self) {
+        let _ = writeln!(self.output);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// Fréquence à `cents` de la note MIDI `note` en La 440
+    fn frequency(note: u8, cents: f32) -> f32 {
+        440.0 * 2f32.powf((note as f32 - 69.0 + cents / 100.0) / 12.0)
+    }
+
+    fn pitch(frequency: f32) -> Option<Pitch> {
+        Some(Pitch {
+            frequency,
+            confidence: 0.9,
+        })
+    }
+
+    fn tuner(instrument: Instrument, smoothing: f32) -> Tuner {
+        Tuner::new(TunerConfig {
+            instrument,
+            smoothing,
+            ..TunerConfig::default()
+        })
+    }
+
+    #[test]
+    fn guitar_targets_the_nearest_string() {
+        let mut tuner = tuner(Instrument::Guitar, 0.0);
+        let now = Instant::now();
+        let reading = tuner.update(pitch(100.0), -20.0, now).unwrap();
+        assert_eq!((reading.note, reading.string), (45, Some(1)));
+        assert!(reading.cents < -100.0);
+        let reading = tuner
+            .update(pitch(frequency(64, 30.0)), -20.0, now)
+            .unwrap();
+        assert_eq!((reading.note, reading.string), (64, Some(5)));
+        assert!((reading.cents - 30.0).abs() < 0.01);
+        assert_eq!(reading.name(), "E4");
+
+        let mut chromatic = self::tuner(Instrument::Chromatic, 0.0);
+        let reading = chromatic.update(pitch(100.0), -20.0, now).unwrap();
+        assert_eq!((reading.note, reading.string), (43, None));
+    }
+
+    #[test]
+    fn smoothing_restarts_on_a_new_note() {
+        let mut tuner = tuner(Instrument::Chromatic, 0.7);
+        let now = Instant::now();
+        let first = tuner
+            .update(pitch(frequency(69, 20.0)), -20.0, now)
+            .unwrap();
+        assert!((first.cents - 20.0).abs() < 0.01);
+        let smoothed = tuner.update(pitch(440.0), -20.0, now).unwrap();
+        assert!((smoothed.cents - 14.0).abs() < 0.01, "{}", smoothed.cents);
+
+        let other = tuner
+            .update(pitch(frequency(70, -5.0)), -20.0, now)
+            .unwrap();
+        assert_eq!(other.note, 70);
+        assert!((other.cents + 5.0).abs() < 0.01, "{}", other.cents);
+    }
+
+    #[test]
+    fn stable_after_enough_frames_in_tune() {
+        let mut tuner = tuner(Instrument::Chromatic, 0.0);
+        let frames = tuner.config().stable_frames;
+        let now = Instant::now();
+        for _ in 1..frames {
+            let reading = tuner.update(pitch(frequency(69, 2.0)), -20.0, now).unwrap();
+            assert!(!reading.stable);
+        }
+        assert!(tuner.update(pitch(440.0), -20.0, now).unwrap().stable);
+        assert!(
+            !tuner
+                .update(pitch(frequency(69, 10.0)), -20.0, now)
+                .unwrap()
+                .stable
+        );
+        assert!(!tuner.update(pitch(440.0), -20.0, now).unwrap().stable);
+    }
+
+    #[test]
+    fn gate_and_missing_pitch_give_nothing() {
+        let mut tuner = tuner(Instrument::Chromatic, 0.0);
+        let now = Instant::now();
+        assert_eq!(tuner.update(pitch(440.0), -60.0, now), None);
+        assert_eq!(tuner.update(None, -20.0, now), None);
+        assert!(tuner.update(pitch(440.0), -20.0, now).is_some());
+    }
+
+    #[test]
+    fn strobe_stands_still_when_in_tune() {
+        let mut tuner = tuner(Instrument::Chromatic, 0.0);
+        let start = Instant::now();
+        for step in 0..10 {
+            let at = start + Duration::from_millis(100 * step);
+            let reading = tuner.update(pitch(440.0), -20.0, at).unwrap();
+            assert!(reading.strobe_phase.abs() < 1e-3);
+        }
+        // 10 cents trop haut pendant 1 s : 5 cases vers la droite
+        let mut phase = 0.0;
+        for step in 10..20 {
+            let at = start + Duration::from_millis(100 * step);
+            phase = tuner
+                .update(pitch(frequency(69, 10.0)), -20.0, at)
+                .unwrap()
+                .strobe_phase;
+        }
+        assert!((phase - 5.0).abs() < 0.01, "{}", phase);
+    }
+}
@@ -0,0 +1,640 @@
+// src/tuning.rs
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Note MIDI du La 4, référence par défaut
+const A4: u8 = 69;
+
+/// Note MIDI du Do 4, tonique par défaut des gammes
+const C4: u8 = 60;
+
+/// Tempéraments historiques à 12 notes, définis par l'écart en cents de chaque degré depuis Do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Temperament {
+    Equal,
+    Pythagorean,
+    QuarterCommaMeantone,
+    Werckmeister3,
+    Vallotti,
+    Kirnberger3,
+}
+
+impl Temperament {
+    fn cents(self) -> [f64; 12] {
+        match self {
+            Temperament::Equal => std::array::from_fn(|degree| degree as f64 * 100.0),
+            Temperament::Pythagorean => [
+                0.0, 90.22, 203.91, 294.13, 407.82, 498.04, 588.27, 701.96, 792.18, 905.87, 996.09,
+                1109.78,
+            ],
+            Temperament::QuarterCommaMeantone => [
+                0.0, 76.05, 193.16, 310.26, 386.31, 503.42, 579.47, 696.58, 772.63, 889.74,
+                1006.84, 1082.89,
+            ],
+            Temperament::Werckmeister3 => [
+                0.0, 90.22, 192.18, 294.13, 390.22, 498.04, 588.27, 696.09, 792.18, 888.27, 996.09,
+                1092.18,
+            ],
+            Temperament::Vallotti => [
+                0.0, 94.13, 196.09, 298.04, 392.18, 501.96, 592.18, 698.04, 796.09, 894.13, 1000.0,
+                1090.22,
+            ],
+            Temperament::Kirnberger3 => [
+                0.0, 90.22, 193.16, 294.13, 386.31, 498.04, 590.22, 696.58, 792.18, 889.74, 996.09,
+                1088.27,
+            ],
+        }
+    }
+
+    /// Nom utilisé en ligne de commande (`equal`, `pythagorean`, `meantone`, ...)
+    pub fn parse(name: &str) -> Option<Temperament> {
+        match name {
+            "equal" => Some(Temperament::Equal),
+            "pythagorean" => Some(Temperament::Pythagorean),
+            "meantone" => Some(Temperament::QuarterCommaMeantone),
+            "werckmeister3" => Some(Temperament::Werckmeister3),
+            "vallotti" => Some(Temperament::Vallotti),
+            "kirnberger3" => Some(Temperament::Kirnberger3),
+            _ => None,
+        }
+    }
+}
+
+/// Erreur de lecture d'un fichier Scala (.scl ou .kbm)
+#[derive(Debug)]
+pub enum ScalaError {
+    Io(io::Error),
+    /// Ligne (à partir de 1) et raison
+    Syntax {
+        line: usize,
+        reason: String,
+    },
+}
+
+impl fmt::Display for ScalaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScalaError::Io(err) => write!(f, "Lecture du fichier Scala impossible : {}", err),
+            ScalaError::Syntax { line, reason } => {
+                write!(f, "Fichier Scala invalide, ligne {} : {}", line, reason)
+            }
+        }
+    }
+}
+
+impl Error for ScalaError {}
+
+impl From<io::Error> for ScalaError {
+    fn from(err: io::Error) -> Self {
+        ScalaError::Io(err)
+    }
+}
+
+/// Gamme : rapports de fréquence des degrés 1 à N par rapport au degré 0,
+/// le dernier étant la période (l'octave 2/1 en général)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scale {
+    pub description: String,
+    pub ratios: Vec<f64>,
+}
+
+impl Scale {
+    /// Rapport du degré `degree` (0 = 1/1)
+    fn ratio(&self, degree: usize) -> f64 {
+        if degree == 0 {
+            1.0
+        } else {
+            self.ratios[degree - 1]
+        }
+    }
+
+    /// Lit une gamme au format Scala (.scl) : description, nombre de notes, puis un degré
+    /// par ligne en cents (avec un point) ou en rapport (`3/2`, `2`)
+    pub fn parse_scl(text: &str) -> Result<Scale, ScalaError> {
+        let mut lines = significant_lines(text);
+        let description = lines
+            .next()
+            .map(|(_, line)| line.to_string())
+            .unwrap_or_default();
+        let (line, count) = lines.next().ok_or(ScalaError::Syntax {
+            line: 0,
+            reason: "nombre de notes manquant".to_string(),
+        })?;
+        let count: usize = parse_field(line, first_word(count), "nombre de notes")?;
+
+        let mut ratios = Vec::with_capacity(count);
+        for (line, text) in lines.take(count) {
+            let pitch = first_word(text);
+            let ratio = if pitch.contains('.') {
+                let cents: f64 = parse_field(line, pitch, "valeur en cents")?;
+                2f64.powf(cents / 1200.0)
+            } else {
+                let (numerator, denominator) = pitch.split_once('/').unwrap_or((pitch, "1"));
+                let numerator: f64 = parse_field(line, numerator, "rapport")?;
+                let denominator: f64 = parse_field(line, denominator, "rapport")?;
+                numerator / denominator
+            };
+            if ratio.is_nan() || ratio <= 0.0 {
+                return Err(ScalaError::Syntax {
+                    line,
+                    reason: format!("rapport non positif : {}", pitch),
+                });
+            }
+            ratios.push(ratio);
+        }
+        if ratios.len() != count || count == 0 {
+            return Err(ScalaError::Syntax {
+                line: 0,
+                reason: format!("{} notes annoncées, {} lues", count, ratios.len()),
+            });
+        }
+        Ok(Scale {
+            description,
+            ratios,
+        })
+    }
+}
+
+/// Placement de la gamme sur le clavier MIDI (format Scala .kbm)
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyboardMapping {
+    /// Degré associé à chaque touche d'une période du clavier (`None` : touche muette) ;
+    /// vide pour un placement linéaire, une touche par degré
+    pub keys: Vec<Option<usize>>,
+    pub first_note: u8,
+    pub last_note: u8,
+    /// Touche du degré 0
+    pub middle_note: u8,
+    /// Touche dont la fréquence est donnée
+    pub reference_note: u8,
+    pub reference_frequency: f64,
+    /// Degré qui sert de période ; `None` : dernier degré de la gamme
+    pub octave_degree: Option<usize>,
+}
+
+impl KeyboardMapping {
+    /// Placement linéaire, degré 0 sur le Do 4 et `reference` Hz sur le La 4
+    pub fn linear(reference: f64) -> Self {
+        KeyboardMapping {
+            keys: Vec::new(),
+            first_note: 0,
+            last_note: 127,
+            middle_note: C4,
+            reference_note: A4,
+            reference_frequency: reference,
+            octave_degree: None,
+        }
+    }
+
+    /// Lit un placement au format Scala (.kbm)
+    pub fn parse_kbm(text: &str) -> Result<KeyboardMapping, ScalaError> {
+        let mut lines = significant_lines(text);
+        let mut field = |name: &str| {
+            lines
+                .next()
+                .map(|(line, text)| (line, first_word(text)))
+                .ok_or_else(|| ScalaError::Syntax {
+                    line: 0,
+                    reason: format!("{} manquant", name),
+                })
+        };
+
+        let (line, size) = field("taille du placement")?;
+        let size: usize = parse_field(line, size, "taille du placement")?;
+        let (line, first) = field("première note")?;
+        let first_note = parse_field(line, first, "première note")?;
+        let (line, last) = field("dernière note")?;
+        let last_note = parse_field(line, last, "dernière note")?;
+        let (line, middle) = field("note du degré 0")?;
+        let middle_note = parse_field(line, middle, "note du degré 0")?;
+        let (line, reference) = field("note de référence")?;
+        let reference_note = parse_field(line, reference, "note de référence")?;
+        let (line, frequency) = field("fréquence de référence")?;
+        let reference_frequency = parse_field(line, frequency, "fréquence de référence")?;
+        let (line, octave) = field("degré de période")?;
+        let octave: usize = parse_field(line, octave, "degré de période")?;
+
+        let mut keys = Vec::with_capacity(size);
+        for _ in 0..size {
+            // Les entrées absentes en fin de fichier sont des touches muettes
+            match field("degré") {
+                Ok((_, "x")) | Err(_) => keys.push(None),
+                Ok((line, degree)) => keys.push(Some(parse_field(line, degree, "degré")?)),
+            }
+        }
+
+        Ok(KeyboardMapping {
+            keys,
+            first_note,
+            last_note,
+            middle_note,
+            reference_note,
+            reference_frequency,
+            octave_degree: (octave > 0).then_some(octave),
+        })
+    }
+}
+
+/// Accord : fréquence de chaque note MIDI selon une gamme, un placement sur le clavier
+/// et une fréquence de référence. Sert à la détection (fréquence → note), au nommage
+/// (écart en cents) et à la synthèse (note → fréquence)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tuning {
+    /// Fréquence de chaque note MIDI, `None` pour une touche non placée
+    frequencies: Vec<Option<f32>>,
+    /// Notes placées, triées par fréquence croissante
+    sorted: Vec<(f32, u8)>,
+}
+
+impl Default for Tuning {
+    /// Tempérament égal, La 4 = 440 Hz
+    fn default() -> Self {
+        Tuning::equal(440.0)
+    }
+}
+
+impl Tuning {
+    /// Tempérament égal à 12 demi-tons, La 4 à `reference` Hz (415, 432, 442...)
+    pub fn equal(reference: f32) -> Self {
+        Tuning::temperament(Temperament::Equal, reference)
+    }
+
+    /// Tempérament historique à 12 notes à partir de Do, La 4 à `reference` Hz
+    pub fn temperament(temperament: Temperament, reference: f32) -> Self {
+        let ratios = temperament
+            .cents()
+            .iter()
+            .skip(1)
+            .map(|cents| 2f64.powf(cents / 1200.0))
+            .chain([2.0])
+            .collect();
+        Tuning::from_scale(
+            &Scale {
+                description: format!("{:?}", temperament),
+                ratios,
+            },
+            &KeyboardMapping::linear(reference as f64),
+        )
+    }
+
+    /// Intonation juste (limite 5) sur la tonique `tonic` (classe de hauteur, 0 = Do),
+    /// La 4 à `reference` Hz
+    pub fn just(tonic: u8, reference: f32) -> Self {
+        let ratios = [
+            16.0 / 15.0,
+            9.0 / 8.0,
+            6.0 / 5.0,
+            5.0 / 4.0,
+            4.0 / 3.0,
+            45.0 / 32.0,
+            3.0 / 2.0,
+            8.0 / 5.0,
+            5.0 / 3.0,
+            9.0 / 5.0,
+            15.0 / 8.0,
+            2.0,
+        ];
+        let mut mapping = KeyboardMapping::linear(reference as f64);
+        mapping.middle_note = C4 + tonic % 12;
+        Tuning::from_scale(
+            &Scale {
+                description: "Intonation juste".to_string(),
+                ratios: ratios.to_vec(),
+            },
+            &mapping,
+        )
+    }
+
+    /// Accord défini par des fichiers Scala ; sans .kbm, placement linéaire sur le Do 4
+    /// avec La 4 à 440 Hz
+    pub fn from_scala<P: AsRef<Path>>(scl: P, kbm: Option<P>) -> Result<Self, ScalaError> {
+        let scale = Scale::parse_scl(&fs::read_to_string(scl)?)?;
+        let mapping = match kbm {
+            Some(path) => KeyboardMapping::parse_kbm(&fs::read_to_string(path)?)?,
+            None => KeyboardMapping::linear(440.0),
+        };
+        Ok(Tuning::from_scale(&scale, &mapping))
+    }
+
+    /// Calcule la fréquence de chaque note MIDI
+    pub fn from_scale(scale: &Scale, mapping: &KeyboardMapping) -> Self {
+        let degrees = scale.ratios.len();
+        let period = scale.ratio(mapping.octave_degree.unwrap_or(degrees).min(degrees));
+        let keys_per_period = if mapping.keys.is_empty() {
+            degrees
+        } else {
+            mapping.keys.len()
+        };
+
+        // Rapport de chaque note par rapport au degré 0, avant calage sur la référence
+        let relative = |note: u8| -> Option<f64> {
+            let steps = note as i64 - mapping.middle_note as i64;
+            let octave = steps.div_euclid(keys_per_period as i64);
+            let index = steps.rem_euclid(keys_per_period as i64) as usize;
+            let degree = if mapping.keys.is_empty() {
+                Some(index)
+            } else {
+                mapping.keys[index]
+            }?;
+            // Un degré au-delà de la gamme se prolonge sur les périodes suivantes
+            let ratio = scale.ratio(degree % degrees) * period.powi((degree / degrees) as i32);
+            Some(ratio * period.powi(octave as i32))
+        };
+
+        let reference = relative(mapping.reference_note).unwrap_or(1.0);
+        let frequencies: Vec<Option<f32>> = (0..=127u8)
+            .map(|note| {
+                if note < mapping.first_note || note > mapping.last_note {
+                    return None;
+                }
+                relative(note).map(|r| (mapping.reference_frequency * r / reference) as f32)
+            })
+            .collect();
+
+        let mut sorted: Vec<(f32, u8)> = frequencies
+            .iter()
+            .enumerate()
+            .filter_map(|(note, frequency)| frequency.map(|f| (f, note as u8)))
+            .collect();
+        sorted.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Tuning {
+            frequencies,
+            sorted,
+        }
+    }
+
+    /// Fréquence d'une note MIDI, `None` hors clavier ou pour une touche non placée
+    pub fn frequency(&self, note: u8) -> Option<f32> {
+        self.frequencies.get(note as usize).copied().flatten()
+    }
+
+    /// Note MIDI la plus proche d'une fréquence (à l'échelle logarithmique)
+    pub fn note(&self, frequency: f32) -> Option<u8> {
+        if frequency <= 0.0 || self.sorted.is_empty() {
+            return None;
+        }
+        let index = self.sorted.partition_point(|(f, _)| *f < frequency);
+        let below = index.checked_sub(1).map(|i| self.sorted[i]);
+        let above = self.sorted.get(index).copied();
+        let nearest = match (below, above) {
+            (Some(low), Some(high)) => {
+                if frequency / low.0 < high.0 / frequency {
+                    low
+                } else {
+                    high
+                }
+            }
+            (Some(only), None) | (None, Some(only)) => only,
+            (None, None) => return None,
+        };
+        // Au-delà d'un demi-ton des notes extrêmes, la fréquence est hors clavier
+        let cents = 1200.0 * (frequency / nearest.0).log2();
+        (cents.abs() <= 50.0 || (index > 0 && index < self.sorted.len())).then_some(nearest.1)
+    }
+
+    /// Écart en cents entre une fréquence et la fréquence d'une note
+    pub fn cents(&self, frequency: f32, note: u8) -> f32 {
+        match self.frequency(note) {
+            Some(nominal) if frequency > 0.0 => 1200.0 * (frequency / nominal).log2(),
+            _ => 0.0,
+        }
+    }
+}
+
+/// Lignes utiles d'un fichier Scala (numérotées à partir de 1), sans les commentaires `!`
+fn significant_lines(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.starts_with('!'))
+}
+
+fn first_word(text: &str) -> &str {
+    text.split_whitespace().next().unwrap_or("")
+}
+
+fn parse_field<T: std::str::FromStr>(line: usize, text: &str, name: &str) -> Result<T, ScalaError> {
+    text.parse().map_err(|_| ScalaError::Syntax {
+        line,
+        reason: format!("{} invalide : {:?}", name, text),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PENTATONIC_SCL: &str = "! penta.scl
+!
+Pentatonique juste
+ 5
+!
+ 9/8
+ 5/4
+ 3/2
+ 5/3   la
+ 2
+";
+
+    /// Pentatonique sur les touches blanches Do, Ré, Mi, Sol, La ; La 4 à 440 Hz
+    const PENTATONIC_KBM: &str = "! penta.kbm
+12
+0
+127
+60
+69
+440.0
+5
+! Degré de chaque touche à partir du Do
+0
+x
+1
+x
+2
+x
+x
+3
+x
+4
+";
+
+    fn assert_hz(actual: Option<f32>, expected: f64) {
+        let actual = actual.expect("note placée") as f64;
+        assert!(
+            (actual - expected).abs() < 1e-3 * expected,
+            "{} Hz au lieu de {} Hz",
+            actual,
+            expected
+        );
+    }
+
+    fn syntax_line(result: Result<impl fmt::Debug, ScalaError>) -> usize {
+        match result {
+            Err(ScalaError::Syntax { line, .. }) => line,
+            other => panic!("erreur de syntaxe attendue : {:?}", other),
+        }
+    }
+
+    #[test]
+    fn equal_temperament() {
+        let tuning = Tuning::default();
+        assert_hz(tuning.frequency(A4), 440.0);
+        assert_hz(tuning.frequency(C4), 261.626);
+        assert_hz(tuning.frequency(0), 8.176);
+        assert_eq!(tuning.note(445.0), Some(A4));
+        assert!((tuning.cents(445.0, A4) - 19.56).abs() < 0.01);
+        assert_eq!(tuning.note(0.0), None);
+        // Au-delà d'un demi-ton sous la note la plus grave : hors clavier
+        assert_eq!(tuning.note(7.5), None);
+        assert_hz(Tuning::equal(415.0).frequency(A4), 415.0);
+    }
+
+    #[test]
+    fn historical_temperaments_keep_the_reference() {
+        for (temperament, e4_cents) in [
+            (Temperament::Pythagorean, 407.82),
+            (Temperament::QuarterCommaMeantone, 386.31),
+            (Temperament::Werckmeister3, 390.22),
+        ] {
+            let tuning = Tuning::temperament(temperament, 440.0);
+            assert_hz(tuning.frequency(A4), 440.0);
+            let c4 = tuning.frequency(C4).unwrap() as f64;
+            assert_hz(tuning.frequency(64), c4 * 2f64.powf(e4_cents / 1200.0));
+            assert_hz(tuning.frequency(72), c4 * 2.0);
+        }
+        assert_eq!(
+            Temperament::parse("meantone"),
+            Some(Temperament::QuarterCommaMeantone)
+        );
+        assert_eq!(Temperament::parse("mesotonique"), None);
+    }
+
+    #[test]
+    fn just_intonation_on_a_tonic() {
+        // Do majeur juste, La = 5/3 de Do
+        let tuning = Tuning::just(0, 440.0);
+        assert_hz(tuning.frequency(C4), 264.0);
+        assert_hz(tuning.frequency(67), 396.0);
+        // Sur Ré, le La est la quinte juste
+        let tuning = Tuning::just(2, 440.0);
+        assert_hz(tuning.frequency(62), 440.0 / 1.5);
+    }
+
+    #[test]
+    fn parse_scl_reads_cents_and_ratios() {
+        let scale = Scale::parse_scl("Quarts de ton\n3\n150.0\n3/2\n1200.0 octave\n").unwrap();
+        assert_eq!(scale.description, "Quarts de ton");
+        assert_eq!(scale.ratios.len(), 3);
+        assert!((scale.ratios[0] - 2f64.powf(0.125)).abs() < 1e-12);
+        assert_eq!(scale.ratios[1], 1.5);
+        assert!((scale.ratios[2] - 2.0).abs() < 1e-12);
+
+        let scale = Scale::parse_scl(PENTATONIC_SCL).unwrap();
+        assert_eq!(scale.description, "Pentatonique juste");
+        assert_eq!(scale.ratios, vec![1.125, 1.25, 1.5, 5.0 / 3.0, 2.0]);
+    }
+
+    #[test]
+    fn parse_scl_errors() {
+        assert_eq!(syntax_line(Scale::parse_scl("Vide\n")), 0);
+        assert_eq!(syntax_line(Scale::parse_scl("Titre\ndouze\n")), 2);
+        assert_eq!(
+            syntax_line(Scale::parse_scl("Titre\n3\n9/8\nabc\n2/1\n")),
+            4
+        );
+        assert_eq!(syntax_line(Scale::parse_scl("Titre\n2\n-3/2\n2/1\n")), 3);
+        assert_eq!(syntax_line(Scale::parse_scl("Titre\n2\n3/0.5.\n2/1\n")), 3);
+        // Moins de notes que annoncé, ou aucune
+        assert_eq!(syntax_line(Scale::parse_scl("Titre\n3\n9/8\n2/1\n")), 0);
+        assert_eq!(syntax_line(Scale::parse_scl("Titre\n0\n")), 0);
+    }
+
+    #[test]
+    fn parse_kbm_reads_the_mapping() {
+        let mapping = KeyboardMapping::parse_kbm(PENTATONIC_KBM).unwrap();
+        assert_eq!(mapping.keys.len(), 12);
+        assert_eq!(&mapping.keys[..4], &[Some(0), None, Some(1), None]);
+        // Les entrées manquantes en fin de fichier sont muettes
+        assert_eq!(&mapping.keys[9..], &[Some(4), None, None]);
+        assert_eq!((mapping.first_note, mapping.last_note), (0, 127));
+        assert_eq!((mapping.middle_note, mapping.reference_note), (60, 69));
+        assert_eq!(mapping.reference_frequency, 440.0);
+        assert_eq!(mapping.octave_degree, Some(5));
+    }
+
+    #[test]
+    fn parse_kbm_errors() {
+        assert_eq!(syntax_line(KeyboardMapping::parse_kbm("12\n0\n")), 0);
+        assert_eq!(
+            syntax_line(KeyboardMapping::parse_kbm("12\n0\n127\n60\nla\n")),
+            5
+        );
+        assert_eq!(
+            syntax_line(KeyboardMapping::parse_kbm("1\n0\n300\n60\n69\n440\n0\n")),
+            3
+        );
+        assert_eq!(
+            syntax_line(KeyboardMapping::parse_kbm(
+                "2\n0\n127\n60\n69\n440.0\n0\n0\n?\n"
+            )),
+            9
+        );
+    }
+
+    #[test]
+    fn scala_tuning_places_degrees_on_keys() {
+        let scale = Scale::parse_scl(PENTATONIC_SCL).unwrap();
+        let mapping = KeyboardMapping::parse_kbm(PENTATONIC_KBM).unwrap();
+        let tuning = Tuning::from_scale(&scale, &mapping);
+        assert_hz(tuning.frequency(69), 440.0);
+        assert_hz(tuning.frequency(C4), 264.0);
+        assert_hz(tuning.frequency(62), 297.0);
+        assert_hz(tuning.frequency(72), 528.0);
+        assert_hz(tuning.frequency(57), 220.0);
+        assert_eq!(tuning.frequency(61), None);
+        // Les touches muettes ne sont jamais reconnues
+        assert_eq!(tuning.note(285.0), Some(62));
+        assert_eq!(tuning.note(275.0), Some(C4));
+    }
+
+    #[test]
+    fn keyboard_range_limits_the_notes() {
+        let scale = Scale::parse_scl(PENTATONIC_SCL).unwrap();
+        let mut mapping = KeyboardMapping::linear(440.0);
+        mapping.first_note = 60;
+        mapping.last_note = 70;
+        mapping.reference_note = 60;
+        mapping.reference_frequency = 264.0;
+        let tuning = Tuning::from_scale(&scale, &mapping);
+        assert_eq!(tuning.frequency(59), None);
+        assert_eq!(tuning.frequency(71), None);
+        // Placement linéaire : une touche par degré
+        assert_hz(tuning.frequency(61), 297.0);
+        assert_hz(tuning.frequency(65), 528.0);
+        assert_eq!(tuning.note(100.0), None);
+    }
+
+    #[test]
+    fn from_scala_reads_files() {
+        let directory = std::env::temp_dir();
+        let scl = directory.join(format!("tuning-{}.scl", std::process::id()));
+        let kbm = directory.join(format!("tuning-{}.kbm", std::process::id()));
+        fs::write(&scl, PENTATONIC_SCL).unwrap();
+        fs::write(&kbm, PENTATONIC_KBM).unwrap();
+
+        let tuning = Tuning::from_scala(&scl, Some(&kbm)).unwrap();
+        assert_hz(tuning.frequency(62), 297.0);
+        // Sans .kbm : placement linéaire, La 4 à 440 Hz
+        let linear = Tuning::from_scala(&scl, None).unwrap();
+        assert_hz(linear.frequency(A4), 440.0);
+        let missing = Tuning::from_scala(&directory.join("absent.scl"), None);
+        assert!(matches!(missing, Err(ScalaError::Io(_))));
+
+        fs::remove_file(scl).unwrap();
+        fs::remove_file(kbm).unwrap();
+    }
+}
@@ -1,38 +1,42 @@
 // src/utils.rs
 
+use crate::tuning::Tuning;
+use std::sync::OnceLock;
+
+/// Accord par défaut des fonctions ci-dessous : tempérament égal, La 4 = 440 Hz.
+/// Le pipeline utilise le `Tuning` de sa configuration
+fn standard_tuning() -> &'static Tuning {
+    static STANDARD: OnceLock<Tuning> = OnceLock::new();
+    STANDARD.get_or_init(Tuning::default)
+}
+
 /// Convertit une fréquence en numéro de note MIDI
 pub fn frequency_to_midi_note_number(freq: f32) -> Option<u8> {
-    if freq <= 0.0 {
-        return None;
-    }
-    let a4 = 440.0;
-    let a4_midi = 69.0;
-    let midi_num = 12.0 * (freq / a4).log2() + a4_midi;
-    let midi_num_rounded = midi_num.round() as i32;
-    if midi_num_rounded < 0 || midi_num_rounded > 127 {
-        None
-    } else {
-        Some(midi_num_rounded as u8)
-    }
+    standard_tuning().note(freq)
 }
 
 /// Convertit un numéro de note MIDI en fréquence (Hz)
 pub fn frequency_to_midi_note_number_to_freq(note_number: u8) -> Option<f32> {
-    if note_number > 127 {
-        return None;
-    }
-    let a4 = 69.0;
-    let a4_freq = 440.0;
-    Some(a4_freq * 2.0_f32.powf((note_number as f32 - a4) / 12.0))
+    standard_tuning().frequency(note_number)
+}
+
+/// Écart en cents entre une fréquence et la fréquence nominale d'une note MIDI
+pub fn cents_offset(freq: f32, note_number: u8) -> f32 {
+    standard_tuning().cents(freq, note_number)
+}
+
+/// Noms des 12 classes de hauteur, à partir de Do
+const NOTE_NAMES: [&str; 12] = [
+    "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B",
+];
+
+/// Nom d'une classe de hauteur (0 = C, 9 = A)
+pub fn pitch_class_name(pitch_class: u8) -> &'static str {
+    NOTE_NAMES[(pitch_class % 12) as usize]
 }
 
 /// Convertit un numéro de note MIDI en nom de note (ex. C4, A#3)
 pub fn midi_note_number_to_name(note_number: u8) -> String {
-    let note_names = [
-        "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B",
-    ];
-    let note_index = note_number % 12;
     let octave = (note_number / 12).saturating_sub(1); // Utiliser saturating_sub pour éviter le sous-dépassement
-    let note_name = note_names[note_index as usize];
-    format!("{}{}", note_name, octave)
+    format!("{}{}", pitch_class_name(note_number), octave)
 }